@@ -0,0 +1,83 @@
+mod date;
+mod png;
+mod scene;
+mod svg;
+mod ticks;
+
+use write_model::value_object::XValue;
+
+pub use self::png::render_png;
+pub use self::scene::{layout, Color, Element, Scene, TextAnchor};
+pub use self::svg::{escape, render_svg};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("encode png")]
+    EncodePng(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("invalid size")]
+    InvalidSize,
+    #[error("unknown theme")]
+    UnknownTheme,
+}
+
+/// The chart to render. Each line is drawn in its own color
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    pub lines: Vec<Line>,
+    pub title: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    /// Shown in the legend. `None` is not shown
+    pub name: Option<String>,
+    pub points: Vec<Point>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x_value: XValue,
+    pub y_value: f64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Options {
+    /// The first date of the x-axis. `None` starts at the first point
+    pub from: Option<XValue>,
+    pub height: u32,
+    pub theme: Theme,
+    /// The date after the last date of the x-axis (exclusive). `None` ends at the last point
+    pub to: Option<XValue>,
+    pub width: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            from: None,
+            height: 480,
+            theme: Theme::default(),
+            to: None,
+            width: 640,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Theme {
+    Dark,
+    #[default]
+    Light,
+}
+
+impl std::str::FromStr for Theme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => Err(Error::UnknownTheme),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use write_model::value_object::XValue;
+
+/// Returns the number of days since 1970-01-01
+pub(crate) fn days_from_x_value(x_value: XValue) -> i64 {
+    i64::from(x_value.days_since_epoch())
+}
+
+/// Returns the date `days` days after 1970-01-01. `None` if the date is out of `0000-01-01..=9999-12-31`
+pub(crate) fn x_value_from_days(days: i64) -> Option<XValue> {
+    i32::try_from(days)
+        .ok()
+        .and_then(XValue::from_days_since_epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_days_conversion() -> anyhow::Result<()> {
+        for (s, days) in [
+            ("1970-01-01", 0),
+            ("1969-12-31", -1),
+            ("2000-03-01", 11017),
+            ("2020-02-29", 18321),
+        ] {
+            let x_value = XValue::from_str(s)?;
+            assert_eq!(days_from_x_value(x_value), days);
+            assert_eq!(x_value_from_days(days), Some(x_value));
+        }
+        assert_eq!(
+            x_value_from_days(days_from_x_value(XValue::from_str("9999-12-31")?) + 1),
+            None
+        );
+        Ok(())
+    }
+}
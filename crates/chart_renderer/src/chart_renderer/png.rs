@@ -0,0 +1,284 @@
+use ab_glyph::{Font as _, FontRef, OutlineCurve};
+use tiny_skia::{FillRule, LineJoin, Paint, Path, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+use crate::chart_renderer::{
+    scene::{layout, Color, Element, TextAnchor},
+    Chart, Error, Options,
+};
+
+/// Renders the chart as a PNG image. Characters not covered by the bundled font are drawn as boxes
+pub fn render_png(chart: &Chart, options: &Options) -> Result<Vec<u8>, Error> {
+    let scene = layout(chart, options);
+    let mut pixmap = Pixmap::new(scene.width, scene.height).ok_or(Error::InvalidSize)?;
+    let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)
+        .expect("bundled font to be valid");
+    for element in &scene.elements {
+        match element {
+            Element::Circle {
+                center,
+                color,
+                radius,
+            } => {
+                if let Some(path) =
+                    PathBuilder::from_circle(center.0 as f32, center.1 as f32, *radius as f32)
+                {
+                    fill(&mut pixmap, &path, *color);
+                }
+            }
+            Element::Line {
+                color,
+                from,
+                to,
+                width,
+            } => {
+                if let Some(path) = polyline_path(&[*from, *to]) {
+                    stroke(&mut pixmap, &path, *color, *width);
+                }
+            }
+            Element::Polyline {
+                color,
+                points,
+                width,
+            } => {
+                if let Some(path) = polyline_path(points) {
+                    stroke(&mut pixmap, &path, *color, *width);
+                }
+            }
+            Element::Rect {
+                color,
+                size,
+                top_left,
+            } => {
+                if let Some(rect) = Rect::from_xywh(
+                    top_left.0 as f32,
+                    top_left.1 as f32,
+                    size.0 as f32,
+                    size.1 as f32,
+                ) {
+                    pixmap.fill_rect(rect, &paint(*color), Transform::identity(), None);
+                }
+            }
+            Element::Text {
+                anchor,
+                color,
+                position,
+                size,
+                text,
+            } => {
+                if let Some(path) = text_path(&font, text, *position, *size, *anchor) {
+                    fill(&mut pixmap, &path, *color);
+                }
+            }
+        }
+    }
+    pixmap
+        .encode_png()
+        .map_err(|e| Error::EncodePng(Box::new(e)))
+}
+
+fn fill(pixmap: &mut Pixmap, path: &Path, color: Color) {
+    pixmap.fill_path(
+        path,
+        &paint(color),
+        FillRule::Winding,
+        Transform::identity(),
+        None,
+    );
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.0, color.1, color.2, 0xff);
+    paint.anti_alias = true;
+    paint
+}
+
+fn polyline_path(points: &[(f64, f64)]) -> Option<Path> {
+    let (first, rest) = points.split_first()?;
+    let mut builder = PathBuilder::new();
+    builder.move_to(first.0 as f32, first.1 as f32);
+    for point in rest {
+        builder.line_to(point.0 as f32, point.1 as f32);
+    }
+    builder.finish()
+}
+
+fn stroke(pixmap: &mut Pixmap, path: &Path, color: Color, width: f64) {
+    pixmap.stroke_path(
+        path,
+        &paint(color),
+        &Stroke {
+            line_join: LineJoin::Round,
+            width: width as f32,
+            ..Stroke::default()
+        },
+        Transform::identity(),
+        None,
+    );
+}
+
+/// Converts the glyph outlines of the text to a path. `position` is the baseline point of the `anchor`
+fn text_path(
+    font: &FontRef,
+    text: &str,
+    position: (f64, f64),
+    size: f64,
+    anchor: TextAnchor,
+) -> Option<Path> {
+    let scale = size as f32 / font.units_per_em()?;
+    let glyph_ids = text.chars().map(|c| font.glyph_id(c)).collect::<Vec<_>>();
+    // the offsets of the glyphs in font units
+    let mut offsets = Vec::with_capacity(glyph_ids.len());
+    let mut advance = 0.0;
+    for (index, glyph_id) in glyph_ids.iter().enumerate() {
+        if index > 0 {
+            advance += font.kern_unscaled(glyph_ids[index - 1], *glyph_id);
+        }
+        offsets.push(advance);
+        advance += font.h_advance_unscaled(*glyph_id);
+    }
+    let left = position.0 as f32
+        - match anchor {
+            TextAnchor::End => advance * scale,
+            TextAnchor::Middle => advance * scale / 2.0,
+            TextAnchor::Start => 0.0,
+        };
+    let baseline = position.1 as f32;
+
+    let mut builder = PathBuilder::new();
+    for (glyph_id, offset) in glyph_ids.into_iter().zip(offsets) {
+        let Some(outline) = font.outline(glyph_id) else {
+            continue;
+        };
+        // font units are y-up
+        let to = |point: ab_glyph::Point| {
+            (
+                left + (offset + point.x) * scale,
+                baseline - point.y * scale,
+            )
+        };
+        let mut last = None;
+        for curve in outline.curves {
+            let (start, end) = match curve {
+                OutlineCurve::Line(start, end)
+                | OutlineCurve::Quad(start, _, end)
+                | OutlineCurve::Cubic(start, _, _, end) => (start, end),
+            };
+            // a curve not continuing the previous one starts a new contour
+            if last != Some(start) {
+                if last.is_some() {
+                    builder.close();
+                }
+                let (x, y) = to(start);
+                builder.move_to(x, y);
+            }
+            match curve {
+                OutlineCurve::Line(_, p1) => {
+                    let (x, y) = to(p1);
+                    builder.line_to(x, y);
+                }
+                OutlineCurve::Quad(_, p1, p2) => {
+                    let ((x1, y1), (x, y)) = (to(p1), to(p2));
+                    builder.quad_to(x1, y1, x, y);
+                }
+                OutlineCurve::Cubic(_, p1, p2, p3) => {
+                    let ((x1, y1), (x2, y2), (x, y)) = (to(p1), to(p2), to(p3));
+                    builder.cubic_to(x1, y1, x2, y2, x, y);
+                }
+            }
+            last = Some(end);
+        }
+        if last.is_some() {
+            builder.close();
+        }
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::value_object::XValue;
+
+    use crate::chart_renderer::{Line, Point, Theme};
+
+    use super::*;
+
+    fn chart() -> anyhow::Result<Chart> {
+        Ok(Chart {
+            lines: vec![Line {
+                name: Some("name1".to_string()),
+                points: vec![
+                    Point {
+                        x_value: XValue::from_str("2020-01-01")?,
+                        y_value: 1.0,
+                    },
+                    Point {
+                        x_value: XValue::from_str("2020-01-02")?,
+                        y_value: 2.5,
+                    },
+                ],
+            }],
+            title: "title1".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_render_png() -> anyhow::Result<()> {
+        for (theme, background) in [(Theme::Light, [0xff; 3]), (Theme::Dark, [0x1e; 3])] {
+            let png = render_png(
+                &chart()?,
+                &Options {
+                    height: 200,
+                    theme,
+                    width: 300,
+                    ..Options::default()
+                },
+            )?;
+            let pixmap = Pixmap::decode_png(&png)?;
+            assert_eq!((pixmap.width(), pixmap.height()), (300, 200));
+            let pixel = pixmap.pixel(0, 0).expect("pixel to exist");
+            assert_eq!(
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()],
+                [background[0], background[1], background[2], 0xff]
+            );
+            // something other than the background is drawn
+            assert!(pixmap
+                .pixels()
+                .iter()
+                .any(|pixel| pixel.red() != background[0]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_png_invalid_size() -> anyhow::Result<()> {
+        assert!(matches!(
+            render_png(
+                &chart()?,
+                &Options {
+                    width: 0,
+                    ..Options::default()
+                },
+            ),
+            Err(Error::InvalidSize)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_path() -> anyhow::Result<()> {
+        let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)?;
+        let bounds = |anchor: TextAnchor| {
+            text_path(&font, "abc", (100.0, 50.0), 10.0, anchor).map(|path| path.bounds())
+        };
+        let start = bounds(TextAnchor::Start).expect("path to exist");
+        let end = bounds(TextAnchor::End).expect("path to exist");
+        assert!(start.left() >= 100.0 && start.bottom() <= 50.5);
+        assert!(end.right() <= 100.0);
+        assert!(bounds(TextAnchor::Middle).is_some());
+        assert!(text_path(&font, " ", (0.0, 0.0), 10.0, TextAnchor::Start).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+use crate::chart_renderer::{
+    date::days_from_x_value,
+    ticks::{x_ticks, y_ticks},
+    Chart, Options, Theme,
+};
+
+const LINE_COLORS: [Color; 6] = [
+    Color(0x1f, 0x77, 0xb4),
+    Color(0xff, 0x7f, 0x0e),
+    Color(0x2c, 0xa0, 0x2c),
+    Color(0xd6, 0x27, 0x28),
+    Color(0x94, 0x67, 0xbd),
+    Color(0x8c, 0x56, 0x4b),
+];
+
+const FONT_SIZE: f64 = 11.0;
+const TITLE_FONT_SIZE: f64 = 16.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextAnchor {
+    End,
+    Middle,
+    Start,
+}
+
+/// A drawing primitive. Coordinates are in pixels from the top-left corner
+#[derive(Clone, Debug, PartialEq)]
+pub enum Element {
+    Circle {
+        center: (f64, f64),
+        color: Color,
+        radius: f64,
+    },
+    Line {
+        color: Color,
+        from: (f64, f64),
+        to: (f64, f64),
+        width: f64,
+    },
+    Polyline {
+        color: Color,
+        points: Vec<(f64, f64)>,
+        width: f64,
+    },
+    Rect {
+        color: Color,
+        size: (f64, f64),
+        top_left: (f64, f64),
+    },
+    /// `position` is the baseline point of the `anchor`
+    Text {
+        anchor: TextAnchor,
+        color: Color,
+        position: (f64, f64),
+        size: f64,
+        text: String,
+    },
+}
+
+/// The chart laid out as drawing primitives, shared by the image formats
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene {
+    /// Painted in order
+    pub elements: Vec<Element>,
+    pub height: u32,
+    pub title: String,
+    pub width: u32,
+}
+
+struct Palette {
+    axis: Color,
+    background: Color,
+    grid: Color,
+    text: Color,
+}
+
+impl Palette {
+    fn of(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self {
+                axis: Color(0xcc, 0xcc, 0xcc),
+                background: Color(0x1e, 0x1e, 0x1e),
+                grid: Color(0x44, 0x44, 0x44),
+                text: Color(0xdd, 0xdd, 0xdd),
+            },
+            Theme::Light => Self {
+                axis: Color(0x33, 0x33, 0x33),
+                background: Color(0xff, 0xff, 0xff),
+                grid: Color(0xdd, 0xdd, 0xdd),
+                text: Color(0x33, 0x33, 0x33),
+            },
+        }
+    }
+}
+
+pub fn layout(chart: &Chart, options: &Options) -> Scene {
+    let palette = Palette::of(options.theme);
+    let (width, height) = (f64::from(options.width), f64::from(options.height));
+    let mut elements = vec![Element::Rect {
+        color: palette.background,
+        size: (width, height),
+        top_left: (0.0, 0.0),
+    }];
+    elements.push(Element::Text {
+        anchor: TextAnchor::Middle,
+        color: palette.text,
+        position: (width / 2.0, 8.0 + TITLE_FONT_SIZE),
+        size: TITLE_FONT_SIZE,
+        text: chart.title.clone(),
+    });
+
+    let named_lines = chart
+        .lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| line.name.as_ref().map(|name| (index, name)))
+        .collect::<Vec<_>>();
+    let (left, right) = (56.0, width - 16.0);
+    let top = if named_lines.is_empty() { 40.0 } else { 60.0 };
+    let bottom = height - 32.0;
+
+    // legend
+    let mut legend_x = left;
+    for (index, name) in named_lines {
+        let color = LINE_COLORS[index % LINE_COLORS.len()];
+        let y = top - 14.0;
+        elements.push(Element::Line {
+            color,
+            from: (legend_x, y - FONT_SIZE / 3.0),
+            to: (legend_x + 16.0, y - FONT_SIZE / 3.0),
+            width: 2.0,
+        });
+        elements.push(Element::Text {
+            anchor: TextAnchor::Start,
+            color: palette.text,
+            position: (legend_x + 20.0, y),
+            size: FONT_SIZE,
+            text: name.clone(),
+        });
+        // approximates the text width
+        legend_x += 20.0 + name.chars().count() as f64 * FONT_SIZE * 0.6 + 16.0;
+    }
+
+    let points = chart
+        .lines
+        .iter()
+        .flat_map(|line| line.points.iter())
+        .filter(|point| point.y_value.is_finite())
+        .collect::<Vec<_>>();
+    let first_day = options
+        .from
+        .map(days_from_x_value)
+        .or_else(|| {
+            points
+                .iter()
+                .map(|point| days_from_x_value(point.x_value))
+                .min()
+        })
+        .unwrap_or(0);
+    let last_day = options
+        .to
+        .map(|to| days_from_x_value(to) - 1)
+        .or_else(|| {
+            points
+                .iter()
+                .map(|point| days_from_x_value(point.x_value))
+                .max()
+        })
+        .unwrap_or(first_day)
+        .max(first_day);
+    // a single day is drawn in the middle
+    let (x_min, x_max) = if first_day == last_day {
+        (first_day as f64 - 1.0, last_day as f64 + 1.0)
+    } else {
+        (first_day as f64, last_day as f64)
+    };
+    let y_min = points
+        .iter()
+        .map(|point| point.y_value)
+        .fold(f64::INFINITY, f64::min);
+    let y_max = points
+        .iter()
+        .map(|point| point.y_value)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let ((y_lower, y_upper), y_ticks) = if points.is_empty() {
+        y_ticks(0.0, 1.0)
+    } else {
+        y_ticks(y_min, y_max)
+    };
+    let to_x = |days: f64| left + (days - x_min) / (x_max - x_min) * (right - left);
+    let to_y = |value: f64| bottom - (value - y_lower) / (y_upper - y_lower) * (bottom - top);
+
+    // y-axis
+    for (value, label) in y_ticks {
+        let y = to_y(value);
+        elements.push(Element::Line {
+            color: palette.grid,
+            from: (left, y),
+            to: (right, y),
+            width: 1.0,
+        });
+        elements.push(Element::Text {
+            anchor: TextAnchor::End,
+            color: palette.text,
+            position: (left - 6.0, y + FONT_SIZE / 3.0),
+            size: FONT_SIZE,
+            text: label,
+        });
+    }
+    elements.push(Element::Line {
+        color: palette.axis,
+        from: (left, top),
+        to: (left, bottom),
+        width: 1.0,
+    });
+
+    // x-axis
+    elements.push(Element::Line {
+        color: palette.axis,
+        from: (left, bottom),
+        to: (right, bottom),
+        width: 1.0,
+    });
+    for (days, label) in x_ticks(first_day, last_day) {
+        let x = to_x(days as f64);
+        elements.push(Element::Line {
+            color: palette.axis,
+            from: (x, bottom),
+            to: (x, bottom + 4.0),
+            width: 1.0,
+        });
+        elements.push(Element::Text {
+            anchor: TextAnchor::Middle,
+            color: palette.text,
+            position: (x, bottom + 6.0 + FONT_SIZE),
+            size: FONT_SIZE,
+            text: label,
+        });
+    }
+
+    // lines
+    for (index, line) in chart.lines.iter().enumerate() {
+        let color = LINE_COLORS[index % LINE_COLORS.len()];
+        let mut line_points = line
+            .points
+            .iter()
+            .filter(|point| point.y_value.is_finite())
+            .map(|point| (days_from_x_value(point.x_value), point.y_value))
+            .filter(|(days, _)| (first_day..=last_day).contains(days))
+            .map(|(days, value)| (to_x(days as f64), to_y(value)))
+            .collect::<Vec<(f64, f64)>>();
+        line_points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        if line_points.len() >= 2 {
+            elements.push(Element::Polyline {
+                color,
+                points: line_points.clone(),
+                width: 2.0,
+            });
+        }
+        for center in line_points {
+            elements.push(Element::Circle {
+                center,
+                color,
+                radius: 3.0,
+            });
+        }
+    }
+
+    Scene {
+        elements,
+        height: options.height,
+        title: chart.title.clone(),
+        width: options.width,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::value_object::XValue;
+
+    use crate::chart_renderer::{Line, Point};
+
+    use super::*;
+
+    #[test]
+    fn test_layout() -> anyhow::Result<()> {
+        let chart = Chart {
+            lines: vec![Line {
+                name: None,
+                points: vec![
+                    Point {
+                        x_value: XValue::from_str("2020-01-03")?,
+                        y_value: 10.0,
+                    },
+                    Point {
+                        x_value: XValue::from_str("2020-01-01")?,
+                        y_value: 0.0,
+                    },
+                ],
+            }],
+            title: "title1".to_string(),
+        };
+        let scene = layout(&chart, &Options::default());
+        let polylines = scene
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                Element::Polyline { points, .. } => Some(points.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // sorted by date, from the bottom-left to the top-right of the plot area
+        assert_eq!(polylines, vec![vec![(56.0, 448.0), (624.0, 40.0)]]);
+        assert_eq!(
+            scene
+                .elements
+                .iter()
+                .filter(|element| matches!(element, Element::Circle { .. }))
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_layout_empty() {
+        let chart = Chart {
+            lines: vec![],
+            title: "title1".to_string(),
+        };
+        let scene = layout(&chart, &Options::default());
+        assert!(scene
+            .elements
+            .iter()
+            .all(|element| !matches!(element, Element::Polyline { .. })));
+    }
+
+    #[test]
+    fn test_layout_theme() {
+        let chart = Chart {
+            lines: vec![],
+            title: "title1".to_string(),
+        };
+        let background = |theme: Theme| {
+            layout(
+                &chart,
+                &Options {
+                    theme,
+                    ..Options::default()
+                },
+            )
+            .elements[0]
+                .clone()
+        };
+        assert!(matches!(
+            background(Theme::Light),
+            Element::Rect {
+                color: Color(0xff, 0xff, 0xff),
+                ..
+            }
+        ));
+        assert!(matches!(
+            background(Theme::Dark),
+            Element::Rect {
+                color: Color(0x1e, 0x1e, 0x1e),
+                ..
+            }
+        ));
+    }
+}
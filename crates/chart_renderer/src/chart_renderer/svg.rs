@@ -0,0 +1,186 @@
+use std::fmt::Write as _;
+
+use crate::chart_renderer::{
+    scene::{layout, Element, TextAnchor},
+    Chart, Options,
+};
+
+/// Renders the chart as an SVG document
+pub fn render_svg(chart: &Chart, options: &Options) -> String {
+    let scene = layout(chart, options);
+    let mut svg = String::new();
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+        w = scene.width,
+        h = scene.height,
+    )
+    .expect("write to String to succeed");
+    write!(svg, "<title>{}</title>", escape(&scene.title)).expect("write to String to succeed");
+    for element in &scene.elements {
+        match element {
+            Element::Circle {
+                center,
+                color,
+                radius,
+            } => write!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                number(center.0),
+                number(center.1),
+                number(*radius),
+                color
+            ),
+            Element::Line {
+                color,
+                from,
+                to,
+                width,
+            } => write!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+                number(from.0),
+                number(from.1),
+                number(to.0),
+                number(to.1),
+                color,
+                number(*width)
+            ),
+            Element::Polyline {
+                color,
+                points,
+                width,
+            } => write!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"/>"#,
+                points
+                    .iter()
+                    .map(|(x, y)| format!("{},{}", number(*x), number(*y)))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                color,
+                number(*width)
+            ),
+            Element::Rect {
+                color,
+                size,
+                top_left,
+            } => write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                number(top_left.0),
+                number(top_left.1),
+                number(size.0),
+                number(size.1),
+                color
+            ),
+            Element::Text {
+                anchor,
+                color,
+                position,
+                size,
+                text,
+            } => write!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="{}" text-anchor="{}" fill="{}">{}</text>"#,
+                number(position.0),
+                number(position.1),
+                number(*size),
+                match anchor {
+                    TextAnchor::End => "end",
+                    TextAnchor::Middle => "middle",
+                    TextAnchor::Start => "start",
+                },
+                color,
+                escape(text)
+            ),
+        }
+        .expect("write to String to succeed");
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Escapes the text for XML and HTML element contents and attribute values
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Formats the coordinate with up to 2 fractional digits
+fn number(n: f64) -> String {
+    let s = format!("{:.2}", n);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_owned()
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::value_object::XValue;
+
+    use crate::chart_renderer::{Line, Point};
+
+    use super::*;
+
+    #[test]
+    fn test_render_svg() -> anyhow::Result<()> {
+        let chart = Chart {
+            lines: vec![Line {
+                name: Some("a & b".to_string()),
+                points: vec![
+                    Point {
+                        x_value: XValue::from_str("2020-01-01")?,
+                        y_value: 1.0,
+                    },
+                    Point {
+                        x_value: XValue::from_str("2020-01-02")?,
+                        y_value: 2.5,
+                    },
+                ],
+            }],
+            title: "<title1>".to_string(),
+        };
+        let svg = render_svg(
+            &chart,
+            &Options {
+                height: 200,
+                width: 300,
+                ..Options::default()
+            },
+        );
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="200" viewBox="0 0 300 200""#
+        ));
+        assert!(svg.contains("<title>&lt;title1&gt;</title>"));
+        assert!(svg.contains(">a &amp; b</text>"));
+        assert_eq!(svg.matches("<polyline ").count(), 1);
+        assert_eq!(svg.matches("<circle ").count(), 2);
+        assert!(svg.ends_with("</svg>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(1.5), "1.5");
+        assert_eq!(number(1.234), "1.23");
+        assert_eq!(number(-0.001), "0");
+    }
+}
@@ -0,0 +1,166 @@
+use crate::chart_renderer::date::{days_from_x_value, x_value_from_days};
+
+const MAX_TICKS: i64 = 8;
+
+/// Returns the days and the labels of the x-axis ticks in `first..=last`
+pub(crate) fn x_ticks(first: i64, last: i64) -> Vec<(i64, String)> {
+    let span = last - first;
+    let date = |days: i64| x_value_from_days(days).map(|x_value| (days, x_value));
+    if let Some(step) = [1, 2, 7, 14]
+        .into_iter()
+        .find(|step| span / step < MAX_TICKS)
+    {
+        // weekly ticks are on Mondays (1970-01-05 is Monday)
+        let start = if step >= 7 {
+            first + (4 - first).rem_euclid(7)
+        } else {
+            first
+        };
+        return (start..=last)
+            .step_by(step as usize)
+            .filter_map(date)
+            .map(|(days, x_value)| {
+                (
+                    days,
+                    format!("{:02}-{:02}", x_value.month(), x_value.day_of_month()),
+                )
+            })
+            .collect();
+    }
+
+    let months = |days: i64| {
+        x_value_from_days(days)
+            .map(|x_value| i64::from(x_value.year()) * 12 + i64::from(x_value.month()) - 1)
+    };
+    let (Some(first_month), Some(last_month)) = (months(first), months(last)) else {
+        return vec![];
+    };
+    let month_span = last_month - first_month;
+    let first_day_of_month = |month: i64| {
+        format!("{:04}-{:02}-01", month / 12, month % 12 + 1)
+            .parse()
+            .ok()
+            .map(days_from_x_value)
+    };
+    if let Some(step) = [1, 2, 3, 6]
+        .into_iter()
+        .find(|step| month_span / step < MAX_TICKS)
+    {
+        return (first_month..=last_month + 1)
+            .filter(|month| (month % 12) % step == 0)
+            .filter_map(|month| first_day_of_month(month).map(|days| (days, month)))
+            .filter(|(days, _)| (first..=last).contains(days))
+            .map(|(days, month)| (days, format!("{:04}-{:02}", month / 12, month % 12 + 1)))
+            .collect();
+    }
+
+    let (first_year, last_year) = (first_month / 12, last_month / 12);
+    let step = nice_step((last_year - first_year) as f64 / MAX_TICKS as f64).ceil() as i64;
+    (first_year..=last_year + 1)
+        .filter(|year| year % step.max(1) == 0)
+        .filter_map(|year| first_day_of_month(year * 12).map(|days| (days, year)))
+        .filter(|(days, _)| (first..=last).contains(days))
+        .map(|(days, year)| (days, format!("{:04}", year)))
+        .collect()
+}
+
+/// Returns `1`, `2` or `5` times a power of ten that is greater than or equal to `rough`
+fn nice_step(rough: f64) -> f64 {
+    if rough <= 0.0 || !rough.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10_f64.powf(rough.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|n| n * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Returns the extended bounds and the values and the labels of the y-axis ticks
+pub(crate) fn y_ticks(min: f64, max: f64) -> ((f64, f64), Vec<(f64, String)>) {
+    let (min, max) = if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    };
+    let step = nice_step((max - min) / 5.0);
+    let (lower, upper) = ((min / step).floor() * step, (max / step).ceil() * step);
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let count = ((upper - lower) / step).round() as i64;
+    let ticks = (0..=count)
+        .map(|i| {
+            let value = lower + step * i as f64;
+            // avoids "-0"
+            let value = if value.abs() < step * 1e-9 {
+                0.0
+            } else {
+                value
+            };
+            (value, format!("{:.*}", decimals, value))
+        })
+        .collect();
+    ((lower, upper), ticks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::value_object::XValue;
+
+    use super::*;
+
+    fn days(s: &str) -> anyhow::Result<i64> {
+        Ok(days_from_x_value(XValue::from_str(s)?))
+    }
+
+    fn labels(ticks: Vec<(i64, String)>) -> Vec<String> {
+        ticks.into_iter().map(|(_, label)| label).collect()
+    }
+
+    #[test]
+    fn test_x_ticks() -> anyhow::Result<()> {
+        assert_eq!(
+            labels(x_ticks(days("2020-01-30")?, days("2020-02-02")?)),
+            vec!["01-30", "01-31", "02-01", "02-02"]
+        );
+        // 2020-01-06 is Monday
+        assert_eq!(
+            labels(x_ticks(days("2020-01-01")?, days("2020-02-01")?)),
+            vec!["01-06", "01-13", "01-20", "01-27"]
+        );
+        assert_eq!(
+            labels(x_ticks(days("2020-01-15")?, days("2020-06-15")?)),
+            vec!["2020-02", "2020-03", "2020-04", "2020-05", "2020-06"]
+        );
+        assert_eq!(
+            labels(x_ticks(days("2020-01-01")?, days("2021-12-31")?)),
+            vec![
+                "2020-01", "2020-04", "2020-07", "2020-10", "2021-01", "2021-04", "2021-07",
+                "2021-10"
+            ]
+        );
+        assert_eq!(
+            labels(x_ticks(days("2000-06-01")?, days("2020-06-01")?)),
+            vec!["2005", "2010", "2015", "2020"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_y_ticks() {
+        let ((lower, upper), ticks) = y_ticks(-3.0, 12.0);
+        assert_eq!((lower, upper), (-5.0, 15.0));
+        assert_eq!(
+            ticks
+                .into_iter()
+                .map(|(_, label)| label)
+                .collect::<Vec<_>>(),
+            vec!["-5", "0", "5", "10", "15"]
+        );
+        let ((lower, upper), ticks) = y_ticks(0.25, 0.25);
+        assert_eq!((lower, upper), (-1.0, 1.5));
+        assert_eq!(ticks[0].1, "-1.0");
+    }
+}
@@ -0,0 +1,3 @@
+mod chart_renderer;
+
+pub use self::chart_renderer::*;
@@ -1,7 +1,12 @@
 pub mod create_chart;
 pub mod create_data_point;
+pub mod create_series;
 pub mod delete_chart;
 pub mod delete_data_point;
+pub mod delete_series;
+pub mod import_data_points;
 pub mod port;
+pub mod rename_series;
+mod retry;
 pub mod update_chart;
 pub mod update_data_point;
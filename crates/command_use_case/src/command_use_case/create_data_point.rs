@@ -2,9 +2,13 @@ use std::{str::FromStr as _, sync::Arc};
 
 use write_model::{
     aggregate::DataPoint,
-    value_object::{ChartId, XValue, YValue},
+    value_object::{ChartId, SeriesId, XValue, YValue},
 };
 
+use crate::command_use_case::{
+    port::chart_repository,
+    retry::{retry, Retryable},
+};
 #[cfg(any(test, feature = "test-util"))]
 use crate::port::{ChartRepository, DataPointRepository};
 use crate::port::{HasChartRepository, HasDataPointRepository};
@@ -12,8 +16,9 @@ use crate::port::{HasChartRepository, HasDataPointRepository};
 #[derive(Debug)]
 pub struct Input {
     pub chart_id: String,
+    pub series_id: Option<String>,
     pub x_value: String,
-    pub y_value: u32,
+    pub y_value: String,
 }
 
 #[derive(Debug)]
@@ -33,8 +38,23 @@ pub enum Error {
     DataPointCreate(#[source] write_model::aggregate::data_point::Error),
     #[error("data point store")]
     DataPointStore(#[source] crate::command_use_case::port::data_point_repository::Error),
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+    #[error("series not found (id = {0})")]
+    SeriesNotFound(SeriesId),
     #[error("x value")]
     XValue(#[source] write_model::value_object::x_value::Error),
+    #[error("y value")]
+    YValue(#[source] write_model::value_object::y_value::Error),
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::ChartFind(chart_repository::Error::Unavailable(_))
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -44,6 +64,7 @@ pub trait CreateDataPoint: HasChartRepository + HasDataPointRepository {
         &self,
         Input {
             chart_id,
+            series_id,
             x_value,
             y_value,
         }: Input,
@@ -52,26 +73,39 @@ pub trait CreateDataPoint: HasChartRepository + HasDataPointRepository {
         let data_point_repository = self.data_point_repository();
 
         let chart_id = ChartId::from_str(&chart_id).map_err(Error::ChartId)?;
+        let series_id = series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()
+            .map_err(Error::SeriesId)?;
         let x_value = XValue::from_str(&x_value).map_err(Error::XValue)?;
-        let y_value = YValue::from(y_value);
-
-        let chart = chart_repository
-            .find(chart_id)
-            .await
-            .map_err(Error::ChartFind)?
-            .ok_or(Error::ChartNotFound(chart_id))?;
+        let y_value = YValue::from_str(&y_value).map_err(Error::YValue)?;
+
+        retry(|| async {
+            let chart = chart_repository
+                .find(chart_id)
+                .await
+                .map_err(Error::ChartFind)?
+                .ok_or(Error::ChartNotFound(chart_id))?;
+            if let Some(series_id) = series_id {
+                if chart.find_series(series_id).is_none() {
+                    return Err(Error::SeriesNotFound(series_id));
+                }
+            }
+
+            let (state, events) = DataPoint::create(chart.id(), series_id, x_value, y_value)
+                .map_err(Error::DataPointCreate)?;
+
+            data_point_repository
+                .store(None, &events)
+                .await
+                .map_err(Error::DataPointStore)?;
 
-        let (state, events) =
-            DataPoint::create(chart.id(), x_value, y_value).map_err(Error::DataPointCreate)?;
-
-        data_point_repository
-            .store(None, &events)
-            .await
-            .map_err(Error::DataPointStore)?;
-
-        Ok(Output {
-            data_point_id: state.id().to_string(),
+            Ok(Output {
+                data_point_id: state.id().to_string(),
+            })
         })
+        .await
     }
 }
 
@@ -0,0 +1,112 @@
+use std::{str::FromStr as _, sync::Arc};
+
+use write_model::value_object::ChartId;
+
+#[cfg(any(test, feature = "test-util"))]
+use crate::command_use_case::port::ChartRepository;
+use crate::command_use_case::{
+    port::chart_repository,
+    retry::{retry, Retryable},
+};
+use crate::port::HasChartRepository;
+
+#[derive(Debug)]
+pub struct Input {
+    pub chart_id: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct Output {
+    pub series_id: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart find")]
+    ChartFind(#[source] crate::command_use_case::port::chart_repository::Error),
+    #[error("chart id")]
+    ChartId(#[source] write_model::value_object::chart_id::Error),
+    #[error("chart not found")]
+    ChartNotFound(write_model::value_object::ChartId),
+    #[error("chart store")]
+    ChartStore(#[source] crate::command_use_case::port::chart_repository::Error),
+    #[error("series create")]
+    SeriesCreate(#[source] write_model::aggregate::chart::Error),
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::ChartFind(chart_repository::Error::Unavailable(_))
+                | Error::ChartStore(chart_repository::Error::Conflict(_))
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait CreateSeries: HasChartRepository {
+    #[tracing::instrument(level = tracing::Level::INFO, err(Debug), ret, skip(self))]
+    async fn execute(&self, input: Input) -> Result<Output, Error> {
+        let chart_repository = self.chart_repository();
+        let chart_id = ChartId::from_str(&input.chart_id).map_err(Error::ChartId)?;
+        retry(|| async {
+            let chart = chart_repository
+                .find(chart_id)
+                .await
+                .map_err(Error::ChartFind)?
+                .ok_or(Error::ChartNotFound(chart_id))?;
+            let (state, events) = chart
+                .create_series(input.name.clone())
+                .map_err(Error::SeriesCreate)?;
+            chart_repository
+                .store(Some(chart.version()), &events)
+                .await
+                .map_err(Error::ChartStore)?;
+            let series = state
+                .series()
+                .last()
+                .expect("created series to be the last one");
+            Ok(Output {
+                series_id: series.id().to_string(),
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+mockall::mock! {
+    pub CreateSeries {}
+
+    impl HasChartRepository for CreateSeries {
+        fn chart_repository(&self) -> Arc<dyn ChartRepository + Send + Sync>;
+    }
+
+    #[async_trait::async_trait]
+    impl CreateSeries for CreateSeries {
+        async fn execute(&self, input: Input) -> Result<Output, Error>;
+    }
+}
+
+pub trait HasCreateSeries {
+    fn create_series(&self) -> Arc<dyn CreateSeries + Send + Sync>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock() {
+        let mut mock = MockCreateSeries::new();
+        mock.expect_execute().return_once(|_| {
+            Ok(Output {
+                series_id: "test".to_string(),
+            })
+        });
+    }
+
+    // TODO: test execute
+}
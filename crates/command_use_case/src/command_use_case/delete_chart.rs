@@ -4,15 +4,24 @@ use write_model::value_object::ChartId;
 
 #[cfg(any(test, feature = "test-util"))]
 use crate::command_use_case::port::ChartRepository;
+use crate::command_use_case::{
+    port::chart_repository,
+    retry::{retry, Retryable},
+};
 use crate::port::HasChartRepository;
 
 #[derive(Debug)]
 pub struct Input {
     pub chart_id: String,
+    /// The version the client last saw. `None` skips the check.
+    pub expected_version: Option<u32>,
 }
 
 #[derive(Debug)]
-pub struct Output;
+pub struct Output {
+    /// The version after the change
+    pub version: u32,
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -26,6 +35,18 @@ pub enum Error {
     ChartNotFound(ChartId),
     #[error("chart store")]
     ChartStore(#[source] crate::command_use_case::port::chart_repository::Error),
+    #[error("version mismatch (expected = {expected}, actual = {actual})")]
+    VersionMismatch { actual: u32, expected: u32 },
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::ChartFind(chart_repository::Error::Unavailable(_))
+                | Error::ChartStore(chart_repository::Error::Conflict(_))
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -34,17 +55,28 @@ pub trait DeleteChart: HasChartRepository {
     async fn execute(&self, input: Input) -> Result<Output, Error> {
         let chart_repository = self.chart_repository();
         let chart_id = ChartId::from_str(&input.chart_id).map_err(Error::ChartId)?;
-        let chart = chart_repository
-            .find(chart_id)
-            .await
-            .map_err(Error::ChartFind)?
-            .ok_or(Error::ChartNotFound(chart_id))?;
-        let (_, events) = chart.delete().map_err(Error::ChartDelete)?;
-        chart_repository
-            .store(Some(chart.version()), &events)
-            .await
-            .map_err(Error::ChartStore)?;
-        Ok(Output)
+        retry(|| async {
+            let chart = chart_repository
+                .find(chart_id)
+                .await
+                .map_err(Error::ChartFind)?
+                .ok_or(Error::ChartNotFound(chart_id))?;
+            if let Some(expected) = input.expected_version {
+                let actual = u32::from(chart.version());
+                if actual != expected {
+                    return Err(Error::VersionMismatch { actual, expected });
+                }
+            }
+            let (deleted, events) = chart.delete().map_err(Error::ChartDelete)?;
+            chart_repository
+                .store(Some(chart.version()), &events)
+                .await
+                .map_err(Error::ChartStore)?;
+            Ok(Output {
+                version: u32::from(deleted.version()),
+            })
+        })
+        .await
     }
 }
 
@@ -73,7 +105,8 @@ mod tests {
     #[test]
     fn test_mock() {
         let mut mock = MockDeleteChart::new();
-        mock.expect_execute().return_once(|_| Ok(Output));
+        mock.expect_execute()
+            .return_once(|_| Ok(Output { version: 2 }));
     }
 
     // TODO: test execute
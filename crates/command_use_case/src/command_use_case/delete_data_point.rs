@@ -4,15 +4,24 @@ use write_model::value_object::DataPointId;
 
 #[cfg(any(test, feature = "test-util"))]
 use crate::command_use_case::port::DataPointRepository;
+use crate::command_use_case::{
+    port::data_point_repository,
+    retry::{retry, Retryable},
+};
 use crate::port::HasDataPointRepository;
 
 #[derive(Debug)]
 pub struct Input {
     pub data_point_id: String,
+    /// The version the client last saw. `None` skips the check.
+    pub expected_version: Option<u32>,
 }
 
 #[derive(Debug)]
-pub struct Output;
+pub struct Output {
+    /// The version after the change
+    pub version: u32,
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -26,25 +35,54 @@ pub enum Error {
     DataPointNotFound(DataPointId),
     #[error("data point store")]
     DataPointStore(#[source] crate::command_use_case::port::data_point_repository::Error),
+    #[error("version mismatch (expected = {expected}, actual = {actual})")]
+    VersionMismatch { actual: u32, expected: u32 },
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::DataPointFind(data_point_repository::Error::Unavailable(_))
+                | Error::DataPointStore(data_point_repository::Error::Conflict(_))
+        )
+    }
 }
 
 #[async_trait::async_trait]
 pub trait DeleteDataPoint: HasDataPointRepository {
     #[tracing::instrument(level = tracing::Level::INFO, err(Debug), ret, skip(self))]
-    async fn execute(&self, Input { data_point_id }: Input) -> Result<Output, Error> {
+    async fn execute(
+        &self,
+        Input {
+            data_point_id,
+            expected_version,
+        }: Input,
+    ) -> Result<Output, Error> {
         let data_point_repository = self.data_point_repository();
         let data_point_id = DataPointId::from_str(&data_point_id).map_err(Error::DataPointId)?;
-        let data_point = data_point_repository
-            .find(data_point_id)
-            .await
-            .map_err(Error::DataPointFind)?
-            .ok_or(Error::DataPointNotFound(data_point_id))?;
-        let (_, events) = data_point.delete().map_err(Error::DataPointDelete)?;
-        data_point_repository
-            .store(Some(data_point.version()), &events)
-            .await
-            .map_err(Error::DataPointStore)?;
-        Ok(Output)
+        retry(|| async {
+            let data_point = data_point_repository
+                .find(data_point_id)
+                .await
+                .map_err(Error::DataPointFind)?
+                .ok_or(Error::DataPointNotFound(data_point_id))?;
+            if let Some(expected) = expected_version {
+                let actual = u32::from(data_point.version());
+                if actual != expected {
+                    return Err(Error::VersionMismatch { actual, expected });
+                }
+            }
+            let (deleted, events) = data_point.delete().map_err(Error::DataPointDelete)?;
+            data_point_repository
+                .store(Some(data_point.version()), &events)
+                .await
+                .map_err(Error::DataPointStore)?;
+            Ok(Output {
+                version: u32::from(deleted.version()),
+            })
+        })
+        .await
     }
 }
 
@@ -73,7 +111,8 @@ mod tests {
     #[test]
     fn test_mock() {
         let mut mock = MockDeleteDataPoint::new();
-        mock.expect_execute().return_once(|_| Ok(Output));
+        mock.expect_execute()
+            .return_once(|_| Ok(Output { version: 2 }));
     }
 
     // TODO: test execute
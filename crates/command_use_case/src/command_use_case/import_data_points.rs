@@ -0,0 +1,747 @@
+use std::{collections::BTreeMap, str::FromStr as _, sync::Arc};
+
+use write_model::value_object::{ChartId, DataPointId, SeriesId, XValue, YValue};
+
+#[cfg(any(test, feature = "test-util"))]
+use crate::command_use_case::{
+    create_data_point::CreateDataPoint, port::DataPointRepository,
+    update_data_point::UpdateDataPoint,
+};
+use crate::command_use_case::{
+    create_data_point::{self, HasCreateDataPoint},
+    port::data_point_repository,
+    update_data_point::{self, HasUpdateDataPoint},
+};
+use crate::port::HasDataPointRepository;
+
+const DEFAULT_DATE_COLUMN: &str = "date";
+const DEFAULT_DATE_FORMAT: &str = "YYYY-MM-DD";
+const DEFAULT_VALUE_COLUMN: &str = "value";
+
+#[derive(Debug)]
+pub struct Input {
+    pub chart_id: String,
+    /// The CSV text. Each row has a date and a value
+    pub csv: String,
+    /// The header name of the date column. `None` is `date`. Without a header, the date is the first column
+    pub date_column: Option<String>,
+    /// `YYYY`, `MM` and `DD` stand for the year, the month and the day. `None` is `YYYY-MM-DD`
+    pub date_format: Option<String>,
+    /// Whether the first row is a header. `None` treats the first row as a header if its date does not parse
+    pub header: Option<bool>,
+    pub mode: Mode,
+    pub series_id: Option<String>,
+    /// The header name of the value column. `None` is `value`. Without a header, the value is the second column
+    pub value_column: Option<String>,
+}
+
+/// The rows are checked before anything is written, so invalid rows and conflicts import nothing.
+/// The data points are written one by one, not in a transaction. If a write fails, the rows before
+/// the failing line stay imported and the error is returned. Importing again in `Mode::Skip` resumes
+/// the import
+#[derive(Debug)]
+pub struct Output {
+    pub created: usize,
+    /// The rows that cannot be imported. Nothing is imported if any
+    pub errors: Vec<RowError>,
+    pub overwritten: usize,
+    /// The existing data points left as they are (`skip` mode, or `overwrite` mode with the same value)
+    pub skipped: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RowError {
+    /// 1-based line number in the CSV
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart id")]
+    ChartId(#[source] write_model::value_object::chart_id::Error),
+    #[error("column not found (name = {0})")]
+    ColumnNotFound(String),
+    /// The rows before `line` have been imported
+    #[error("data point create (line = {line})")]
+    DataPointCreate {
+        line: u64,
+        #[source]
+        source: create_data_point::Error,
+    },
+    #[error("data point find")]
+    DataPointFind(#[source] data_point_repository::Error),
+    /// The rows before `line` have been imported
+    #[error("data point update (line = {line})")]
+    DataPointUpdate {
+        line: u64,
+        #[source]
+        source: update_data_point::Error,
+    },
+    #[error("date format must contain YYYY, MM and DD")]
+    DateFormat,
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("mode must be fail, overwrite or skip")]
+pub struct ParseModeError;
+
+/// What to do with the dates that already have a data point
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Import nothing if any date already has a data point
+    #[default]
+    Fail,
+    /// Update the existing data points. A deleted data point is a row error
+    Overwrite,
+    /// Keep the existing data points
+    Skip,
+}
+
+impl std::str::FromStr for Mode {
+    type Err = ParseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fail" => Ok(Self::Fail),
+            "overwrite" => Ok(Self::Overwrite),
+            "skip" => Ok(Self::Skip),
+            _ => Err(ParseModeError),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Row {
+    line: u64,
+    x_value: XValue,
+    y_value: YValue,
+}
+
+#[async_trait::async_trait]
+pub trait ImportDataPoints:
+    HasCreateDataPoint + HasDataPointRepository + HasUpdateDataPoint
+{
+    #[tracing::instrument(level = tracing::Level::INFO, err(Debug), ret, skip_all)]
+    async fn execute(
+        &self,
+        Input {
+            chart_id,
+            csv,
+            date_column,
+            date_format,
+            header,
+            mode,
+            series_id,
+            value_column,
+        }: Input,
+    ) -> Result<Output, Error> {
+        let data_point_repository = self.data_point_repository();
+
+        let chart_id = ChartId::from_str(&chart_id).map_err(Error::ChartId)?;
+        let series_id = series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()
+            .map_err(Error::SeriesId)?;
+        let date_format = date_format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+        if !["YYYY", "MM", "DD"]
+            .into_iter()
+            .all(|token| date_format.contains(token))
+        {
+            return Err(Error::DateFormat);
+        }
+        let (rows, mut errors) = parse_rows(
+            &csv,
+            date_column.as_deref().unwrap_or(DEFAULT_DATE_COLUMN),
+            date_format,
+            header,
+            value_column.as_deref().unwrap_or(DEFAULT_VALUE_COLUMN),
+        )?;
+
+        // checks all rows before writing anything
+        let mut existing = BTreeMap::new();
+        for row in &rows {
+            let data_point_id = DataPointId::new(chart_id, series_id, row.x_value);
+            let Some(data_point) = data_point_repository
+                .find(data_point_id)
+                .await
+                .map_err(Error::DataPointFind)?
+            else {
+                continue;
+            };
+            let deleted = data_point.deleted_at().is_some();
+            match mode {
+                Mode::Fail => errors.push(RowError {
+                    line: row.line,
+                    message: format!("data point already exists (date = {})", row.x_value),
+                }),
+                Mode::Overwrite if deleted => errors.push(RowError {
+                    line: row.line,
+                    message: format!("data point was deleted (date = {})", row.x_value),
+                }),
+                Mode::Overwrite | Mode::Skip => {}
+            }
+            existing.insert(row.line, (data_point.version(), data_point.y_value()));
+        }
+        if !errors.is_empty() {
+            errors.sort_by_key(|error| error.line);
+            return Ok(Output {
+                created: 0,
+                errors,
+                overwritten: 0,
+                skipped: 0,
+            });
+        }
+
+        let create_data_point = self.create_data_point();
+        let update_data_point = self.update_data_point();
+        let mut output = Output {
+            created: 0,
+            errors: vec![],
+            overwritten: 0,
+            skipped: 0,
+        };
+        for row in rows {
+            match existing.get(&row.line) {
+                None => {
+                    create_data_point
+                        .execute(create_data_point::Input {
+                            chart_id: chart_id.to_string(),
+                            series_id: series_id.map(|series_id| series_id.to_string()),
+                            x_value: row.x_value.to_string(),
+                            y_value: row.y_value.to_string(),
+                        })
+                        .await
+                        .map_err(|source| Error::DataPointCreate {
+                            line: row.line,
+                            source,
+                        })?;
+                    output.created += 1;
+                }
+                Some((_, y_value)) if mode == Mode::Skip || *y_value == row.y_value => {
+                    output.skipped += 1;
+                }
+                Some((version, _)) => {
+                    update_data_point
+                        .execute(update_data_point::Input {
+                            data_point_id: DataPointId::new(chart_id, series_id, row.x_value)
+                                .to_string(),
+                            expected_version: Some(u32::from(*version)),
+                            y_value: row.y_value.to_string(),
+                        })
+                        .await
+                        .map_err(|source| Error::DataPointUpdate {
+                            line: row.line,
+                            source,
+                        })?;
+                    output.overwritten += 1;
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+mockall::mock! {
+    pub ImportDataPoints {}
+
+    impl HasCreateDataPoint for ImportDataPoints {
+        fn create_data_point(&self) -> Arc<dyn CreateDataPoint + Send + Sync>;
+    }
+
+    impl HasDataPointRepository for ImportDataPoints {
+        fn data_point_repository(&self) -> Arc<dyn DataPointRepository + Send + Sync>;
+    }
+
+    impl HasUpdateDataPoint for ImportDataPoints {
+        fn update_data_point(&self) -> Arc<dyn UpdateDataPoint + Send + Sync>;
+    }
+
+    #[async_trait::async_trait]
+    impl ImportDataPoints for ImportDataPoints {
+        async fn execute(&self, input: Input) -> Result<Output, Error>;
+    }
+}
+
+pub trait HasImportDataPoints {
+    fn import_data_points(&self) -> Arc<dyn ImportDataPoints + Send + Sync>;
+}
+
+/// Parses the date in `format` and validates it as an `XValue`. `MM` and `DD` also accept a single digit
+fn parse_date(s: &str, format: &str) -> Option<XValue> {
+    let (mut year, mut month, mut day) = (None, None, None);
+    let (mut s, mut format) = (s.trim(), format);
+    while !format.is_empty() {
+        let (token, width) = if format.starts_with("YYYY") {
+            (&mut year, 4..=4)
+        } else if format.starts_with("MM") {
+            (&mut month, 1..=2)
+        } else if format.starts_with("DD") {
+            (&mut day, 1..=2)
+        } else {
+            let c = format.chars().next()?;
+            s = s.strip_prefix(c)?;
+            format = &format[c.len_utf8()..];
+            continue;
+        };
+        let digits = s.chars().take_while(char::is_ascii_digit).count();
+        if !width.contains(&digits) {
+            return None;
+        }
+        *token = Some(s[..digits].parse::<u16>().ok()?);
+        s = &s[digits..];
+        format = &format[if *width.end() == 4 { 4 } else { 2 }..];
+    }
+    if !s.is_empty() {
+        return None;
+    }
+    XValue::from_str(&format!("{:04}-{:02}-{:02}", year?, month?, day?)).ok()
+}
+
+/// Returns the 1-based line number of the record at `position`. The position of a record after blank lines points to the first blank line
+fn line_of(csv: &str, position: &csv::Position) -> u64 {
+    let start = usize::try_from(position.byte())
+        .unwrap_or(csv.len())
+        .min(csv.len());
+    let blank = csv[start..].len() - csv[start..].trim_start_matches(['\r', '\n']).len();
+    1 + csv[..start + blank].matches('\n').count() as u64
+}
+
+/// Returns the valid rows and the errors of the invalid rows
+fn parse_rows(
+    csv: &str,
+    date_column: &str,
+    date_format: &str,
+    header: Option<bool>,
+    value_column: &str,
+) -> Result<(Vec<Row>, Vec<RowError>), Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .has_headers(false)
+        .from_reader(csv.as_bytes());
+    let mut records = reader.records().peekable();
+
+    let first = match records.peek() {
+        Some(Ok(record)) => Some(record.clone()),
+        _ => None,
+    };
+    let header = match (header, first.as_ref()) {
+        (Some(header), _) => header,
+        (None, Some(record)) => record
+            .get(0)
+            .and_then(|date| parse_date(date, date_format))
+            .is_none(),
+        (None, None) => false,
+    };
+    let (date_index, value_index) = match (header, first) {
+        (true, Some(record)) => {
+            records.next();
+            let index = |name: &str| {
+                record
+                    .iter()
+                    .position(|column| column.trim().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| Error::ColumnNotFound(name.to_owned()))
+            };
+            (index(date_column)?, index(value_column)?)
+        }
+        _ => (0, 1),
+    };
+
+    let mut rows = Vec::<Row>::new();
+    let mut errors = vec![];
+    let mut lines = BTreeMap::new();
+    for record in records {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(RowError {
+                    line: e
+                        .position()
+                        .map(|position| line_of(csv, position))
+                        .unwrap_or(0),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record
+            .position()
+            .map(|position| line_of(csv, position))
+            .unwrap_or(0);
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let (Some(date), Some(value)) = (record.get(date_index), record.get(value_index)) else {
+            errors.push(RowError {
+                line,
+                message: "missing column".to_owned(),
+            });
+            continue;
+        };
+        let Some(x_value) = parse_date(date, date_format) else {
+            errors.push(RowError {
+                line,
+                message: format!("invalid date (date = {}, format = {})", date, date_format),
+            });
+            continue;
+        };
+        let Ok(y_value) = YValue::from_str(value.trim()) else {
+            errors.push(RowError {
+                line,
+                message: format!("invalid value (value = {})", value),
+            });
+            continue;
+        };
+        if let Some(first_line) = lines.insert(x_value, line) {
+            errors.push(RowError {
+                line,
+                message: format!("duplicate date (date = {}, line = {})", x_value, first_line),
+            });
+            continue;
+        }
+        rows.push(Row {
+            line,
+            x_value,
+            y_value,
+        });
+    }
+    Ok((rows, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use write_model::{aggregate::DataPoint, event::DataPointEvent, value_object::Version};
+
+    use crate::command_use_case::{
+        create_data_point::MockCreateDataPoint, update_data_point::MockUpdateDataPoint,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mode_fail() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let app = TestApp::new(chart_id, &[("2020-01-02", "2")])?;
+        let output = app
+            .execute(build_input(
+                chart_id,
+                "2020-01-01,1\n2020-01-02,3\n",
+                Mode::Fail,
+            ))
+            .await?;
+        assert_eq!(
+            output.errors,
+            vec![RowError {
+                line: 2,
+                message: "data point already exists (date = 2020-01-02)".to_owned(),
+            }]
+        );
+        assert_eq!(output.created, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mode_overwrite() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut app = TestApp::new(chart_id, &[("2020-01-02", "2"), ("2020-01-03", "3")])?;
+        app.expect_create(1);
+        app.update_data_point = {
+            let data_point_id = format!("{}:2020-01-02", chart_id);
+            let mut mock = MockUpdateDataPoint::new();
+            mock.expect_execute()
+                .withf(move |input| {
+                    input.data_point_id == data_point_id
+                        && input.expected_version == Some(1)
+                        && input.y_value == "4"
+                })
+                .times(1)
+                .returning(|_| Ok(update_data_point::Output { version: 2 }));
+            Arc::new(mock)
+        };
+        let output = app
+            .execute(build_input(
+                chart_id,
+                "2020-01-01,1\n2020-01-02,4\n2020-01-03,3\n",
+                Mode::Overwrite,
+            ))
+            .await?;
+        assert!(output.errors.is_empty());
+        assert_eq!(
+            (output.created, output.overwritten, output.skipped),
+            (1, 1, 1)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mode_skip() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut app = TestApp::new(chart_id, &[("2020-01-02", "2")])?;
+        app.expect_create(1);
+        let output = app
+            .execute(build_input(
+                chart_id,
+                "2020-01-01,1\n2020-01-02,4\n",
+                Mode::Skip,
+            ))
+            .await?;
+        assert!(output.errors.is_empty());
+        assert_eq!(
+            (output.created, output.overwritten, output.skipped),
+            (1, 0, 1)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_row_imports_nothing() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        // the mocks of the create and update use cases panic if called
+        let app = TestApp::new(chart_id, &[])?;
+        let output = app
+            .execute(build_input(
+                chart_id,
+                "2020-01-01,1\n2020-13-01,2\n2020-01-03,x\n",
+                Mode::Skip,
+            ))
+            .await?;
+        assert_eq!(
+            output
+                .errors
+                .iter()
+                .map(|error| error.line)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(output.created, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_failure() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut app = TestApp::new(chart_id, &[])?;
+        app.create_data_point = {
+            let mut mock = MockCreateDataPoint::new();
+            let mut sequence = mockall::Sequence::new();
+            mock.expect_execute()
+                .times(1)
+                .in_sequence(&mut sequence)
+                .returning(|input| {
+                    Ok(create_data_point::Output {
+                        data_point_id: format!("{}:{}", input.chart_id, input.x_value),
+                    })
+                });
+            mock.expect_execute()
+                .times(1)
+                .in_sequence(&mut sequence)
+                .returning(move |_| Err(create_data_point::Error::ChartNotFound(chart_id)));
+            Arc::new(mock)
+        };
+        let result = app
+            .execute(build_input(
+                chart_id,
+                "2020-01-01,1\n2020-01-02,2\n2020-01-03,3\n",
+                Mode::Fail,
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::DataPointCreate { line: 2, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!(Mode::from_str("fail").ok(), Some(Mode::Fail));
+        assert_eq!(Mode::from_str("overwrite").ok(), Some(Mode::Overwrite));
+        assert_eq!(Mode::from_str("skip").ok(), Some(Mode::Skip));
+        assert!(Mode::from_str("Skip").is_err());
+        assert_eq!(Mode::default(), Mode::Fail);
+    }
+
+    #[test]
+    fn test_mock() {
+        let mut mock = MockImportDataPoints::new();
+        mock.expect_execute().return_once(|_| {
+            Ok(Output {
+                created: 1,
+                errors: vec![],
+                overwritten: 0,
+                skipped: 0,
+            })
+        });
+    }
+
+    #[test]
+    fn test_parse_date() -> anyhow::Result<()> {
+        let x_value = XValue::from_str("2020-01-02")?;
+        assert_eq!(parse_date("2020-01-02", "YYYY-MM-DD"), Some(x_value));
+        assert_eq!(parse_date(" 2020/1/2 ", "YYYY/MM/DD"), Some(x_value));
+        assert_eq!(parse_date("02.01.2020", "DD.MM.YYYY"), Some(x_value));
+        assert_eq!(parse_date("01/02/2020", "MM/DD/YYYY"), Some(x_value));
+        assert_eq!(parse_date("2020-01-02", "DD.MM.YYYY"), None);
+        assert_eq!(parse_date("2020-02-30", "YYYY-MM-DD"), None);
+        assert_eq!(parse_date("2020-01-02x", "YYYY-MM-DD"), None);
+        assert_eq!(parse_date("date", "YYYY-MM-DD"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rows() -> anyhow::Result<()> {
+        let row = |line: u64, x_value: &str, y_value: &str| -> anyhow::Result<Row> {
+            Ok(Row {
+                line,
+                x_value: XValue::from_str(x_value)?,
+                y_value: YValue::from_str(y_value)?,
+            })
+        };
+
+        // detects the header and finds the columns by name
+        let (rows, errors) = parse_rows(
+            "Value,Day\n1.5,2020-01-01\n\nx,2020-01-02\n3,2020-13-01\n4,2020-01-01\n",
+            "day",
+            "YYYY-MM-DD",
+            None,
+            "value",
+        )?;
+        assert_eq!(rows, vec![row(2, "2020-01-01", "1.5")?]);
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+
+        // detects no header and uses the first two columns
+        let (rows, errors) = parse_rows(
+            "2020/01/01,1\n2020/01/02,2\n",
+            "date",
+            "YYYY/MM/DD",
+            None,
+            "value",
+        )?;
+        assert_eq!(
+            rows,
+            vec![row(1, "2020-01-01", "1")?, row(2, "2020-01-02", "2")?]
+        );
+        assert!(errors.is_empty());
+
+        assert!(matches!(
+            parse_rows("a,b\n", "date", "YYYY-MM-DD", Some(true), "value"),
+            Err(Error::ColumnNotFound(name)) if name == "date"
+        ));
+        Ok(())
+    }
+
+    struct TestApp {
+        create_data_point: Arc<MockCreateDataPoint>,
+        data_point_repository: Arc<TestDataPointRepository>,
+        update_data_point: Arc<MockUpdateDataPoint>,
+    }
+
+    impl TestApp {
+        fn new(chart_id: ChartId, data_points: &[(&str, &str)]) -> anyhow::Result<Self> {
+            let mut stored = BTreeMap::new();
+            for (x_value, y_value) in data_points {
+                let (data_point, _) = DataPoint::create(
+                    chart_id,
+                    None,
+                    XValue::from_str(x_value)?,
+                    YValue::from_str(y_value)?,
+                )?;
+                stored.insert(data_point.id(), data_point);
+            }
+            Ok(Self {
+                create_data_point: Arc::new(MockCreateDataPoint::new()),
+                data_point_repository: Arc::new(TestDataPointRepository(Mutex::new(stored))),
+                update_data_point: Arc::new(MockUpdateDataPoint::new()),
+            })
+        }
+
+        fn expect_create(&mut self, times: usize) {
+            let mut mock = MockCreateDataPoint::new();
+            mock.expect_execute().times(times).returning(|input| {
+                Ok(create_data_point::Output {
+                    data_point_id: format!("{}:{}", input.chart_id, input.x_value),
+                })
+            });
+            self.create_data_point = Arc::new(mock);
+        }
+    }
+
+    impl HasCreateDataPoint for TestApp {
+        fn create_data_point(&self) -> Arc<dyn CreateDataPoint + Send + Sync> {
+            self.create_data_point.clone()
+        }
+    }
+
+    impl HasDataPointRepository for TestApp {
+        fn data_point_repository(&self) -> Arc<dyn DataPointRepository + Send + Sync> {
+            self.data_point_repository.clone()
+        }
+    }
+
+    impl HasUpdateDataPoint for TestApp {
+        fn update_data_point(&self) -> Arc<dyn UpdateDataPoint + Send + Sync> {
+            self.update_data_point.clone()
+        }
+    }
+
+    impl ImportDataPoints for TestApp {}
+
+    struct TestDataPointRepository(Mutex<BTreeMap<DataPointId, DataPoint>>);
+
+    #[async_trait::async_trait]
+    impl DataPointRepository for TestDataPointRepository {
+        async fn find(
+            &self,
+            id: DataPointId,
+        ) -> Result<Option<DataPoint>, data_point_repository::Error> {
+            Ok(self.0.lock().expect("lock").get(&id).cloned())
+        }
+
+        async fn find_all_events(
+            &self,
+        ) -> Result<Vec<DataPointEvent>, data_point_repository::Error> {
+            unreachable!()
+        }
+
+        async fn find_events(
+            &self,
+            _: DataPointId,
+        ) -> Result<Vec<DataPointEvent>, data_point_repository::Error> {
+            unreachable!()
+        }
+
+        async fn find_ids(&self) -> Result<Vec<DataPointId>, data_point_repository::Error> {
+            unreachable!()
+        }
+
+        async fn store(
+            &self,
+            _: Option<Version>,
+            _: &[DataPointEvent],
+        ) -> Result<(), data_point_repository::Error> {
+            unreachable!()
+        }
+    }
+
+    fn build_input(chart_id: ChartId, csv: &str, mode: Mode) -> Input {
+        Input {
+            chart_id: chart_id.to_string(),
+            csv: csv.to_owned(),
+            date_column: None,
+            date_format: None,
+            header: Some(false),
+            mode,
+            series_id: None,
+            value_column: None,
+        }
+    }
+}
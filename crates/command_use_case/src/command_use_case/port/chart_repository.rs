@@ -7,12 +7,48 @@ use write_model::{
 };
 
 #[derive(Debug, thiserror::Error)]
-#[error(transparent)]
-pub struct Error(#[from] Box<dyn std::error::Error + Send + Sync>);
+pub enum Error {
+    /// The stream already exists, or its version does not match `current`
+    #[error("conflict")]
+    Conflict(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The stored data cannot be read back (e.g. an invalid event sequence)
+    #[error("corrupted")]
+    Corrupted(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The stream to append to does not exist
+    #[error("not found")]
+    NotFound(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The storage cannot be reached. Reads can be retried
+    #[error("unavailable")]
+    Unavailable(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    pub fn new(
+        kind: store_error::ErrorKind,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Self {
+        match kind {
+            store_error::ErrorKind::Conflict => Self::Conflict(error),
+            store_error::ErrorKind::Corrupted => Self::Corrupted(error),
+            store_error::ErrorKind::NotFound => Self::NotFound(error),
+            store_error::ErrorKind::Unavailable => Self::Unavailable(error),
+            store_error::ErrorKind::Other => Self::Other(error),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 pub trait ChartRepository {
     async fn find(&self, id: ChartId) -> Result<Option<Chart>, Error>;
+    /// Returns the events of every stream (including deleted charts), ordered by stream id and version
+    async fn find_all_events(&self) -> Result<Vec<ChartEvent>, Error>;
+    /// Returns the events of the stream (including a deleted aggregate), ordered by version.
+    /// Empty if the stream does not exist
+    async fn find_events(&self, id: ChartId) -> Result<Vec<ChartEvent>, Error>;
+    /// Returns the id of every stream (including deleted aggregates), ordered
+    async fn find_ids(&self) -> Result<Vec<ChartId>, Error>;
     async fn store(&self, current: Option<Version>, events: &[ChartEvent]) -> Result<(), Error>;
 }
 
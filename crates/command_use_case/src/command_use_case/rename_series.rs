@@ -0,0 +1,104 @@
+use std::{str::FromStr as _, sync::Arc};
+
+use write_model::value_object::{ChartId, SeriesId};
+
+#[cfg(any(test, feature = "test-util"))]
+use crate::command_use_case::port::ChartRepository;
+use crate::command_use_case::{
+    port::chart_repository,
+    retry::{retry, Retryable},
+};
+use crate::port::HasChartRepository;
+
+#[derive(Debug)]
+pub struct Input {
+    pub chart_id: String,
+    pub name: String,
+    pub series_id: String,
+}
+
+#[derive(Debug)]
+pub struct Output;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart find")]
+    ChartFind(#[source] crate::command_use_case::port::chart_repository::Error),
+    #[error("chart id")]
+    ChartId(#[source] write_model::value_object::chart_id::Error),
+    #[error("chart not found")]
+    ChartNotFound(write_model::value_object::ChartId),
+    #[error("chart store")]
+    ChartStore(#[source] crate::command_use_case::port::chart_repository::Error),
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+    #[error("series rename")]
+    SeriesRename(#[source] write_model::aggregate::chart::Error),
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::ChartFind(chart_repository::Error::Unavailable(_))
+                | Error::ChartStore(chart_repository::Error::Conflict(_))
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait RenameSeries: HasChartRepository {
+    #[tracing::instrument(level = tracing::Level::INFO, err(Debug), ret, skip(self))]
+    async fn execute(&self, input: Input) -> Result<Output, Error> {
+        let chart_repository = self.chart_repository();
+        let chart_id = ChartId::from_str(&input.chart_id).map_err(Error::ChartId)?;
+        let series_id = SeriesId::from_str(&input.series_id).map_err(Error::SeriesId)?;
+        retry(|| async {
+            let chart = chart_repository
+                .find(chart_id)
+                .await
+                .map_err(Error::ChartFind)?
+                .ok_or(Error::ChartNotFound(chart_id))?;
+            let (_, events) = chart
+                .rename_series(series_id, input.name.clone())
+                .map_err(Error::SeriesRename)?;
+            chart_repository
+                .store(Some(chart.version()), &events)
+                .await
+                .map_err(Error::ChartStore)?;
+            Ok(Output)
+        })
+        .await
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+mockall::mock! {
+    pub RenameSeries {}
+
+    impl HasChartRepository for RenameSeries {
+        fn chart_repository(&self) -> Arc<dyn ChartRepository + Send + Sync>;
+    }
+
+    #[async_trait::async_trait]
+    impl RenameSeries for RenameSeries {
+        async fn execute(&self, input: Input) -> Result<Output, Error>;
+    }
+}
+
+pub trait HasRenameSeries {
+    fn rename_series(&self) -> Arc<dyn RenameSeries + Send + Sync>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock() {
+        let mut mock = MockRenameSeries::new();
+        mock.expect_execute().return_once(|_| Ok(Output));
+    }
+
+    // TODO: test execute
+}
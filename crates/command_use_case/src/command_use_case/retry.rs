@@ -0,0 +1,102 @@
+use std::{future::Future, time::Duration};
+
+use rand::Rng as _;
+
+/// Number of attempts of a read-modify-write whose read failed transiently or whose write lost a race
+const MAX_ATTEMPTS: usize = 3;
+
+/// The upper bound of the first wait between attempts. It doubles on each attempt
+const BASE_DELAY: Duration = Duration::from_millis(20);
+
+pub(crate) trait Retryable {
+    /// Whether running the whole read-modify-write again can succeed without writing twice
+    fn is_retryable(&self) -> bool;
+}
+
+pub(crate) async fn retry<T, E, F, Fut>(mut f: F) -> Result<T, E>
+where
+    E: Retryable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Err(e) if attempt < MAX_ATTEMPTS && e.is_retryable() => {
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The wait after the `attempt`-th failed attempt: a random duration up to
+/// `BASE_DELAY * 2^(attempt - 1)` ("full jitter"), so that the writers that lost the same race
+/// do not collide again
+fn backoff(attempt: usize) -> Duration {
+    let max = BASE_DELAY
+        * 2_u32.pow(
+            u32::try_from(attempt.saturating_sub(1))
+                .unwrap_or(u32::MAX)
+                .min(16),
+        );
+    max.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Error(bool);
+
+    impl Retryable for Error {
+        fn is_retryable(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        for _ in 0..100 {
+            assert!(backoff(1) <= BASE_DELAY);
+            assert!(backoff(2) <= BASE_DELAY * 2);
+            assert!(backoff(3) <= BASE_DELAY * 4);
+        }
+        assert!(backoff(usize::MAX) <= BASE_DELAY * 2_u32.pow(16));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let count = AtomicUsize::new(0);
+        let result = retry(|| async {
+            match count.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(Error(true)),
+                n => Ok(n),
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(1));
+
+        let count = AtomicUsize::new(0);
+        let result = retry(|| async {
+            count.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(Error(true))
+        })
+        .await;
+        assert_eq!(result, Err(Error(true)));
+        assert_eq!(count.load(Ordering::SeqCst), MAX_ATTEMPTS);
+
+        let count = AtomicUsize::new(0);
+        let result = retry(|| async {
+            count.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(Error(false))
+        })
+        .await;
+        assert_eq!(result, Err(Error(false)));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
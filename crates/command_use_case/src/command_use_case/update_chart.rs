@@ -4,16 +4,25 @@ use write_model::value_object::ChartId;
 
 #[cfg(any(test, feature = "test-util"))]
 use crate::command_use_case::port::ChartRepository;
+use crate::command_use_case::{
+    port::chart_repository,
+    retry::{retry, Retryable},
+};
 use crate::port::HasChartRepository;
 
 #[derive(Debug)]
 pub struct Input {
     pub chart_id: String,
+    /// The version the client last saw. `None` skips the check.
+    pub expected_version: Option<u32>,
     pub title: String,
 }
 
 #[derive(Debug)]
-pub struct Output;
+pub struct Output {
+    /// The version after the change
+    pub version: u32,
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -27,6 +36,18 @@ pub enum Error {
     ChartStore(#[source] crate::command_use_case::port::chart_repository::Error),
     #[error("chart update")]
     ChartUpdate(#[source] write_model::aggregate::chart::Error),
+    #[error("version mismatch (expected = {expected}, actual = {actual})")]
+    VersionMismatch { actual: u32, expected: u32 },
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::ChartFind(chart_repository::Error::Unavailable(_))
+                | Error::ChartStore(chart_repository::Error::Conflict(_))
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -35,17 +56,30 @@ pub trait UpdateChart: HasChartRepository {
     async fn execute(&self, input: Input) -> Result<Output, Error> {
         let chart_repository = self.chart_repository();
         let chart_id = ChartId::from_str(&input.chart_id).map_err(Error::ChartId)?;
-        let chart = chart_repository
-            .find(chart_id)
-            .await
-            .map_err(Error::ChartFind)?
-            .ok_or(Error::ChartNotFound(chart_id))?;
-        let (_, events) = chart.update(input.title).map_err(Error::ChartUpdate)?;
-        chart_repository
-            .store(Some(chart.version()), &events)
-            .await
-            .map_err(Error::ChartStore)?;
-        Ok(Output)
+        retry(|| async {
+            let chart = chart_repository
+                .find(chart_id)
+                .await
+                .map_err(Error::ChartFind)?
+                .ok_or(Error::ChartNotFound(chart_id))?;
+            if let Some(expected) = input.expected_version {
+                let actual = u32::from(chart.version());
+                if actual != expected {
+                    return Err(Error::VersionMismatch { actual, expected });
+                }
+            }
+            let (updated, events) = chart
+                .update(input.title.clone())
+                .map_err(Error::ChartUpdate)?;
+            chart_repository
+                .store(Some(chart.version()), &events)
+                .await
+                .map_err(Error::ChartStore)?;
+            Ok(Output {
+                version: u32::from(updated.version()),
+            })
+        })
+        .await
     }
 }
 
@@ -74,7 +108,8 @@ mod tests {
     #[test]
     fn test_mock() {
         let mut mock = MockUpdateChart::new();
-        mock.expect_execute().return_once(|_| Ok(Output));
+        mock.expect_execute()
+            .return_once(|_| Ok(Output { version: 2 }));
     }
 
     // TODO: test execute
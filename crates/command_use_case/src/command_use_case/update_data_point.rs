@@ -4,16 +4,25 @@ use write_model::value_object::{DataPointId, YValue};
 
 #[cfg(any(test, feature = "test-util"))]
 use crate::command_use_case::port::DataPointRepository;
+use crate::command_use_case::{
+    port::data_point_repository,
+    retry::{retry, Retryable},
+};
 use crate::port::HasDataPointRepository;
 
 #[derive(Debug)]
 pub struct Input {
     pub data_point_id: String,
-    pub y_value: u32,
+    /// The version the client last saw. `None` skips the check.
+    pub expected_version: Option<u32>,
+    pub y_value: String,
 }
 
 #[derive(Debug)]
-pub struct Output;
+pub struct Output {
+    /// The version after the change
+    pub version: u32,
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -27,6 +36,20 @@ pub enum Error {
     DataPointStore(#[source] crate::command_use_case::port::data_point_repository::Error),
     #[error("data point update")]
     DataPointUpdate(#[source] write_model::aggregate::data_point::Error),
+    #[error("y value")]
+    YValue(#[source] write_model::value_object::y_value::Error),
+    #[error("version mismatch (expected = {expected}, actual = {actual})")]
+    VersionMismatch { actual: u32, expected: u32 },
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::DataPointFind(data_point_repository::Error::Unavailable(_))
+                | Error::DataPointStore(data_point_repository::Error::Conflict(_))
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -36,25 +59,37 @@ pub trait UpdateDataPoint: HasDataPointRepository {
         &self,
         Input {
             data_point_id,
+            expected_version,
             y_value,
         }: Input,
     ) -> Result<Output, Error> {
         let data_point_repository = self.data_point_repository();
 
         let data_point_id = DataPointId::from_str(&data_point_id).map_err(Error::DataPointId)?;
-        let y_value = YValue::from(y_value);
-
-        let data_point = data_point_repository
-            .find(data_point_id)
-            .await
-            .map_err(Error::DataPointFind)?
-            .ok_or(Error::DataPointNotFound(data_point_id))?;
-        let (_, events) = data_point.update(y_value).map_err(Error::DataPointUpdate)?;
-        data_point_repository
-            .store(Some(data_point.version()), &events)
-            .await
-            .map_err(Error::DataPointStore)?;
-        Ok(Output)
+        let y_value = YValue::from_str(&y_value).map_err(Error::YValue)?;
+
+        retry(|| async {
+            let data_point = data_point_repository
+                .find(data_point_id)
+                .await
+                .map_err(Error::DataPointFind)?
+                .ok_or(Error::DataPointNotFound(data_point_id))?;
+            if let Some(expected) = expected_version {
+                let actual = u32::from(data_point.version());
+                if actual != expected {
+                    return Err(Error::VersionMismatch { actual, expected });
+                }
+            }
+            let (updated, events) = data_point.update(y_value).map_err(Error::DataPointUpdate)?;
+            data_point_repository
+                .store(Some(data_point.version()), &events)
+                .await
+                .map_err(Error::DataPointStore)?;
+            Ok(Output {
+                version: u32::from(updated.version()),
+            })
+        })
+        .await
     }
 }
 
@@ -83,7 +118,8 @@ mod tests {
     #[test]
     fn test_mock() {
         let mut mock = MockUpdateDataPoint::new();
-        mock.expect_execute().return_once(|_| Ok(Output));
+        mock.expect_execute()
+            .return_once(|_| Ok(Output { version: 2 }));
     }
 
     // TODO: test execute
@@ -1,5 +1,8 @@
+mod error;
 mod file_system_chart_store;
 mod file_system_data_point_store;
+mod jsonl;
 
 pub use self::file_system_chart_store::*;
 pub use self::file_system_data_point_store::*;
+pub use self::jsonl::{Scan, UnparsableLine};
@@ -0,0 +1,65 @@
+use store_error::{BoxError, ErrorKind};
+
+fn error_kind(error: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    ErrorKind::of(error, |error| {
+        error.downcast_ref::<std::io::Error>().map(io_error_kind)
+    })
+}
+
+fn io_error_kind(error: &std::io::Error) -> ErrorKind {
+    use std::io::ErrorKind as IoErrorKind;
+    match error.kind() {
+        // the file or the device is busy for now. Reads can be retried
+        IoErrorKind::Interrupted
+        | IoErrorKind::ResourceBusy
+        | IoErrorKind::TimedOut
+        | IoErrorKind::WouldBlock => ErrorKind::Unavailable,
+        // the file is truncated or is not what this store wrote
+        IoErrorKind::InvalidData | IoErrorKind::UnexpectedEof => ErrorKind::Corrupted,
+        // e.g. permission denied or no space left. Retrying does not help
+        _ => ErrorKind::Other,
+    }
+}
+
+pub(crate) fn chart_repository_error(
+    error: BoxError,
+) -> command_use_case::port::chart_repository::Error {
+    command_use_case::port::chart_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_repository_error(
+    error: BoxError,
+) -> command_use_case::port::data_point_repository::Error {
+    command_use_case::port::data_point_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn chart_reader_error(error: BoxError) -> query_use_case::port::chart_reader::Error {
+    query_use_case::port::chart_reader::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_reader_error(
+    error: BoxError,
+) -> query_use_case::port::data_point_reader::Error {
+    query_use_case::port::data_point_reader::Error::new(error_kind(&*error), error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn test_error_kind() {
+        let kind = |kind: io::ErrorKind| -> ErrorKind {
+            let error: BoxError = io::Error::from(kind).into();
+            error_kind(&*error)
+        };
+        assert_eq!(kind(io::ErrorKind::Interrupted), ErrorKind::Unavailable);
+        assert_eq!(kind(io::ErrorKind::WouldBlock), ErrorKind::Unavailable);
+        assert_eq!(kind(io::ErrorKind::InvalidData), ErrorKind::Corrupted);
+        assert_eq!(kind(io::ErrorKind::UnexpectedEof), ErrorKind::Corrupted);
+        assert_eq!(kind(io::ErrorKind::PermissionDenied), ErrorKind::Other);
+        assert_eq!(kind(io::ErrorKind::NotFound), ErrorKind::Other);
+    }
+}
@@ -1,16 +1,27 @@
 use std::{
     collections::BTreeMap,
-    fs::{File, OpenOptions},
-    io::{BufRead, BufReader, Write},
+    fs::File,
+    io::{BufRead, BufReader},
     path::PathBuf,
+    str::FromStr as _,
     sync::Arc,
 };
 
+use query_use_case::projection;
+use store_error::{check_version, CorruptedError};
 use tokio::sync::Mutex;
 use write_model::{
     aggregate::Chart,
-    event::{BaseEvent, ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartUpdated},
-    value_object::{ChartId, Version},
+    event::{
+        BaseEvent, ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated,
+        ChartSeriesDeleted, ChartSeriesRenamed, ChartUpdated,
+    },
+    value_object::{ChartId, SeriesId, Version},
+};
+
+use crate::file_system_store::{
+    error::{chart_reader_error, chart_repository_error},
+    jsonl::{self, Scan},
 };
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -27,6 +38,9 @@ struct EventJson {
 enum EventJsonData {
     Created(EventJsonDataCreated),
     Deleted(EventJsonDataDeleted),
+    SeriesCreated(EventJsonDataSeriesCreated),
+    SeriesDeleted(EventJsonDataSeriesDeleted),
+    SeriesRenamed(EventJsonDataSeriesRenamed),
     Updated(EventJsonDataUpdated),
 }
 
@@ -38,6 +52,23 @@ struct EventJsonDataCreated {
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct EventJsonDataDeleted {}
 
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct EventJsonDataSeriesCreated {
+    name: String,
+    series_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct EventJsonDataSeriesDeleted {
+    series_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct EventJsonDataSeriesRenamed {
+    name: String,
+    series_id: String,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct EventJsonDataUpdated {
     title: String,
@@ -64,6 +95,23 @@ impl From<&ChartEvent> for EventJson {
                 ChartEventData::Deleted(ChartDeleted {}) => {
                     EventJsonData::Deleted(EventJsonDataDeleted {})
                 }
+                ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id }) => {
+                    EventJsonData::SeriesCreated(EventJsonDataSeriesCreated {
+                        name: name.to_owned(),
+                        series_id: series_id.to_string(),
+                    })
+                }
+                ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+                    EventJsonData::SeriesDeleted(EventJsonDataSeriesDeleted {
+                        series_id: series_id.to_string(),
+                    })
+                }
+                ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+                    EventJsonData::SeriesRenamed(EventJsonDataSeriesRenamed {
+                        name: name.to_owned(),
+                        series_id: series_id.to_string(),
+                    })
+                }
                 ChartEventData::Updated(ChartUpdated { title }) => {
                     EventJsonData::Updated(EventJsonDataUpdated {
                         title: title.to_owned(),
@@ -94,6 +142,23 @@ impl TryFrom<EventJson> for ChartEvent {
                 ChartEventData::Created(ChartCreated { title })
             }
             EventJsonData::Deleted(_) => ChartEventData::Deleted(ChartDeleted {}),
+            EventJsonData::SeriesCreated(EventJsonDataSeriesCreated { name, series_id }) => {
+                ChartEventData::SeriesCreated(ChartSeriesCreated {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventJsonData::SeriesDeleted(EventJsonDataSeriesDeleted { series_id }) => {
+                ChartEventData::SeriesDeleted(ChartSeriesDeleted {
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventJsonData::SeriesRenamed(EventJsonDataSeriesRenamed { name, series_id }) => {
+                ChartEventData::SeriesRenamed(ChartSeriesRenamed {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
             EventJsonData::Updated(EventJsonDataUpdated { title }) => {
                 ChartEventData::Updated(ChartUpdated { title })
             }
@@ -108,6 +173,12 @@ impl TryFrom<EventJson> for ChartEvent {
     }
 }
 
+fn parse_event_line(line: &str) -> Result<ChartEvent, Box<dyn std::error::Error + Send + Sync>> {
+    serde_json::from_str::<EventJson>(line)
+        .map_err(Into::into)
+        .and_then(ChartEvent::try_from)
+}
+
 struct Cache {
     command_data: BTreeMap<ChartId, Vec<ChartEvent>>,
     query_data: Vec<query_use_case::port::ChartQueryData>,
@@ -126,6 +197,24 @@ impl FileSystemChartStore {
         }
     }
 
+    /// Reads `charts.jsonl` without failing on unparsable lines. The streams are not validated
+    pub fn scan(&self) -> std::io::Result<Scan<ChartEvent>> {
+        jsonl::scan(&self.dir.join("charts.jsonl"), parse_event_line)
+    }
+
+    /// Rewrites `charts.jsonl` without its unparsable trailing lines (e.g. a line truncated by an
+    /// interrupted write) and returns the number of dropped lines. The original file is kept as
+    /// `charts.jsonl.bak`. Fails with `InvalidData` if an unparsable line is not trailing
+    pub async fn drop_trailing_unparsable_lines(&self) -> std::io::Result<usize> {
+        let mut cache = self.cache.lock().await;
+        let dropped = jsonl::drop_trailing_unparsable_lines(
+            &self.dir.join("charts.jsonl"),
+            parse_event_line,
+        )?;
+        *cache = None;
+        Ok(dropped)
+    }
+
     async fn find_impl(
         &self,
         id: ChartId,
@@ -142,11 +231,63 @@ impl FileSystemChartStore {
                 .get(&id)
             {
                 None => None,
-                Some(events) => Some(Chart::from_events(events)?),
+                Some(events) => {
+                    Some(Chart::from_events(events).map_err(|e| CorruptedError(e.into()))?)
+                }
             },
         )
     }
 
+    async fn find_all_events_impl(
+        &self,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .values()
+            .flatten()
+            .cloned()
+            .collect())
+    }
+
+    async fn find_events_impl(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .get(&id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn find_ids_impl(
+        &self,
+    ) -> Result<Vec<ChartId>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .keys()
+            .copied()
+            .collect())
+    }
+
     async fn get_impl(
         &self,
         id: ChartId,
@@ -169,13 +310,21 @@ impl FileSystemChartStore {
 
     async fn list_impl(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, Box<dyn std::error::Error + Send + Sync>>
     {
         let mut cache = self.cache.lock().await;
         if cache.is_none() {
             *cache = Some(self.load()?);
         }
-        Ok(cache.as_ref().expect("cache to be Some").query_data.clone())
+        Ok(query.apply(
+            cache
+                .as_ref()
+                .expect("cache to be Some")
+                .query_data
+                .iter()
+                .cloned(),
+        ))
     }
 
     fn load(&self) -> Result<Cache, Box<dyn std::error::Error + Send + Sync>> {
@@ -195,10 +344,10 @@ impl FileSystemChartStore {
             if size == 0 {
                 break;
             }
-            let event_json = serde_json::from_str::<EventJson>(&buf)?;
-            let event = ChartEvent::try_from(event_json)?;
+            let event = parse_event_line(&buf).map_err(CorruptedError)?;
             buf.clear();
-            Self::apply_event_to_query_data(&mut query_data, &event)?;
+            projection::apply_chart_event(&mut query_data, &event)
+                .map_err(|e| CorruptedError(e.into()))?;
             command_data
                 .entry(event.stream_id)
                 .or_insert_with(Vec::new)
@@ -224,70 +373,38 @@ impl FileSystemChartStore {
         if events.is_empty() {
             return Ok(());
         }
-        match current {
-            None => {
-                let id = events[0].stream_id;
-                cache.command_data.insert(id, events.to_vec());
-            }
-            Some(_version) => {
-                let id = events[0].stream_id;
-                let stored_events = cache.command_data.get_mut(&id).ok_or("not found")?;
-                // TODO: check version
-                stored_events.extend(events.to_vec());
-            }
-        }
+        let id = events[0].stream_id;
+        let stored_version = cache
+            .command_data
+            .get(&id)
+            .and_then(|stored_events| stored_events.last())
+            .map(|stored_event| stored_event.version);
+        check_version(current, stored_version)?;
         let mut data = events
             .iter()
             .map(|event| serde_json::to_string(&EventJson::from(event)))
             .collect::<serde_json::Result<Vec<String>>>()?
             .join("\n");
         data.push('\n');
-        let path_buf = self.dir.join("charts.jsonl");
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path_buf)?;
-        file.write_all(data.as_bytes())?;
+        jsonl::append(&self.dir.join("charts.jsonl"), data.as_bytes())?;
+
+        // the cache is updated only once the events are on disk, so that a failed write is not
+        // visible to the later reads
+        cache
+            .command_data
+            .entry(id)
+            .or_default()
+            .extend(events.to_vec());
 
         // query writer
         let query_data = &mut cache.query_data;
         for event in events {
-            Self::apply_event_to_query_data(query_data, event)?;
+            projection::apply_chart_event(query_data, event)
+                .map_err(|e| CorruptedError(e.into()))?;
         }
 
         Ok(())
     }
-
-    fn apply_event_to_query_data(
-        query_data: &mut Vec<query_use_case::port::ChartQueryData>,
-        event: &ChartEvent,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        match &event.data {
-            write_model::event::ChartEventData::Created(data) => {
-                query_data.push(query_use_case::port::ChartQueryData {
-                    created_at: event.at,
-                    id: event.stream_id,
-                    title: data.title.clone(),
-                });
-            }
-            write_model::event::ChartEventData::Deleted(_) => {
-                if let Some(index) = query_data
-                    .iter()
-                    .position(|chart| chart.id == event.stream_id)
-                {
-                    query_data.remove(index);
-                }
-            }
-            write_model::event::ChartEventData::Updated(data) => {
-                let index = query_data
-                    .iter()
-                    .position(|chart| chart.id == event.stream_id)
-                    .ok_or("not found")?;
-                query_data[index].title.clone_from(&data.title);
-            }
-        }
-        Ok(())
-    }
 }
 
 #[async_trait::async_trait]
@@ -296,9 +413,30 @@ impl command_use_case::port::ChartRepository for FileSystemChartStore {
         &self,
         id: ChartId,
     ) -> Result<Option<Chart>, command_use_case::port::chart_repository::Error> {
-        self.find_impl(id)
+        self.find_impl(id).await.map_err(chart_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_events_impl(id)
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<ChartId>, command_use_case::port::chart_repository::Error> {
+        self.find_ids_impl().await.map_err(chart_repository_error)
     }
 
     async fn store(
@@ -308,7 +446,7 @@ impl command_use_case::port::ChartRepository for FileSystemChartStore {
     ) -> Result<(), command_use_case::port::chart_repository::Error> {
         self.store_impl(current, events)
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
     }
 }
 
@@ -321,23 +459,22 @@ impl query_use_case::port::ChartReader for FileSystemChartStore {
         Option<query_use_case::port::ChartQueryData>,
         query_use_case::port::chart_reader::Error,
     > {
-        self.get_impl(id)
-            .await
-            .map_err(query_use_case::port::chart_reader::Error::from)
+        self.get_impl(id).await.map_err(chart_reader_error)
     }
 
     async fn list(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, query_use_case::port::chart_reader::Error>
     {
-        self.list_impl()
-            .await
-            .map_err(query_use_case::port::chart_reader::Error::from)
+        self.list_impl(query).await.map_err(chart_reader_error)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{fs::OpenOptions, io::Write as _};
+
     use command_use_case::port::ChartRepository;
     use tempdir::TempDir;
 
@@ -358,4 +495,180 @@ mod tests {
         assert_eq!(store.find(chart_id).await?, Some(state));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_series() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemChartStore::new(path_buf.clone());
+        let (state, events) = Chart::create("title1".to_string())?;
+        store.store(None, &events).await?;
+        let version = state.version();
+        let (state, events) = state.create_series("series1".to_string())?;
+        store.store(Some(version), &events).await?;
+        let series_id = state.series()[0].id();
+        let version = state.version();
+        let (state, events) = state.rename_series(series_id, "series2".to_string())?;
+        store.store(Some(version), &events).await?;
+
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert_eq!(store.find(state.id()).await?, Some(state.clone()));
+        let query_data = query_use_case::port::ChartReader::get(&store, state.id())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("not found"))?;
+        assert_eq!(query_data.series.len(), 1);
+        assert_eq!(query_data.series[0].id, series_id);
+        assert_eq!(query_data.series[0].name, "series2");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemChartStore::new(path_buf.clone());
+        let (state, events) = Chart::create("title1".to_string())?;
+        store.store(None, &events).await?;
+        assert!(matches!(
+            store.store(None, &events).await,
+            Err(command_use_case::port::chart_repository::Error::Conflict(_))
+        ));
+
+        let version = state.version();
+        let (updated, events) = state.update("title2".to_string())?;
+        store.store(Some(version), &events).await?;
+        let (_, stale_events) = state.update("title3".to_string())?;
+        assert!(matches!(
+            store.store(Some(version), &stale_events).await,
+            Err(command_use_case::port::chart_repository::Error::Conflict(_))
+        ));
+
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert_eq!(store.find(state.id()).await?, Some(updated));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_all_events() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert!(store.find_all_events().await?.is_empty());
+        let (chart1, events1) = Chart::create("title1".to_string())?;
+        store.store(None, &events1).await?;
+        let (chart2, events2) = Chart::create("title2".to_string())?;
+        store.store(None, &events2).await?;
+        let (_, events3) = chart1.update("title3".to_string())?;
+        store.store(Some(chart1.version()), &events3).await?;
+
+        let store = FileSystemChartStore::new(path_buf.clone());
+        let mut expected = [events1, events3].concat();
+        expected = if chart1.id() < chart2.id() {
+            [expected, events2].concat()
+        } else {
+            [events2, expected].concat()
+        };
+        assert_eq!(store.find_all_events().await?, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_write() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemChartStore::new(path_buf.clone());
+        let (chart1, events1) = Chart::create("title1".to_string())?;
+        store.store(None, &events1).await?;
+        // the appends to `charts.jsonl` fail from now on
+        std::fs::remove_file(path_buf.join("charts.jsonl"))?;
+        std::fs::create_dir(path_buf.join("charts.jsonl"))?;
+
+        let (chart2, events2) = Chart::create("title2".to_string())?;
+        assert!(store.store(None, &events2).await.is_err());
+        assert_eq!(store.find(chart1.id()).await?, Some(chart1));
+        assert_eq!(store.find(chart2.id()).await?, None);
+        assert_eq!(
+            query_use_case::port::ChartReader::get(&store, chart2.id()).await?,
+            None
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_corrupted() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        std::fs::write(path_buf.join("charts.jsonl"), "{\n")?;
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert!(matches!(
+            store.find(ChartId::generate()).await,
+            Err(command_use_case::port::chart_repository::Error::Corrupted(
+                _
+            ))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_drop_trailing_unparsable_lines() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert_eq!(
+            store.scan()?,
+            Scan {
+                events: vec![],
+                unparsable_lines: vec![]
+            }
+        );
+        let (chart, events) = Chart::create("title1".to_string())?;
+        store.store(None, &events).await?;
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(path_buf.join("charts.jsonl"))?;
+        file.write_all(b"not json\n{\"at\":")?;
+
+        let store = FileSystemChartStore::new(path_buf.clone());
+        assert!(store.find(chart.id()).await.is_err());
+        let scan = store.scan()?;
+        assert_eq!(scan.events, events);
+        assert_eq!(
+            scan.unparsable_lines
+                .iter()
+                .map(|unparsable_line| unparsable_line.line)
+                .collect::<Vec<usize>>(),
+            vec![2, 3]
+        );
+
+        assert_eq!(store.drop_trailing_unparsable_lines().await?, 2);
+        assert_eq!(store.find(chart.id()).await?, Some(chart));
+        assert!(store.scan()?.unparsable_lines.is_empty());
+        assert!(path_buf.join("charts.jsonl.bak").exists());
+        assert_eq!(store.drop_trailing_unparsable_lines().await?, 0);
+
+        // an unparsable line followed by a parsable one is not dropped
+        let content = std::fs::read_to_string(path_buf.join("charts.jsonl"))?;
+        let first_line = content.lines().next().unwrap_or_default().to_owned();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(path_buf.join("charts.jsonl"))?;
+        file.write_all(format!("not json\n{}\n", first_line).as_bytes())?;
+        let error = store
+            .drop_trailing_unparsable_lines()
+            .await
+            .expect_err("a line in the middle to be refused");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            std::fs::read_to_string(path_buf.join("charts.jsonl"))?,
+            format!("{}not json\n{}\n", content, first_line)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        store_test_kit::test_chart_store(&FileSystemChartStore::new(path_buf)).await
+    }
 }
@@ -1,11 +1,14 @@
 use std::{
     collections::BTreeMap,
-    fs::{File, OpenOptions},
-    io::{BufRead, BufReader, Write},
+    fs::File,
+    io::{BufRead, BufReader},
     path::PathBuf,
+    str::FromStr as _,
     sync::Arc,
 };
 
+use query_use_case::projection;
+use store_error::{check_version, CorruptedError};
 use tokio::sync::Mutex;
 use write_model::{
     aggregate::DataPoint,
@@ -16,6 +19,11 @@ use write_model::{
     value_object::{ChartId, DataPointId, Version, YValue},
 };
 
+use crate::file_system_store::{
+    error::{data_point_reader_error, data_point_repository_error},
+    jsonl::{self, Scan},
+};
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct EventJson {
     at: String,
@@ -35,7 +43,7 @@ enum EventJsonData {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct EventJsonDataCreated {
-    value: u32,
+    value: EventJsonValue,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -43,7 +51,32 @@ struct EventJsonDataDeleted {}
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct EventJsonDataUpdated {
-    value: u32,
+    value: EventJsonValue,
+}
+
+/// `U32` is the format written before `YValue` supported signed decimals.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+enum EventJsonValue {
+    U32(u32),
+    Decimal(String),
+}
+
+impl From<YValue> for EventJsonValue {
+    fn from(value: YValue) -> Self {
+        Self::Decimal(value.to_string())
+    }
+}
+
+impl TryFrom<EventJsonValue> for YValue {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(value: EventJsonValue) -> Result<Self, Self::Error> {
+        match value {
+            EventJsonValue::U32(value) => Ok(YValue::from(value)),
+            EventJsonValue::Decimal(value) => Ok(YValue::from_str(&value)?),
+        }
+    }
 }
 
 impl From<&DataPointEvent> for EventJson {
@@ -61,7 +94,7 @@ impl From<&DataPointEvent> for EventJson {
             data: match data {
                 DataPointEventData::Created(DataPointCreated { value }) => {
                     EventJsonData::Created(EventJsonDataCreated {
-                        value: u32::from(*value),
+                        value: EventJsonValue::from(*value),
                     })
                 }
                 DataPointEventData::Deleted(DataPointDeleted {}) => {
@@ -69,7 +102,7 @@ impl From<&DataPointEvent> for EventJson {
                 }
                 DataPointEventData::Updated(DataPointUpdated { value }) => {
                     EventJsonData::Updated(EventJsonDataUpdated {
-                        value: u32::from(*value),
+                        value: EventJsonValue::from(*value),
                     })
                 }
             },
@@ -95,13 +128,13 @@ impl TryFrom<EventJson> for DataPointEvent {
         let data = match data {
             EventJsonData::Created(EventJsonDataCreated { value }) => {
                 DataPointEventData::Created(DataPointCreated {
-                    value: YValue::from(value),
+                    value: YValue::try_from(value)?,
                 })
             }
             EventJsonData::Deleted(_) => DataPointEventData::Deleted(DataPointDeleted {}),
             EventJsonData::Updated(EventJsonDataUpdated { value }) => {
                 DataPointEventData::Updated(DataPointUpdated {
-                    value: YValue::from(value),
+                    value: YValue::try_from(value)?,
                 })
             }
         };
@@ -115,6 +148,14 @@ impl TryFrom<EventJson> for DataPointEvent {
     }
 }
 
+fn parse_event_line(
+    line: &str,
+) -> Result<DataPointEvent, Box<dyn std::error::Error + Send + Sync>> {
+    serde_json::from_str::<EventJson>(line)
+        .map_err(Into::into)
+        .and_then(DataPointEvent::try_from)
+}
+
 struct Cache {
     command_data: BTreeMap<DataPointId, Vec<DataPointEvent>>,
     query_data: Vec<query_use_case::port::DataPointQueryData>,
@@ -133,6 +174,24 @@ impl FileSystemDataPointStore {
         }
     }
 
+    /// Reads `data_points.jsonl` without failing on unparsable lines. The streams are not validated
+    pub fn scan(&self) -> std::io::Result<Scan<DataPointEvent>> {
+        jsonl::scan(&self.dir.join("data_points.jsonl"), parse_event_line)
+    }
+
+    /// Rewrites `data_points.jsonl` without its unparsable trailing lines (e.g. a line truncated by an
+    /// interrupted write) and returns the number of dropped lines. The original file is kept as
+    /// `data_points.jsonl.bak`. Fails with `InvalidData` if an unparsable line is not trailing
+    pub async fn drop_trailing_unparsable_lines(&self) -> std::io::Result<usize> {
+        let mut cache = self.cache.lock().await;
+        let dropped = jsonl::drop_trailing_unparsable_lines(
+            &self.dir.join("data_points.jsonl"),
+            parse_event_line,
+        )?;
+        *cache = None;
+        Ok(dropped)
+    }
+
     async fn find_impl(
         &self,
         id: DataPointId,
@@ -149,11 +208,63 @@ impl FileSystemDataPointStore {
                 .get(&id)
             {
                 None => None,
-                Some(events) => Some(DataPoint::from_events(events)?),
+                Some(events) => {
+                    Some(DataPoint::from_events(events).map_err(|e| CorruptedError(e.into()))?)
+                }
             },
         )
     }
 
+    async fn find_all_events_impl(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .values()
+            .flatten()
+            .cloned()
+            .collect())
+    }
+
+    async fn find_events_impl(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .get(&id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn find_ids_impl(
+        &self,
+    ) -> Result<Vec<DataPointId>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            *cache = Some(self.load()?);
+        }
+        Ok(cache
+            .as_ref()
+            .expect("cache to be Some")
+            .command_data
+            .keys()
+            .copied()
+            .collect())
+    }
+
     async fn get_impl(
         &self,
         id: DataPointId,
@@ -171,7 +282,9 @@ impl FileSystemDataPointStore {
             .query_data
             .iter()
             .find(|data_point| {
-                data_point.chart_id == id.chart_id() && data_point.x_value == id.x_value()
+                data_point.chart_id == id.chart_id()
+                    && data_point.series_id == id.series_id()
+                    && data_point.x_value == id.x_value()
             })
             .cloned())
     }
@@ -179,6 +292,8 @@ impl FileSystemDataPointStore {
     async fn list_impl(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
@@ -192,7 +307,11 @@ impl FileSystemDataPointStore {
             .expect("cache to be Some")
             .query_data
             .iter()
-            .filter(|data_point| data_point.chart_id == chart_id)
+            .filter(|data_point| {
+                data_point.chart_id == chart_id
+                    && series.matches(data_point.series_id)
+                    && range.contains(data_point.x_value)
+            })
             .cloned()
             .collect::<Vec<query_use_case::port::DataPointQueryData>>())
     }
@@ -214,10 +333,10 @@ impl FileSystemDataPointStore {
             if size == 0 {
                 break;
             }
-            let event_json = serde_json::from_str::<EventJson>(&buf)?;
-            let event = DataPointEvent::try_from(event_json)?;
+            let event = parse_event_line(&buf).map_err(CorruptedError)?;
             buf.clear();
-            Self::apply_event_to_query_data(&mut query_data, &event)?;
+            projection::apply_data_point_event(&mut query_data, &event)
+                .map_err(|e| CorruptedError(e.into()))?;
             command_data
                 .entry(event.stream_id)
                 .or_insert_with(Vec::new)
@@ -243,74 +362,38 @@ impl FileSystemDataPointStore {
         if events.is_empty() {
             return Ok(());
         }
-        match current {
-            None => {
-                let id = events[0].stream_id;
-                cache.command_data.insert(id, events.to_vec());
-            }
-            Some(_version) => {
-                let id = events[0].stream_id;
-                let stored_events = cache.command_data.get_mut(&id).ok_or("not found")?;
-                // TODO: check version
-                stored_events.extend(events.to_vec());
-            }
-        }
+        let id = events[0].stream_id;
+        let stored_version = cache
+            .command_data
+            .get(&id)
+            .and_then(|stored_events| stored_events.last())
+            .map(|stored_event| stored_event.version);
+        check_version(current, stored_version)?;
         let mut data = events
             .iter()
             .map(|event| serde_json::to_string(&EventJson::from(event)))
             .collect::<serde_json::Result<Vec<String>>>()?
             .join("\n");
         data.push('\n');
-        let path_buf = self.dir.join("data_points.jsonl");
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path_buf)?;
-        file.write_all(data.as_bytes())?;
+        jsonl::append(&self.dir.join("data_points.jsonl"), data.as_bytes())?;
+
+        // the cache is updated only once the events are on disk, so that a failed write is not
+        // visible to the later reads
+        cache
+            .command_data
+            .entry(id)
+            .or_default()
+            .extend(events.to_vec());
 
         // query writer
         let query_data = &mut cache.query_data;
         for event in events {
-            Self::apply_event_to_query_data(query_data, event)?;
+            projection::apply_data_point_event(query_data, event)
+                .map_err(|e| CorruptedError(e.into()))?;
         }
 
         Ok(())
     }
-
-    fn apply_event_to_query_data(
-        query_data: &mut Vec<query_use_case::port::DataPointQueryData>,
-        event: &DataPointEvent,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        match &event.data {
-            write_model::event::DataPointEventData::Created(data) => {
-                query_data.push(query_use_case::port::DataPointQueryData {
-                    chart_id: event.stream_id.chart_id(),
-                    created_at: event.at,
-                    x_value: event.stream_id.x_value(),
-                    y_value: data.value,
-                });
-            }
-            write_model::event::DataPointEventData::Deleted(_) => {
-                if let Some(index) = query_data.iter().position(|data_point| {
-                    data_point.chart_id == event.stream_id.chart_id()
-                        && data_point.x_value == event.stream_id.x_value()
-                }) {
-                    query_data.remove(index);
-                }
-            }
-            write_model::event::DataPointEventData::Updated(data) => {
-                let index = query_data
-                    .iter()
-                    .position(|data_point| {
-                        data_point.chart_id == event.stream_id.chart_id()
-                            && data_point.x_value == event.stream_id.x_value()
-                    })
-                    .ok_or("not found")?;
-                query_data[index].y_value = data.value;
-            }
-        }
-        Ok(())
-    }
 }
 
 #[async_trait::async_trait]
@@ -321,7 +404,32 @@ impl command_use_case::port::DataPointRepository for FileSystemDataPointStore {
     ) -> Result<Option<DataPoint>, command_use_case::port::data_point_repository::Error> {
         self.find_impl(id)
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_events_impl(id)
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<DataPointId>, command_use_case::port::data_point_repository::Error> {
+        self.find_ids_impl()
+            .await
+            .map_err(data_point_repository_error)
     }
 
     async fn store(
@@ -331,7 +439,7 @@ impl command_use_case::port::DataPointRepository for FileSystemDataPointStore {
     ) -> Result<(), command_use_case::port::data_point_repository::Error> {
         self.store_impl(current, events)
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
     }
 }
 
@@ -344,21 +452,21 @@ impl query_use_case::port::DataPointReader for FileSystemDataPointStore {
         Option<query_use_case::port::DataPointQueryData>,
         query_use_case::port::data_point_reader::Error,
     > {
-        self.get_impl(id)
-            .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+        self.get_impl(id).await.map_err(data_point_reader_error)
     }
 
     async fn list(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         query_use_case::port::data_point_reader::Error,
     > {
-        self.list_impl(chart_id)
+        self.list_impl(chart_id, series, range)
             .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+            .map_err(data_point_reader_error)
     }
 }
 
@@ -380,6 +488,7 @@ mod tests {
         let chart_id = ChartId::generate();
         let (state, events) = DataPoint::create(
             chart_id,
+            None,
             XValue::from_str("2020-01-02")?,
             YValue::from(123_u32),
         )?;
@@ -392,4 +501,58 @@ mod tests {
         assert_eq!(store.find(data_point_id).await?, Some(state));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_decimal_y_value() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let store = FileSystemDataPointStore::new(path_buf.clone());
+        let (state, events) = DataPoint::create(
+            ChartId::generate(),
+            None,
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("-12.5")?,
+        )?;
+        store.store(None, &events).await?;
+
+        let store = FileSystemDataPointStore::new(path_buf.clone());
+        assert_eq!(store.find(state.id()).await?, Some(state));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_u32_y_value() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        let data_point_id =
+            DataPointId::from_str("67051e1b-fc32-43c8-899f-e2c73a1319f4:2020-01-02")?;
+        std::fs::write(
+            path_buf.join("data_points.jsonl"),
+            format!(
+                "{}\n",
+                serde_json::json!({
+                    "at": "2020-01-02T03:04:05.678Z",
+                    "data": { "type": "Created", "value": 123 },
+                    "id": "1b8e1f6e-4d0a-4a51-9a55-3c1a8a6f0b8e",
+                    "stream_id": data_point_id.to_string(),
+                    "version": 1
+                })
+            ),
+        )?;
+
+        let store = FileSystemDataPointStore::new(path_buf.clone());
+        let data_point = store.find(data_point_id).await?;
+        assert_eq!(
+            data_point.map(|data_point| data_point.y_value()),
+            Some(YValue::from(123_u32))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path_buf = temp_dir.into_path();
+        store_test_kit::test_data_point_store(&FileSystemDataPointStore::new(path_buf)).await
+    }
 }
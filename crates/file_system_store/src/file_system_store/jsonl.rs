@@ -0,0 +1,166 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write as _},
+    path::Path,
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A line of a JSON Lines file that cannot be read as an event
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnparsableLine {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+/// The events of a JSON Lines file in file order. The streams are not validated
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scan<E> {
+    pub events: Vec<E>,
+    pub unparsable_lines: Vec<UnparsableLine>,
+}
+
+fn lines(content: &[u8]) -> Vec<&[u8]> {
+    let content = content.strip_suffix(b"\n").unwrap_or(content);
+    if content.is_empty() {
+        return vec![];
+    }
+    content.split(|b| *b == b'\n').collect()
+}
+
+fn parse_line<E>(line: &[u8], parse: &impl Fn(&str) -> Result<E, BoxError>) -> Result<E, BoxError> {
+    std::str::from_utf8(line)
+        .map_err(BoxError::from)
+        .and_then(parse)
+}
+
+/// Appends `data` (whole lines) to the file and syncs it. On failure, the file is truncated back
+/// to its previous length: a torn line would be glued onto the next append
+pub(crate) fn append(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    write_or_truncate(&mut file, |file| {
+        file.write_all(data)?;
+        file.sync_data()
+    })
+}
+
+fn write_or_truncate(
+    file: &mut File,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> io::Result<()> {
+    let len = file.metadata()?.len();
+    write(file).inspect_err(|_| {
+        // ignore truncate error, the write error is returned
+        let _ = file.set_len(len).and_then(|()| file.sync_data());
+    })
+}
+
+/// Reads every line of the file (no file = no lines), collecting the unparsable ones
+pub(crate) fn scan<E>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<E, BoxError>,
+) -> io::Result<Scan<E>> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let mut events = vec![];
+    let mut unparsable_lines = vec![];
+    for (index, line) in lines(&content).into_iter().enumerate() {
+        match parse_line(line, &parse) {
+            Ok(event) => events.push(event),
+            Err(e) => unparsable_lines.push(UnparsableLine {
+                line: index + 1,
+                message: e.to_string(),
+            }),
+        }
+    }
+    Ok(Scan {
+        events,
+        unparsable_lines,
+    })
+}
+
+/// Rewrites the file without its unparsable trailing lines (e.g. a line truncated by an
+/// interrupted write) and returns the number of dropped lines. The original file is kept as
+/// `<file>.bak`.
+///
+/// Fails with `InvalidData`, writing nothing, if an unparsable line is followed by a parsable
+/// one: dropping it could lose an event in the middle of the log
+pub(crate) fn drop_trailing_unparsable_lines<E>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<E, BoxError>,
+) -> io::Result<usize> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let lines = lines(&content);
+    let kept_len = lines
+        .iter()
+        .rposition(|line| parse_line(line, &parse).is_ok())
+        .map_or(0, |index| index + 1);
+    if let Some(index) = lines[..kept_len]
+        .iter()
+        .position(|line| parse_line(line, &parse).is_err())
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "line {} is unparsable but followed by parsable lines (path = {})",
+                index + 1,
+                path.display()
+            ),
+        ));
+    }
+    let dropped = lines.len() - kept_len;
+    if dropped == 0 {
+        return Ok(0);
+    }
+    let mut kept = Vec::with_capacity(content.len());
+    for line in &lines[..kept_len] {
+        kept.extend_from_slice(line);
+        kept.push(b'\n');
+    }
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    fs::copy(path, &backup)?;
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(&kept)?;
+    file.sync_all()?;
+    fs::rename(&temp, path)?;
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_append_failure() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("file_system_store")?;
+        let path = temp_dir.path().join("events.jsonl");
+        append(&path, b"1\n")?;
+
+        let mut file = OpenOptions::new().append(true).open(&path)?;
+        let result = write_or_truncate(&mut file, |file| {
+            file.write_all(b"2")?;
+            Err(io::Error::other("disk full"))
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read(&path)?, b"1\n");
+
+        append(&path, b"3\n")?;
+        let scan = scan(&path, |line| line.parse::<u32>().map_err(BoxError::from))?;
+        assert_eq!(scan.events, vec![1, 3]);
+        assert!(scan.unparsable_lines.is_empty());
+        Ok(())
+    }
+}
@@ -59,6 +59,38 @@ where
 #[error(transparent)]
 pub struct Error(#[from] InnerError);
 
+impl Error {
+    /// `ABORTED`, `ALREADY_EXISTS` or `FAILED_PRECONDITION`: the write lost a race with another write
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self.code(),
+            Some(
+                tonic::Code::Aborted | tonic::Code::AlreadyExists | tonic::Code::FailedPrecondition
+            )
+        )
+    }
+
+    /// `UNAVAILABLE`, `DEADLINE_EXCEEDED` or `RESOURCE_EXHAUSTED`, or the token could not be fetched
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self.0, InnerError::Token(_))
+            || matches!(
+                self.code(),
+                Some(
+                    tonic::Code::Unavailable
+                        | tonic::Code::DeadlineExceeded
+                        | tonic::Code::ResourceExhausted
+                )
+            )
+    }
+
+    fn code(&self) -> Option<tonic::Code> {
+        match &self.0 {
+            InnerError::Status(status) => Some(status.code()),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 enum InnerError {
     #[error("deserialize")]
@@ -90,8 +122,52 @@ pub struct FirestoreClient {
     token_source: Arc<dyn google_cloud_token::TokenSource>,
 }
 
+#[derive(Debug)]
+struct EmulatorTokenSource;
+
+#[async_trait::async_trait]
+impl google_cloud_token::TokenSource for EmulatorTokenSource {
+    async fn token(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        // The emulator accepts any bearer token. `owner` bypasses the security rules.
+        Ok("Bearer owner".to_owned())
+    }
+}
+
 impl FirestoreClient {
+    /// Connects to the Firestore emulator if `FIRESTORE_EMULATOR_HOST` is set, otherwise to
+    /// Firestore with the default Google credentials.
+    ///
+    /// The emulator's project id is read from `GOOGLE_CLOUD_PROJECT` (default: `demo-project`).
     pub async fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        match std::env::var("FIRESTORE_EMULATOR_HOST") {
+            Ok(emulator_host) if !emulator_host.is_empty() => {
+                let project_id = std::env::var("GOOGLE_CLOUD_PROJECT")
+                    .unwrap_or_else(|_| "demo-project".to_owned());
+                Self::new_with_emulator(&emulator_host, &project_id).await
+            }
+            _ => Self::new_with_default_credentials().await,
+        }
+    }
+
+    /// Connects to the Firestore emulator running at `emulator_host` (e.g. `localhost:8080`).
+    pub async fn new_with_emulator(
+        emulator_host: &str,
+        project_id: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let channel = tonic::transport::Channel::from_shared(format!("http://{}", emulator_host))?
+            .connect()
+            .await?;
+        let database_name =
+            DatabaseName::from_project_id(project_id).map_err(InnerError::ProjectId)?;
+        Ok(Self {
+            channel,
+            database_name,
+            token_source: Arc::new(EmulatorTokenSource),
+        })
+    }
+
+    async fn new_with_default_credentials() -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    {
         let default_token_source_provider =
             google_cloud_auth::token::DefaultTokenSourceProvider::new(
                 google_cloud_auth::project::Config {
@@ -201,6 +277,23 @@ impl FirestoreClient {
         Ok(())
     }
 
+    /// Returns the reference value of the document. Used as the `__name__` value of `start_after`
+    pub fn document_reference(
+        &self,
+        document_path: &DocumentPath,
+    ) -> google_api_proto::google::firestore::v1::Value {
+        google_api_proto::google::firestore::v1::Value {
+            value_type: Some(
+                google_api_proto::google::firestore::v1::value::ValueType::ReferenceValue(
+                    self.database_name
+                        .doc(document_path.clone())
+                        .expect("document_path to be valid document_name")
+                        .to_string(),
+                ),
+            ),
+        }
+    }
+
     pub async fn get_document<T>(
         &self,
         document_path: &DocumentPath,
@@ -1,4 +1,4 @@
-#[ignore = "it requires a Firestore instance to be running"]
+#[ignore = "it requires a Firestore instance to be running (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
 #[tokio::test]
 async fn test() -> anyhow::Result<()> {
     use anyhow::Context as _;
@@ -1,10 +1,14 @@
 pub(crate) mod converter;
+pub(crate) mod error;
 mod firestore_chart_store;
 mod firestore_data_point_store;
 pub(crate) mod firestore_event_store;
+mod firestore_query_data_rebuilder;
 pub(crate) mod firestore_query_data_store;
 pub(crate) mod path;
+pub(crate) mod projection;
 pub(crate) mod schema;
 
 pub use self::firestore_chart_store::*;
 pub use self::firestore_data_point_store::*;
+pub use self::firestore_query_data_rebuilder::*;
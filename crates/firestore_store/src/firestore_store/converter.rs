@@ -3,15 +3,16 @@ use std::str::FromStr as _;
 use firestore_client::Document;
 use write_model::{
     event::{
-        ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartUpdated, DataPointCreated,
-        DataPointDeleted, DataPointEvent, DataPointEventData, DataPointUpdated, Event,
+        ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated,
+        ChartSeriesDeleted, ChartSeriesRenamed, ChartUpdated, DataPointCreated, DataPointDeleted,
+        DataPointEvent, DataPointEventData, DataPointUpdated, Event,
     },
-    value_object::{ChartId, DateTime, XValue, YValue},
+    value_object::{ChartId, DateTime, SeriesId, Version, XValue, YValue},
 };
 
 use crate::schema::{
     self, ChartDocumentData, ChartEventDataDocumentData, DataPointDocumentData,
-    DataPointEventDataDocumentData, EventDataDocumentData, EventDocumentData,
+    DataPointEventDataDocumentData, EventDataDocumentData, EventDocumentData, YValueDocumentData,
 };
 
 pub(crate) fn query_data_from_document(
@@ -20,7 +21,26 @@ pub(crate) fn query_data_from_document(
     Ok(query_use_case::port::ChartQueryData {
         created_at: DateTime::from_str(&document.fields.created_at)?,
         id: ChartId::from_str(document.name.document_id().as_ref())?,
+        series: document
+            .fields
+            .series
+            .into_iter()
+            .map(|series| {
+                Ok(query_use_case::port::SeriesQueryData {
+                    id: SeriesId::from_str(&series.id)?,
+                    name: series.name,
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error + Send + Sync>>>()?,
         title: document.fields.title,
+        updated_at: DateTime::from_str(
+            document
+                .fields
+                .updated_at
+                .as_deref()
+                .unwrap_or(&document.fields.created_at),
+        )?,
+        version: Version::try_from(document.fields.version)?,
     })
 }
 
@@ -30,8 +50,15 @@ pub(crate) fn data_point_query_data_from_document(
     Ok(query_use_case::port::DataPointQueryData {
         chart_id: ChartId::from_str(&document.fields.chart_id)?,
         created_at: DateTime::from_str(&document.fields.created_at)?,
+        series_id: document
+            .fields
+            .series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()?,
+        version: Version::try_from(document.fields.version)?,
         x_value: XValue::from_str(&document.fields.x_value)?,
-        y_value: YValue::from(u32::try_from(document.fields.y_value)?),
+        y_value: y_value_from_document_data(document.fields.y_value)?,
     })
 }
 
@@ -46,6 +73,23 @@ pub(crate) fn chart_event_from_document(
                     ChartEventData::Created(ChartCreated { title: data.title })
                 }
                 ChartEventDataDocumentData::Deleted(_) => ChartEventData::Deleted(ChartDeleted {}),
+                ChartEventDataDocumentData::SeriesCreated(data) => {
+                    ChartEventData::SeriesCreated(ChartSeriesCreated {
+                        name: data.name,
+                        series_id: SeriesId::from_str(&data.series_id)?,
+                    })
+                }
+                ChartEventDataDocumentData::SeriesDeleted(data) => {
+                    ChartEventData::SeriesDeleted(ChartSeriesDeleted {
+                        series_id: SeriesId::from_str(&data.series_id)?,
+                    })
+                }
+                ChartEventDataDocumentData::SeriesRenamed(data) => {
+                    ChartEventData::SeriesRenamed(ChartSeriesRenamed {
+                        name: data.name,
+                        series_id: SeriesId::from_str(&data.series_id)?,
+                    })
+                }
                 ChartEventDataDocumentData::Updated(data) => {
                     ChartEventData::Updated(ChartUpdated { title: data.title })
                 }
@@ -68,7 +112,7 @@ pub(crate) fn data_point_event_from_document(
             EventDataDocumentData::DataPoint(event_data) => match event_data {
                 DataPointEventDataDocumentData::Created(data) => {
                     DataPointEventData::Created(DataPointCreated {
-                        value: YValue::from(u32::try_from(data.value)?),
+                        value: y_value_from_document_data(data.value)?,
                     })
                 }
                 DataPointEventDataDocumentData::Deleted(_) => {
@@ -76,7 +120,7 @@ pub(crate) fn data_point_event_from_document(
                 }
                 DataPointEventDataDocumentData::Updated(data) => {
                     DataPointEventData::Updated(DataPointUpdated {
-                        value: YValue::from(u32::try_from(data.value)?),
+                        value: y_value_from_document_data(data.value)?,
                     })
                 }
             },
@@ -141,6 +185,29 @@ fn document_data_from_chart_event_data(
         write_model::event::ChartEventData::Deleted(_) => {
             ChartEventDataDocumentData::Deleted(schema::chart_event_data_document_data::Deleted {})
         }
+        write_model::event::ChartEventData::SeriesCreated(data) => {
+            ChartEventDataDocumentData::SeriesCreated(
+                schema::chart_event_data_document_data::SeriesCreated {
+                    name: data.name.to_owned(),
+                    series_id: data.series_id.to_string(),
+                },
+            )
+        }
+        write_model::event::ChartEventData::SeriesDeleted(data) => {
+            ChartEventDataDocumentData::SeriesDeleted(
+                schema::chart_event_data_document_data::SeriesDeleted {
+                    series_id: data.series_id.to_string(),
+                },
+            )
+        }
+        write_model::event::ChartEventData::SeriesRenamed(data) => {
+            ChartEventDataDocumentData::SeriesRenamed(
+                schema::chart_event_data_document_data::SeriesRenamed {
+                    name: data.name.to_owned(),
+                    series_id: data.series_id.to_string(),
+                },
+            )
+        }
         write_model::event::ChartEventData::Updated(data) => {
             ChartEventDataDocumentData::Updated(schema::chart_event_data_document_data::Updated {
                 title: data.title.to_owned(),
@@ -156,7 +223,7 @@ fn document_data_from_data_point_event_data(
         write_model::event::DataPointEventData::Created(data) => {
             DataPointEventDataDocumentData::Created(
                 schema::data_point_event_data_document_data::Created {
-                    value: i64::from(u32::from(data.value)),
+                    value: document_data_from_y_value(data.value),
                 },
             )
         }
@@ -168,9 +235,22 @@ fn document_data_from_data_point_event_data(
         write_model::event::DataPointEventData::Updated(data) => {
             DataPointEventDataDocumentData::Updated(
                 schema::data_point_event_data_document_data::Updated {
-                    value: i64::from(u32::from(data.value)),
+                    value: document_data_from_y_value(data.value),
                 },
             )
         }
     }
 }
+
+fn y_value_from_document_data(
+    document_data: YValueDocumentData,
+) -> Result<YValue, Box<dyn std::error::Error + Send + Sync>> {
+    match document_data {
+        YValueDocumentData::Integer(value) => Ok(YValue::from(u32::try_from(value)?)),
+        YValueDocumentData::Decimal(value) => Ok(YValue::from_str(&value)?),
+    }
+}
+
+fn document_data_from_y_value(y_value: YValue) -> YValueDocumentData {
+    YValueDocumentData::Decimal(y_value.to_string())
+}
@@ -0,0 +1,41 @@
+use store_error::{BoxError, ErrorKind};
+
+/// The events were stored but the query data could not be updated
+#[derive(Debug, thiserror::Error)]
+#[error("query data update")]
+pub(crate) struct QueryDataUpdateError(#[source] pub(crate) BoxError);
+
+fn error_kind(error: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    ErrorKind::of(error, |error| {
+        let error = error.downcast_ref::<firestore_client::Error>()?;
+        if error.is_conflict() {
+            Some(ErrorKind::Conflict)
+        } else if error.is_unavailable() {
+            Some(ErrorKind::Unavailable)
+        } else {
+            None
+        }
+    })
+}
+
+pub(crate) fn chart_repository_error(
+    error: BoxError,
+) -> command_use_case::port::chart_repository::Error {
+    command_use_case::port::chart_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_repository_error(
+    error: BoxError,
+) -> command_use_case::port::data_point_repository::Error {
+    command_use_case::port::data_point_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn chart_reader_error(error: BoxError) -> query_use_case::port::chart_reader::Error {
+    query_use_case::port::chart_reader::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_reader_error(
+    error: BoxError,
+) -> query_use_case::port::data_point_reader::Error {
+    query_use_case::port::data_point_reader::Error::new(error_kind(&*error), error)
+}
@@ -1,8 +1,11 @@
 use std::str::FromStr;
 
 use crate::{
-    firestore_event_store::FirestoreEventStore, firestore_query_data_store::FirestoreQueryDataStore,
+    error::{chart_reader_error, chart_repository_error, QueryDataUpdateError},
+    firestore_event_store::FirestoreEventStore,
+    firestore_query_data_store::FirestoreQueryDataStore,
 };
+use store_error::CorruptedError;
 use write_model::{
     aggregate::Chart,
     event::{ChartEvent, Event},
@@ -34,9 +37,10 @@ impl FirestoreChartStore {
 
     async fn reader_list_impl(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, Box<dyn std::error::Error + Send + Sync>>
     {
-        self.query_data_store.list_charts().await
+        self.query_data_store.list_charts(query).await
     }
 
     async fn repository_find_impl(
@@ -54,7 +58,68 @@ impl FirestoreChartStore {
                 write_model::event::Event::DataPoint(_) => unreachable!(),
             })
             .collect::<Vec<ChartEvent>>();
-        Ok(Some(Chart::from_events(&events)?))
+        if events.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            Chart::from_events(&events).map_err(|e| CorruptedError(e.into()))?,
+        ))
+    }
+
+    async fn repository_find_events_impl(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let event_stream_id = EventStreamId::from_str(id.to_string().as_str())?;
+        let events = self
+            .event_store
+            .find_events_by_event_stream_id(&event_stream_id)
+            .await?
+            .into_iter()
+            .map(|event| match event {
+                write_model::event::Event::Chart(event) => Ok(event),
+                write_model::event::Event::DataPoint(_) => Err(CorruptedError(
+                    format!("event stream {} is not a chart stream", event_stream_id).into(),
+                )),
+            })
+            .collect::<Result<Vec<ChartEvent>, CorruptedError>>()?;
+        Ok(events)
+    }
+
+    async fn repository_find_ids_impl(
+        &self,
+    ) -> Result<Vec<ChartId>, Box<dyn std::error::Error + Send + Sync>> {
+        // chart ids and data point ids share the event_streams collection.
+        // a data point id contains ':' (`<chart_id>:...`), a chart id (UUID) does not
+        let mut ids = self
+            .event_store
+            .find_event_stream_ids()
+            .await?
+            .into_iter()
+            .map(|event_stream_id| event_stream_id.to_string())
+            .filter(|id| !id.contains(':'))
+            .map(|id| ChartId::from_str(&id))
+            .collect::<Result<Vec<ChartId>, _>>()
+            .map_err(|e| CorruptedError(e.into()))?;
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn repository_find_all_events_impl(
+        &self,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut events = self
+            .event_store
+            .find_all_events()
+            .await?
+            .into_iter()
+            .filter_map(|event| match event {
+                write_model::event::Event::Chart(event) => Some(event),
+                write_model::event::Event::DataPoint(_) => None,
+            })
+            .collect::<Vec<ChartEvent>>();
+        events.sort_by_key(|event| (event.stream_id, event.version));
+        Ok(events)
     }
 
     async fn repository_store_impl(
@@ -70,7 +135,10 @@ impl FirestoreChartStore {
             .await?;
 
         // To simplify the structure, update the query data at this timing (not supported for failure).
-        self.query_data_store.update().await?;
+        self.query_data_store
+            .update()
+            .await
+            .map_err(QueryDataUpdateError)?;
 
         Ok(())
     }
@@ -85,18 +153,17 @@ impl query_use_case::port::ChartReader for FirestoreChartStore {
         Option<query_use_case::port::ChartQueryData>,
         query_use_case::port::chart_reader::Error,
     > {
-        self.reader_get_impl(id)
-            .await
-            .map_err(query_use_case::port::chart_reader::Error::from)
+        self.reader_get_impl(id).await.map_err(chart_reader_error)
     }
 
     async fn list(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, query_use_case::port::chart_reader::Error>
     {
-        self.reader_list_impl()
+        self.reader_list_impl(query)
             .await
-            .map_err(query_use_case::port::chart_reader::Error::from)
+            .map_err(chart_reader_error)
     }
 }
 
@@ -109,7 +176,33 @@ impl command_use_case::port::ChartRepository for FirestoreChartStore {
     ) -> Result<Option<Chart>, command_use_case::port::chart_repository::Error> {
         self.repository_find_impl(id)
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
+    }
+
+    #[tracing::instrument(level = tracing::Level::DEBUG, err(Debug), skip(self))]
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.repository_find_all_events_impl()
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.repository_find_events_impl(id)
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<ChartId>, command_use_case::port::chart_repository::Error> {
+        self.repository_find_ids_impl()
+            .await
+            .map_err(chart_repository_error)
     }
 
     #[tracing::instrument(level = tracing::Level::DEBUG, err(Debug), ret, skip(self))]
@@ -120,7 +213,7 @@ impl command_use_case::port::ChartRepository for FirestoreChartStore {
     ) -> Result<(), command_use_case::port::chart_repository::Error> {
         self.repository_store_impl(current, events.to_vec())
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
     }
 }
 
@@ -130,7 +223,7 @@ mod tests {
 
     use super::*;
 
-    #[ignore = "requires Firestore"]
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
     #[tokio::test]
     async fn test() -> anyhow::Result<()> {
         let store = FirestoreChartStore::new()
@@ -139,7 +232,132 @@ mod tests {
         let (chart, events) = Chart::create("title1".to_owned())?;
         assert_eq!(store.find(chart.id()).await?, None);
         store.store(None, &events).await?;
-        assert_eq!(store.find(chart.id()).await?, None);
+        assert_eq!(store.find(chart.id()).await?, Some(chart));
+        Ok(())
+    }
+
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let store = FirestoreChartStore::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        store_test_kit::test_chart_store(&store).await
+    }
+
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
+    #[tokio::test]
+    async fn test_legacy_version() -> anyhow::Result<()> {
+        use query_use_case::port::ChartReader as _;
+
+        let store = FirestoreChartStore::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let (chart, events) = Chart::create("title1".to_owned())?;
+        store.store(None, &events).await?;
+        let (updated, events) = chart.update("title2".to_owned())?;
+        store.store(Some(chart.version()), &events).await?;
+
+        // a document written before the version was projected
+        let client = firestore_client::FirestoreClient::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let document_path = crate::path::chart_document(chart.id());
+        let document = client
+            .get_document::<crate::schema::ChartDocumentData>(&document_path)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("chart document not found"))?;
+        client
+            .update_document(
+                &document_path,
+                &crate::schema::ChartDocumentData {
+                    version: 0,
+                    ..document.fields
+                },
+            )
+            .await?;
+
+        let query_data = store
+            .get(chart.id())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("chart not found"))?;
+        assert_eq!(query_data.version, updated.version());
+        Ok(())
+    }
+
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
+    #[tokio::test]
+    async fn test_legacy_updated_at() -> anyhow::Result<()> {
+        use query_use_case::port::ChartReader as _;
+
+        /// A chart document written before the update time was projected
+        #[derive(serde::Serialize)]
+        struct LegacyChartDocumentData {
+            created_at: String,
+            series: Vec<crate::schema::SeriesDocumentData>,
+            title: String,
+            version: i64,
+        }
+
+        let store = FirestoreChartStore::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let (chart, events) = Chart::create("title1".to_owned())?;
+        store.store(None, &events).await?;
+
+        let client = firestore_client::FirestoreClient::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let document_path = crate::path::chart_document(chart.id());
+        let document = client
+            .get_document::<crate::schema::ChartDocumentData>(&document_path)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("chart document not found"))?;
+        client.delete_document(&document_path).await?;
+        client
+            .create_document(
+                &document_path,
+                &LegacyChartDocumentData {
+                    created_at: document.fields.created_at.clone(),
+                    series: document.fields.series,
+                    title: document.fields.title,
+                    version: document.fields.version,
+                },
+            )
+            .await?;
+        client
+            .delete_document(&crate::path::query_updated_at_backfill_document())
+            .await?;
+
+        // the next update backfills `updated_at`
+        let (other, events) = Chart::create("title2".to_owned())?;
+        store.store(None, &events).await?;
+
+        let document = client
+            .get_document::<crate::schema::ChartDocumentData>(&document_path)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("chart document not found"))?;
+        assert_eq!(
+            document.fields.updated_at.as_deref(),
+            Some(document.fields.created_at.as_str())
+        );
+        let mut listed = vec![];
+        let mut query = query_use_case::port::ChartListQuery {
+            after: None,
+            limit: 100,
+            order: query_use_case::port::SortOrder::Ascending,
+            sort_key: query_use_case::port::ChartSortKey::UpdatedAt,
+        };
+        loop {
+            let page = store.list(query.clone()).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            query.after = Some(query.cursor(last));
+            listed.extend(page.into_iter().map(|chart| chart.id));
+        }
+        assert!(listed.contains(&chart.id()));
+        assert!(listed.contains(&other.id()));
         Ok(())
     }
 }
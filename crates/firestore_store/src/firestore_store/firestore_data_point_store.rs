@@ -1,8 +1,11 @@
 use std::str::FromStr as _;
 
 use crate::{
-    firestore_event_store::FirestoreEventStore, firestore_query_data_store::FirestoreQueryDataStore,
+    error::{data_point_reader_error, data_point_repository_error, QueryDataUpdateError},
+    firestore_event_store::FirestoreEventStore,
+    firestore_query_data_store::FirestoreQueryDataStore,
 };
+use store_error::CorruptedError;
 use write_model::{
     aggregate::DataPoint,
     event::{DataPointEvent, Event},
@@ -35,11 +38,15 @@ impl FirestoreDataPointStore {
     async fn reader_list_impl(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        self.query_data_store.list_data_points(chart_id).await
+        self.query_data_store
+            .list_data_points(chart_id, series, range)
+            .await
     }
 
     async fn repository_find_impl(
@@ -57,7 +64,72 @@ impl FirestoreDataPointStore {
                 write_model::event::Event::DataPoint(event) => event,
             })
             .collect::<Vec<DataPointEvent>>();
-        Ok(Some(DataPoint::from_events(&events)?))
+        if events.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            DataPoint::from_events(&events).map_err(|e| CorruptedError(e.into()))?,
+        ))
+    }
+
+    async fn repository_find_events_impl(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let event_stream_id = EventStreamId::from_str(id.to_string().as_str())?;
+        let events = self
+            .event_store
+            .find_events_by_event_stream_id(&event_stream_id)
+            .await?
+            .into_iter()
+            .map(|event| match event {
+                write_model::event::Event::DataPoint(event) => Ok(event),
+                write_model::event::Event::Chart(_) => Err(CorruptedError(
+                    format!(
+                        "event stream {} is not a data_point stream",
+                        event_stream_id
+                    )
+                    .into(),
+                )),
+            })
+            .collect::<Result<Vec<DataPointEvent>, CorruptedError>>()?;
+        Ok(events)
+    }
+
+    async fn repository_find_ids_impl(
+        &self,
+    ) -> Result<Vec<DataPointId>, Box<dyn std::error::Error + Send + Sync>> {
+        // chart ids and data point ids share the event_streams collection.
+        // a data point id contains ':' (`<chart_id>:...`), a chart id (UUID) does not
+        let mut ids = self
+            .event_store
+            .find_event_stream_ids()
+            .await?
+            .into_iter()
+            .map(|event_stream_id| event_stream_id.to_string())
+            .filter(|id| id.contains(':'))
+            .map(|id| DataPointId::from_str(&id))
+            .collect::<Result<Vec<DataPointId>, _>>()
+            .map_err(|e| CorruptedError(e.into()))?;
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn repository_find_all_events_impl(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut events = self
+            .event_store
+            .find_all_events()
+            .await?
+            .into_iter()
+            .filter_map(|event| match event {
+                write_model::event::Event::DataPoint(event) => Some(event),
+                write_model::event::Event::Chart(_) => None,
+            })
+            .collect::<Vec<DataPointEvent>>();
+        events.sort_by_key(|event| (event.stream_id, event.version));
+        Ok(events)
     }
 
     async fn repository_store_impl(
@@ -73,7 +145,10 @@ impl FirestoreDataPointStore {
             .await?;
 
         // To simplify the structure, update the query data at this timing (not supported for failure).
-        self.query_data_store.update().await?;
+        self.query_data_store
+            .update()
+            .await
+            .map_err(QueryDataUpdateError)?;
 
         Ok(())
     }
@@ -88,7 +163,33 @@ impl command_use_case::port::DataPointRepository for FirestoreDataPointStore {
     ) -> Result<Option<DataPoint>, command_use_case::port::data_point_repository::Error> {
         self.repository_find_impl(id)
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
+    }
+
+    #[tracing::instrument(level = tracing::Level::DEBUG, err(Debug), skip(self))]
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.repository_find_all_events_impl()
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.repository_find_events_impl(id)
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<DataPointId>, command_use_case::port::data_point_repository::Error> {
+        self.repository_find_ids_impl()
+            .await
+            .map_err(data_point_repository_error)
     }
 
     #[tracing::instrument(level = tracing::Level::DEBUG, err(Debug), ret, skip(self))]
@@ -99,7 +200,7 @@ impl command_use_case::port::DataPointRepository for FirestoreDataPointStore {
     ) -> Result<(), command_use_case::port::data_point_repository::Error> {
         self.repository_store_impl(current, events.to_vec())
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
     }
 }
 
@@ -114,18 +215,34 @@ impl query_use_case::port::DataPointReader for FirestoreDataPointStore {
     > {
         self.reader_get_impl(id)
             .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+            .map_err(data_point_reader_error)
     }
 
     async fn list(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         query_use_case::port::data_point_reader::Error,
     > {
-        self.reader_list_impl(chart_id)
+        self.reader_list_impl(chart_id, series, range)
+            .await
+            .map_err(data_point_reader_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let store = FirestoreDataPointStore::new()
             .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        store_test_kit::test_data_point_store(&store).await
     }
 }
@@ -1,10 +1,11 @@
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, str::FromStr as _};
 
 use crate::{
     converter, path,
     schema::{EventDocumentData, EventStreamDocumentData},
 };
 use firestore_client::{FieldPath, Filter, FirestoreClient, Transaction};
+use store_error::{check_version, CorruptedError, StreamNotFoundError};
 use write_model::{
     event::Event,
     value_object::{EventStreamId, Version},
@@ -17,6 +18,37 @@ impl FirestoreEventStore {
         Ok(Self(FirestoreClient::new().await?))
     }
 
+    pub async fn find_all_events(
+        &self,
+    ) -> Result<Vec<Event>, Box<dyn std::error::Error + Send + Sync>> {
+        let documents = self
+            .0
+            .list_all_documents::<EventDocumentData>(&path::event_collection())
+            .await?;
+        let events = documents
+            .into_iter()
+            .map(converter::event_from_document)
+            .collect::<Result<Vec<Event>, Box<dyn std::error::Error + Send + Sync>>>()
+            .map_err(CorruptedError)?;
+        Ok(events)
+    }
+
+    /// Returns the id of every event stream (charts and data points)
+    pub async fn find_event_stream_ids(
+        &self,
+    ) -> Result<Vec<EventStreamId>, Box<dyn std::error::Error + Send + Sync>> {
+        let documents = self
+            .0
+            .list_all_documents::<EventStreamDocumentData>(&path::event_stream_collection())
+            .await?;
+        let ids = documents
+            .into_iter()
+            .map(|document| EventStreamId::from_str(&document.fields.id))
+            .collect::<Result<Vec<EventStreamId>, _>>()
+            .map_err(|e| CorruptedError(e.into()))?;
+        Ok(ids)
+    }
+
     pub async fn find_events_by_event_stream_id(
         &self,
         event_stream_id: &EventStreamId,
@@ -60,7 +92,8 @@ impl FirestoreEventStore {
         let events = all_documents
             .into_iter()
             .map(converter::event_from_document)
-            .collect::<Result<Vec<Event>, Box<dyn std::error::Error + Send + Sync>>>()?;
+            .collect::<Result<Vec<Event>, Box<dyn std::error::Error + Send + Sync>>>()
+            .map_err(CorruptedError)?;
         Ok(events)
     }
 
@@ -98,12 +131,12 @@ impl FirestoreEventStore {
                                 event_stream_id.to_string().as_str(),
                             ))
                             .await?
-                            .ok_or("event stream not found")?;
+                            .ok_or(StreamNotFoundError)?;
 
                         // check version
-                        if event_stream.fields.version != i64::from(current) {
-                            return Err("version mismatch".into());
-                        }
+                        let stored = Version::try_from(event_stream.fields.version)
+                            .map_err(|e| CorruptedError(e.into()))?;
+                        check_version(Some(current), Some(stored))?;
 
                         // update event_stream
                         transaction.update(
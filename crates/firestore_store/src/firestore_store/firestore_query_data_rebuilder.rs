@@ -0,0 +1,152 @@
+use std::str::FromStr as _;
+
+use firestore_client::{path::CollectionId, CollectionPath};
+
+use crate::{firestore_query_data_store::FirestoreQueryDataStore, path};
+
+/// Reported while rebuilding the query data
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RebuildProgress {
+    /// The whole event log was read and projected in memory
+    Projected {
+        charts: usize,
+        data_points: usize,
+        events: usize,
+    },
+    /// `done` of `total` query documents under `collection` were deleted
+    Deleted {
+        collection: String,
+        done: usize,
+        total: usize,
+    },
+    /// `done` of `total` query documents under `collection` were written
+    Written {
+        collection: String,
+        done: usize,
+        total: usize,
+    },
+    /// The readers were pointed at `collection`
+    Swapped { collection: String },
+    /// The updater metadata was reset to the last event
+    UpdaterReset,
+}
+
+/// Rebuilds the query data (`charts` and their `data_points`) from the whole event log.
+///
+/// Stop the writers while rebuilding. Events stored meanwhile may be missing from the result.
+pub struct FirestoreQueryDataRebuilder {
+    query_data_store: FirestoreQueryDataStore,
+}
+
+impl FirestoreQueryDataRebuilder {
+    pub async fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self {
+            query_data_store: FirestoreQueryDataStore::new().await?,
+        })
+    }
+
+    /// Wipes the query documents and the updater metadata and replays every event.
+    ///
+    /// With `shadow_collection` (a root collection id such as `charts_rebuild`), the query data
+    /// is built in the collection not in use (alternating between `charts` and the shadow
+    /// collection on each rebuild) while the current one stays readable. The readers switch to
+    /// the new collection at once, when a pointer document is written after the whole event log
+    /// has been written.
+    pub async fn rebuild(
+        &self,
+        shadow_collection: Option<&str>,
+        mut progress: impl FnMut(RebuildProgress) + Send,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let shadow_collection = shadow_collection
+            .map(|collection_id| {
+                let collection_id = CollectionId::from_str(collection_id)?;
+                if [
+                    path::chart_collection_id(),
+                    path::event_collection_id(),
+                    path::event_stream_collection_id(),
+                    path::query_updater_document().collection_id().clone(),
+                ]
+                .contains(&collection_id)
+                {
+                    return Err(format!(
+                        "shadow collection must not be a collection in use (collection = {})",
+                        collection_id
+                    )
+                    .into());
+                }
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>(CollectionPath::new(
+                    None,
+                    collection_id,
+                ))
+            })
+            .transpose()?;
+        self.query_data_store
+            .rebuild(shadow_collection, &mut progress)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_use_case::port::ChartRepository as _;
+    use query_use_case::port::ChartReader as _;
+    use write_model::aggregate::Chart;
+
+    use crate::FirestoreChartStore;
+
+    use super::*;
+
+    #[ignore = "requires Firestore (e.g. FIRESTORE_EMULATOR_HOST=localhost:8080)"]
+    #[tokio::test]
+    async fn test_rebuild_shadow_collection() -> anyhow::Result<()> {
+        let store = FirestoreChartStore::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let (chart, events) = Chart::create("title1".to_owned())?;
+        store.store(None, &events).await?;
+        let rebuilder = FirestoreQueryDataRebuilder::new()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut swapped = vec![];
+        rebuilder
+            .rebuild(Some("charts_rebuild_test"), |progress| {
+                if let RebuildProgress::Swapped { collection } = progress {
+                    swapped.push(collection);
+                }
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(swapped, vec!["charts_rebuild_test".to_owned()]);
+        let query_data = store.get(chart.id()).await?;
+        assert_eq!(
+            query_data.map(|chart| chart.title),
+            Some("title1".to_owned())
+        );
+        let (updated, events) = chart.update("title2".to_owned())?;
+        store.store(Some(chart.version()), &events).await?;
+        let query_data = store.get(chart.id()).await?;
+        assert_eq!(
+            query_data.map(|chart| chart.version),
+            Some(updated.version())
+        );
+
+        // the shadow collection is in use, the next rebuild goes back to `charts`
+        let mut swapped = vec![];
+        rebuilder
+            .rebuild(Some("charts_rebuild_test"), |progress| {
+                if let RebuildProgress::Swapped { collection } = progress {
+                    swapped.push(collection);
+                }
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(swapped, vec!["charts".to_owned()]);
+        let query_data = store.get(chart.id()).await?;
+        assert_eq!(
+            query_data.map(|chart| chart.title),
+            Some("title2".to_owned())
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,33 @@
-use std::{future::Future, pin::Pin, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+};
 
 use crate::{
     converter, path,
+    projection::{self, Projection},
     schema::{
-        self, ChartDocumentData, ChartEventDataDocumentData, DataPointDocumentData,
-        DataPointEventDataDocumentData, EventDocumentData, UpdaterMetadataDocumentData,
-        UpdaterMetadataProcessedEventDocumentData,
+        self, ChartCollectionDocumentData, ChartDocumentData, DataPointDocumentData,
+        EventDocumentData, EventStreamDocumentData, UpdatedAtBackfillDocumentData,
+        UpdaterMetadataDocumentData, UpdaterMetadataProcessedEventDocumentData,
     },
+    RebuildProgress,
+};
+use firestore_client::{
+    path::CollectionId, CollectionPath, DocumentName, DocumentPath, FieldPath, Filter,
+    FirestoreClient, Precondition, Transaction,
 };
-use firestore_client::{FieldPath, Filter, FirestoreClient, Precondition, Transaction};
-use write_model::value_object::{ChartId, DataPointId, EventId};
+use store_error::CorruptedError;
+use write_model::value_object::{ChartId, DataPointId, DateTime, EventId};
+
+/// The number of writes committed at once while rebuilding. Firestore limits the writes in a commit
+const BATCH_SIZE: usize = 100;
+
+/// Any document. Used to list documents only to delete them
+#[derive(serde::Deserialize)]
+struct AnyDocumentData {}
 
 pub(crate) struct FirestoreQueryDataStore {
     client: FirestoreClient,
@@ -29,11 +47,21 @@ impl FirestoreQueryDataStore {
         Option<query_use_case::port::ChartQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        self.client
-            .get_document::<ChartDocumentData>(&path::chart_document(id))
+        let Some(mut document) = self
+            .client
+            .get_document::<ChartDocumentData>(&path::chart_document_in(
+                &self.chart_collection().await?,
+                id,
+            ))
             .await?
-            .map(converter::query_data_from_document)
-            .transpose()
+        else {
+            return Ok(None);
+        };
+        self.resolve_legacy_version(&document.name, &mut document.fields.version)
+            .await?;
+        converter::query_data_from_document(document)
+            .map(Some)
+            .map_err(|e| CorruptedError(e).into())
     }
 
     pub(crate) async fn get_data_point(
@@ -43,47 +71,173 @@ impl FirestoreQueryDataStore {
         Option<query_use_case::port::DataPointQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        self.client
-            .get_document::<DataPointDocumentData>(&path::data_point_document(id))
+        let Some(mut document) = self
+            .client
+            .get_document::<DataPointDocumentData>(&path::data_point_document_in(
+                &self.chart_collection().await?,
+                id,
+            ))
             .await?
-            .map(converter::data_point_query_data_from_document)
-            .transpose()
+        else {
+            return Ok(None);
+        };
+        self.resolve_legacy_version(&document.name, &mut document.fields.version)
+            .await?;
+        converter::data_point_query_data_from_document(document)
+            .map(Some)
+            .map_err(|e| CorruptedError(e).into())
     }
 
     pub(crate) async fn list_charts(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, Box<dyn std::error::Error + Send + Sync>>
     {
-        let documents = self
+        let chart_collection = self.chart_collection().await?;
+        let field_path = FieldPath::raw(match query.sort_key {
+            query_use_case::port::ChartSortKey::CreatedAt => "created_at",
+            query_use_case::port::ChartSortKey::Title => "title",
+            query_use_case::port::ChartSortKey::UpdatedAt => "updated_at",
+        });
+        let order_by = match query.order {
+            query_use_case::port::SortOrder::Ascending => [
+                field_path.ascending(),
+                FieldPath::raw("__name__").ascending(),
+            ],
+            query_use_case::port::SortOrder::Descending => [
+                field_path.descending(),
+                FieldPath::raw("__name__").descending(),
+            ],
+        };
+        let start_after = query
+            .after
+            .map(|cursor| {
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>([
+                    firestore_client::to_value(&cursor.key)?,
+                    self.client
+                        .document_reference(&path::chart_document_in(&chart_collection, cursor.id)),
+                ])
+            })
+            .transpose()?;
+        let mut documents = self
             .client
-            .list_all_documents::<ChartDocumentData>(&path::chart_collection())
+            .run_collection_query::<ChartDocumentData>(
+                &chart_collection,
+                None,
+                Some(order_by),
+                start_after,
+                Some(i32::try_from(query.limit)?),
+            )
             .await?;
+        for document in documents.iter_mut() {
+            self.resolve_legacy_version(&document.name, &mut document.fields.version)
+                .await?;
+        }
         let documents = documents
             .into_iter()
             .map(converter::query_data_from_document)
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(CorruptedError)?;
         Ok(documents)
     }
 
     pub(crate) async fn list_data_points(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        let documents = self
-            .client
-            .list_all_documents::<DataPointDocumentData>(&path::data_point_collection(chart_id))
-            .await?;
+        let collection_path =
+            path::data_point_collection_in(&self.chart_collection().await?, chart_id);
+        let mut filters = vec![];
+        // legacy documents have no `series_id` field, so the default series is filtered below
+        if let query_use_case::port::SeriesFilter::Series(series_id) = series {
+            filters.push(
+                FieldPath::raw("series_id")
+                    .equal(firestore_client::to_value(&series_id.to_string())?)?,
+            );
+        }
+        if let Some(from) = range.from {
+            filters.push(
+                FieldPath::raw("x_value")
+                    .greater_than_or_equal(firestore_client::to_value(&from.to_string())?)?,
+            );
+        }
+        if let Some(to) = range.to {
+            filters.push(
+                FieldPath::raw("x_value")
+                    .less_than(firestore_client::to_value(&to.to_string())?)?,
+            );
+        }
+        let mut documents = if filters.is_empty() {
+            self.client
+                .list_all_documents::<DataPointDocumentData>(&collection_path)
+                .await?
+        } else {
+            self.client
+                .run_collection_query::<DataPointDocumentData>(
+                    &collection_path,
+                    Some(Filter::and(filters)),
+                    None::<Vec<_>>,
+                    None::<Vec<_>>,
+                    None,
+                )
+                .await?
+        };
+        for document in documents.iter_mut() {
+            self.resolve_legacy_version(&document.name, &mut document.fields.version)
+                .await?;
+        }
         let documents = documents
             .into_iter()
             .map(converter::data_point_query_data_from_document)
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(CorruptedError)?
+            .into_iter()
+            .filter(|data_point| series.matches(data_point.series_id))
+            .collect::<Vec<_>>();
         Ok(documents)
     }
 
+    /// The collection holding the chart documents (see `ChartCollectionDocumentData`)
+    async fn chart_collection(
+        &self,
+    ) -> Result<CollectionPath, Box<dyn std::error::Error + Send + Sync>> {
+        let document = self
+            .client
+            .get_document::<ChartCollectionDocumentData>(&path::query_chart_collection_document())
+            .await?;
+        chart_collection_from_document(document.map(|document| document.fields))
+    }
+
+    /// Documents written before the version was projected have the version `0`. The version of
+    /// their aggregate is read from its event stream instead, whose id is the document id
+    async fn resolve_legacy_version(
+        &self,
+        document_name: &DocumentName,
+        version: &mut i64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if *version != 0 {
+            return Ok(());
+        }
+        if let Some(event_stream) = self
+            .client
+            .get_document::<EventStreamDocumentData>(&path::event_stream_document(
+                document_name.document_id().as_ref(),
+            ))
+            .await?
+        {
+            *version = event_stream.fields.version;
+        }
+        Ok(())
+    }
+
     pub(crate) async fn update(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.backfill_updated_at().await?;
+
         let updater_metadata_document_path = path::query_updater_document();
         let last_processed_event_at = self
             .client
@@ -132,86 +286,56 @@ impl FirestoreQueryDataStore {
                             )?),
                             &UpdaterMetadataProcessedEventDocumentData {},
                         )?;
+                        // read in the transaction, a rebuild swapping the collection meanwhile
+                        // fails the commit
+                        let chart_collection = chart_collection_from_document(
+                            transaction
+                                .get::<ChartCollectionDocumentData>(
+                                    &path::query_chart_collection_document(),
+                                )
+                                .await?
+                                .map(|document| document.fields),
+                        )?;
 
                         match event.fields.data {
                             schema::EventDataDocumentData::Chart(event_data) => {
                                 let chart_id = ChartId::from_str(&event.fields.stream_id)?;
-                                let chart_document_path = path::chart_document(chart_id);
-                                match event_data {
-                                    ChartEventDataDocumentData::Created(
-                                        schema::chart_event_data_document_data::Created { title },
-                                    ) => {
-                                        transaction.create(
-                                            &chart_document_path,
-                                            &ChartDocumentData {
-                                                created_at: event.fields.at.clone(),
-                                                title,
-                                            },
-                                        )?;
-                                    }
-                                    ChartEventDataDocumentData::Deleted(
-                                        schema::chart_event_data_document_data::Deleted {},
-                                    ) => transaction.delete(&chart_document_path)?,
-                                    ChartEventDataDocumentData::Updated(
-                                        schema::chart_event_data_document_data::Updated { title },
-                                    ) => {
-                                        let document = transaction
-                                            .get::<ChartDocumentData>(&chart_document_path)
-                                            .await?
-                                            .ok_or("not found")?;
-                                        transaction.update(
-                                            &chart_document_path,
-                                            &ChartDocumentData {
-                                                created_at: document.fields.created_at,
-                                                title,
-                                            },
-                                        )?
-                                    }
-                                }
+                                let chart_document_path =
+                                    path::chart_document_in(&chart_collection, chart_id);
+                                let document = transaction
+                                    .get::<ChartDocumentData>(&chart_document_path)
+                                    .await?
+                                    .map(|document| document.fields);
+                                write_projection(
+                                    transaction,
+                                    &chart_document_path,
+                                    projection::project_chart_event(
+                                        document,
+                                        &event.fields.at,
+                                        event.fields.version,
+                                        event_data,
+                                    )?,
+                                )?;
                             }
                             schema::EventDataDocumentData::DataPoint(event_data) => {
                                 let data_point_id = DataPointId::from_str(&event.fields.stream_id)?;
                                 let data_point_document_path =
-                                    path::data_point_document(data_point_id);
-                                match event_data {
-                                    DataPointEventDataDocumentData::Created(
-                                        schema::data_point_event_data_document_data::Created {
-                                            value,
-                                        },
-                                    ) => {
-                                        transaction.create(
-                                            &data_point_document_path,
-                                            &DataPointDocumentData {
-                                                chart_id: data_point_id.chart_id().to_string(),
-                                                created_at: event.fields.at.clone(),
-                                                x_value: data_point_id.x_value().to_string(),
-                                                y_value: value,
-                                            },
-                                        )?;
-                                    }
-                                    DataPointEventDataDocumentData::Deleted(_) => {
-                                        transaction.delete(&data_point_document_path)?
-                                    }
-                                    DataPointEventDataDocumentData::Updated(
-                                        schema::data_point_event_data_document_data::Updated {
-                                            value,
-                                        },
-                                    ) => {
-                                        let document = transaction
-                                            .get::<DataPointDocumentData>(&data_point_document_path)
-                                            .await?
-                                            .ok_or("not found")?;
-                                        transaction.update(
-                                            &data_point_document_path,
-                                            &DataPointDocumentData {
-                                                chart_id: data_point_id.chart_id().to_string(),
-                                                created_at: document.fields.created_at,
-                                                x_value: data_point_id.x_value().to_string(),
-                                                y_value: value,
-                                            },
-                                        )?
-                                    }
-                                }
+                                    path::data_point_document_in(&chart_collection, data_point_id);
+                                let document = transaction
+                                    .get::<DataPointDocumentData>(&data_point_document_path)
+                                    .await?
+                                    .map(|document| document.fields);
+                                write_projection(
+                                    transaction,
+                                    &data_point_document_path,
+                                    projection::project_data_point_event(
+                                        data_point_id,
+                                        document,
+                                        &event.fields.at,
+                                        event.fields.version,
+                                        event_data,
+                                    )?,
+                                )?;
                             }
                         }
 
@@ -253,6 +377,388 @@ impl FirestoreQueryDataStore {
         Ok(())
     }
 
+    /// Sets `updated_at` to `created_at` in the chart documents written before the update time
+    /// was projected, so that they are listed when sorting by `updated_at`. Runs until it has
+    /// completed once. Each document is read in the transaction that writes it, so a chart
+    /// updated meanwhile is not overwritten
+    async fn backfill_updated_at(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let backfill_document_path = path::query_updated_at_backfill_document();
+        if self
+            .client
+            .get_document::<UpdatedAtBackfillDocumentData>(&backfill_document_path)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+        let document_paths = self
+            .client
+            .list_all_documents::<ChartDocumentData>(&self.chart_collection().await?)
+            .await?
+            .into_iter()
+            .filter(|document| document.fields.updated_at.is_none())
+            .map(|document| document.name.document_path().clone())
+            .collect::<Vec<DocumentPath>>();
+        for batch in document_paths.chunks(BATCH_SIZE) {
+            let batch = batch.to_vec();
+            if self
+                .run_transaction(move |transaction| {
+                    Box::pin(async move {
+                        for document_path in &batch {
+                            let Some(document) =
+                                transaction.get::<ChartDocumentData>(document_path).await?
+                            else {
+                                continue;
+                            };
+                            if document.fields.updated_at.is_some() {
+                                continue;
+                            }
+                            transaction.update(
+                                document_path,
+                                &ChartDocumentData {
+                                    updated_at: Some(document.fields.created_at.clone()),
+                                    ..document.fields
+                                },
+                            )?;
+                        }
+                        Ok(())
+                    })
+                })
+                .await
+                .is_err()
+            {
+                // ignore error (e.g. contention with the updater)
+                // retry the backfill on the next update
+                return Ok(());
+            }
+        }
+        self.set_documents(
+            vec![(
+                backfill_document_path,
+                UpdatedAtBackfillDocumentData {
+                    completed_at: DateTime::now().to_string(),
+                },
+            )],
+            &mut |_| {},
+        )
+        .await
+    }
+
+    /// Replays the whole event log into the query data and resets the updater metadata.
+    ///
+    /// With `shadow_collection`, the query data is written to the collection not in use (the
+    /// shadow collection, or `charts` if the shadow collection is in use) and the readers are
+    /// pointed at it in one write once complete. The previous collection is deleted afterwards.
+    pub(crate) async fn rebuild(
+        &self,
+        shadow_collection: Option<CollectionPath>,
+        progress: &mut (dyn FnMut(RebuildProgress) + Send),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut events = self
+            .client
+            .list_all_documents::<EventDocumentData>(&path::event_collection())
+            .await?;
+        events.sort_by(|a, b| {
+            (a.fields.stream_id.as_str(), a.fields.version)
+                .cmp(&(b.fields.stream_id.as_str(), b.fields.version))
+        });
+        // the updater processes the events whose `at` is greater than or equal to this
+        let last_event_at = events.iter().map(|event| event.fields.at.clone()).max();
+        let last_event_ids = events
+            .iter()
+            .filter(|event| Some(&event.fields.at) == last_event_at.as_ref())
+            .map(|event| EventId::from_str(&event.fields.id))
+            .collect::<Result<Vec<EventId>, _>>()
+            .map_err(|e| CorruptedError(e.into()))?;
+
+        let event_count = events.len();
+        let mut charts = BTreeMap::<ChartId, ChartDocumentData>::new();
+        let mut data_points = BTreeMap::<DataPointId, DataPointDocumentData>::new();
+        let mut chart_ids = BTreeSet::<ChartId>::new();
+        for event in events {
+            let EventDocumentData {
+                at,
+                data,
+                stream_id,
+                version,
+                ..
+            } = event.fields;
+            match data {
+                schema::EventDataDocumentData::Chart(event_data) => {
+                    let chart_id =
+                        ChartId::from_str(&stream_id).map_err(|e| CorruptedError(e.into()))?;
+                    chart_ids.insert(chart_id);
+                    let document = projection::project_chart_event(
+                        charts.remove(&chart_id),
+                        &at,
+                        version,
+                        event_data,
+                    )
+                    .map_err(CorruptedError)?;
+                    if let Some(document) = document.into_document() {
+                        charts.insert(chart_id, document);
+                    }
+                }
+                schema::EventDataDocumentData::DataPoint(event_data) => {
+                    let data_point_id =
+                        DataPointId::from_str(&stream_id).map_err(|e| CorruptedError(e.into()))?;
+                    chart_ids.insert(data_point_id.chart_id());
+                    let document = projection::project_data_point_event(
+                        data_point_id,
+                        data_points.remove(&data_point_id),
+                        &at,
+                        version,
+                        event_data,
+                    )
+                    .map_err(CorruptedError)?;
+                    if let Some(document) = document.into_document() {
+                        data_points.insert(data_point_id, document);
+                    }
+                }
+            }
+        }
+        progress(RebuildProgress::Projected {
+            charts: charts.len(),
+            data_points: data_points.len(),
+            events: event_count,
+        });
+
+        let chart_collection = self.chart_collection().await?;
+        match shadow_collection {
+            None => {
+                self.delete_query_documents(&chart_collection, &chart_ids, progress)
+                    .await?;
+                self.write_query_documents(&chart_collection, charts, data_points, progress)
+                    .await?;
+            }
+            Some(shadow_collection) => {
+                let new_collection = if shadow_collection == chart_collection {
+                    path::chart_collection()
+                } else {
+                    shadow_collection
+                };
+                // leftovers of an interrupted rebuild
+                self.delete_query_documents(&new_collection, &chart_ids, progress)
+                    .await?;
+                self.write_query_documents(&new_collection, charts, data_points, progress)
+                    .await?;
+                self.set_documents(
+                    vec![(
+                        path::query_chart_collection_document(),
+                        ChartCollectionDocumentData {
+                            collection_id: new_collection.collection_id().to_string(),
+                        },
+                    )],
+                    &mut |_| {},
+                )
+                .await?;
+                progress(RebuildProgress::Swapped {
+                    collection: new_collection.to_string(),
+                });
+                self.delete_query_documents(&chart_collection, &chart_ids, progress)
+                    .await?;
+            }
+        }
+
+        self.reset_updater(last_event_at, last_event_ids).await?;
+        progress(RebuildProgress::UpdaterReset);
+        Ok(())
+    }
+
+    /// Deletes the chart documents in `chart_collection` and the data point documents of the
+    /// listed charts and `chart_ids` (data points may remain after their chart is deleted)
+    async fn delete_query_documents(
+        &self,
+        chart_collection: &CollectionPath,
+        chart_ids: &BTreeSet<ChartId>,
+        progress: &mut (dyn FnMut(RebuildProgress) + Send),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let chart_documents = self
+            .client
+            .list_all_documents::<AnyDocumentData>(chart_collection)
+            .await?;
+        let mut chart_ids = chart_ids.clone();
+        let mut document_paths = vec![];
+        for document in chart_documents {
+            if let Ok(chart_id) = ChartId::from_str(document.name.document_id().as_ref()) {
+                chart_ids.insert(chart_id);
+            }
+            document_paths.push(document.name.document_path().clone());
+        }
+        for chart_id in chart_ids {
+            let data_point_documents = self
+                .client
+                .list_all_documents::<AnyDocumentData>(&path::data_point_collection_in(
+                    chart_collection,
+                    chart_id,
+                ))
+                .await?;
+            document_paths.extend(
+                data_point_documents
+                    .into_iter()
+                    .map(|document| document.name.document_path().clone()),
+            );
+        }
+
+        let total = document_paths.len();
+        let mut done = 0;
+        for batch in document_paths.chunks(BATCH_SIZE) {
+            let batch = batch.to_vec();
+            done += batch.len();
+            self.run_transaction(move |transaction| {
+                Box::pin(async move {
+                    for document_path in &batch {
+                        transaction.delete(document_path)?;
+                    }
+                    Ok(())
+                })
+            })
+            .await?;
+            progress(RebuildProgress::Deleted {
+                collection: chart_collection.to_string(),
+                done,
+                total,
+            });
+        }
+        Ok(())
+    }
+
+    async fn write_query_documents(
+        &self,
+        chart_collection: &CollectionPath,
+        charts: BTreeMap<ChartId, ChartDocumentData>,
+        data_points: BTreeMap<DataPointId, DataPointDocumentData>,
+        progress: &mut (dyn FnMut(RebuildProgress) + Send),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let total = charts.len() + data_points.len();
+        let mut done = 0;
+        let mut on_batch = |len: usize| {
+            done += len;
+            progress(RebuildProgress::Written {
+                collection: chart_collection.to_string(),
+                done,
+                total,
+            });
+        };
+        self.set_documents(
+            charts
+                .into_iter()
+                .map(|(chart_id, document)| {
+                    (
+                        path::chart_document_in(chart_collection, chart_id),
+                        document,
+                    )
+                })
+                .collect(),
+            &mut on_batch,
+        )
+        .await?;
+        self.set_documents(
+            data_points
+                .into_iter()
+                .map(|(data_point_id, document)| {
+                    (
+                        path::data_point_document_in(chart_collection, data_point_id),
+                        document,
+                    )
+                })
+                .collect(),
+            &mut on_batch,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Points the updater at the last event so that only the events stored after the rebuild
+    /// are processed
+    async fn reset_updater(
+        &self,
+        last_event_at: Option<String>,
+        last_event_ids: Vec<EventId>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let processed_event_paths = self
+            .client
+            .list_all_documents::<UpdaterMetadataProcessedEventDocumentData>(
+                &path::query_updater_processed_event_collection(),
+            )
+            .await?
+            .into_iter()
+            .map(|document| document.name.document_path().clone())
+            .collect::<Vec<DocumentPath>>();
+        for batch in processed_event_paths.chunks(BATCH_SIZE) {
+            let batch = batch.to_vec();
+            self.run_transaction(move |transaction| {
+                Box::pin(async move {
+                    for document_path in &batch {
+                        transaction.delete(document_path)?;
+                    }
+                    Ok(())
+                })
+            })
+            .await?;
+        }
+        match last_event_at {
+            None => {
+                self.client
+                    .delete_document(&path::query_updater_document())
+                    .await?;
+            }
+            Some(last_processed_event_at) => {
+                self.set_documents(
+                    last_event_ids
+                        .into_iter()
+                        .map(|event_id| {
+                            (
+                                path::query_updater_processed_event_document(event_id),
+                                UpdaterMetadataProcessedEventDocumentData {},
+                            )
+                        })
+                        .collect(),
+                    &mut |_| {},
+                )
+                .await?;
+                self.set_documents(
+                    vec![(
+                        path::query_updater_document(),
+                        UpdaterMetadataDocumentData {
+                            last_processed_event_at,
+                        },
+                    )],
+                    &mut |_| {},
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the documents (overwriting existing ones) in batches
+    async fn set_documents<T>(
+        &self,
+        documents: Vec<(DocumentPath, T)>,
+        on_batch: &mut (dyn FnMut(usize) + Send),
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: serde::Serialize + Send + Sync + 'static,
+    {
+        let mut documents = documents.into_iter().peekable();
+        while documents.peek().is_some() {
+            let batch = documents.by_ref().take(BATCH_SIZE).collect::<Vec<_>>();
+            let len = batch.len();
+            self.run_transaction(move |transaction| {
+                Box::pin(async move {
+                    for (document_path, document_data) in &batch {
+                        transaction.set(document_path, document_data)?;
+                    }
+                    Ok(())
+                })
+            })
+            .await?;
+            on_batch(len);
+        }
+        Ok(())
+    }
+
     async fn run_transaction<F>(
         &self,
         callback: F,
@@ -283,3 +789,32 @@ impl FirestoreQueryDataStore {
         }
     }
 }
+
+fn chart_collection_from_document(
+    document: Option<ChartCollectionDocumentData>,
+) -> Result<CollectionPath, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(match document {
+        None => path::chart_collection(),
+        Some(document) => CollectionPath::new(
+            None,
+            CollectionId::from_str(&document.collection_id)
+                .map_err(|e| CorruptedError(e.into()))?,
+        ),
+    })
+}
+
+fn write_projection<T>(
+    transaction: &mut Transaction,
+    document_path: &DocumentPath,
+    projection: Projection<T>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    T: serde::Serialize,
+{
+    match projection {
+        Projection::Create(document_data) => transaction.create(document_path, &document_data)?,
+        Projection::Delete => transaction.delete(document_path)?,
+        Projection::Update(document_data) => transaction.update(document_path, &document_data)?,
+    }
+    Ok(())
+}
@@ -8,7 +8,9 @@ use firestore_client::{
 };
 use write_model::value_object::{ChartId, DataPointId, EventId};
 
-pub(crate) use self::event_stream::event_stream_document;
+pub(crate) use self::event_stream::{
+    event_stream_collection, event_stream_collection_id, event_stream_document,
+};
 
 pub(crate) fn query_updater_document() -> DocumentPath {
     CollectionPath::new(
@@ -19,10 +21,37 @@ pub(crate) fn query_updater_document() -> DocumentPath {
     .expect("query updater document path to be valid")
 }
 
-pub(crate) fn query_updater_processed_event_document(event_id: EventId) -> DocumentPath {
+pub(crate) fn query_updated_at_backfill_document() -> DocumentPath {
+    CollectionPath::new(
+        None,
+        CollectionId::from_str("query").expect("query collection id to be valid"),
+    )
+    .doc(
+        DocumentId::from_str("updated_at_backfill")
+            .expect("updated_at_backfill document id to be valid"),
+    )
+    .expect("query updated_at_backfill document path to be valid")
+}
+
+pub(crate) fn query_chart_collection_document() -> DocumentPath {
+    CollectionPath::new(
+        None,
+        CollectionId::from_str("query").expect("query collection id to be valid"),
+    )
+    .doc(
+        DocumentId::from_str("chart_collection").expect("chart_collection document id to be valid"),
+    )
+    .expect("query chart_collection document path to be valid")
+}
+
+pub(crate) fn query_updater_processed_event_collection() -> CollectionPath {
     query_updater_document()
         .collection("processed_events")
         .expect("query updater processed event collection path to be valid")
+}
+
+pub(crate) fn query_updater_processed_event_document(event_id: EventId) -> DocumentPath {
+    query_updater_processed_event_collection()
         .doc(DocumentId::from_str(&event_id.to_string()).expect("event id to be valid"))
         .expect("query updater processed event document path to be valid")
 }
@@ -35,22 +64,37 @@ pub(crate) fn chart_collection_id() -> CollectionId {
     CollectionId::from_str("charts").expect("chart collection id to be valid collection id")
 }
 
+#[cfg(test)]
 pub(crate) fn chart_document(chart_id: ChartId) -> DocumentPath {
-    chart_collection()
+    chart_document_in(&chart_collection(), chart_id)
+}
+
+/// The chart document in `chart_collection` (`charts` or a shadow collection)
+pub(crate) fn chart_document_in(
+    chart_collection: &CollectionPath,
+    chart_id: ChartId,
+) -> DocumentPath {
+    chart_collection
         .doc(DocumentId::from_str(&chart_id.to_string()).expect("chart id to be valid document id"))
         .expect("chart document path to be valid document path")
 }
 
-pub(crate) fn data_point_collection(chart_id: ChartId) -> CollectionPath {
-    chart_document(chart_id)
+pub(crate) fn data_point_collection_in(
+    chart_collection: &CollectionPath,
+    chart_id: ChartId,
+) -> CollectionPath {
+    chart_document_in(chart_collection, chart_id)
         .collection(
             CollectionId::from_str("data_points").expect("data point collection id to be valid"),
         )
         .expect("data point collection path to be valid")
 }
 
-pub(crate) fn data_point_document(data_point_id: DataPointId) -> DocumentPath {
-    data_point_collection(data_point_id.chart_id())
+pub(crate) fn data_point_document_in(
+    chart_collection: &CollectionPath,
+    data_point_id: DataPointId,
+) -> DocumentPath {
+    data_point_collection_in(chart_collection, data_point_id.chart_id())
         .doc(DocumentId::from_str(&data_point_id.to_string()).expect("data point id to be valid"))
         .expect("data point document path to be valid")
 }
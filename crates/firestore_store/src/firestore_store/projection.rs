@@ -0,0 +1,254 @@
+use write_model::value_object::DataPointId;
+
+use crate::schema::{
+    chart_event_data_document_data, data_point_event_data_document_data, ChartDocumentData,
+    ChartEventDataDocumentData, DataPointDocumentData, DataPointEventDataDocumentData,
+    SeriesDocumentData,
+};
+
+/// The change of a query document caused by an event
+#[derive(Debug)]
+pub(crate) enum Projection<T> {
+    Create(T),
+    Delete,
+    Update(T),
+}
+
+impl<T> Projection<T> {
+    /// The document after the change (`None` if deleted)
+    pub(crate) fn into_document(self) -> Option<T> {
+        match self {
+            Self::Create(document) | Self::Update(document) => Some(document),
+            Self::Delete => None,
+        }
+    }
+}
+
+/// Applies a chart event to the chart document (`None` if it does not exist)
+pub(crate) fn project_chart_event(
+    document: Option<ChartDocumentData>,
+    at: &str,
+    version: i64,
+    event_data: ChartEventDataDocumentData,
+) -> Result<Projection<ChartDocumentData>, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(match event_data {
+        ChartEventDataDocumentData::Created(chart_event_data_document_data::Created { title }) => {
+            Projection::Create(ChartDocumentData {
+                created_at: at.to_owned(),
+                series: vec![],
+                title,
+                updated_at: Some(at.to_owned()),
+                version,
+            })
+        }
+        ChartEventDataDocumentData::Deleted(chart_event_data_document_data::Deleted {}) => {
+            Projection::Delete
+        }
+        ChartEventDataDocumentData::SeriesCreated(
+            chart_event_data_document_data::SeriesCreated { name, series_id },
+        ) => {
+            let mut document = document.ok_or("not found")?;
+            document.series.push(SeriesDocumentData {
+                id: series_id,
+                name,
+            });
+            document.updated_at = Some(at.to_owned());
+            document.version = version;
+            Projection::Update(document)
+        }
+        ChartEventDataDocumentData::SeriesDeleted(
+            chart_event_data_document_data::SeriesDeleted { series_id },
+        ) => {
+            let mut document = document.ok_or("not found")?;
+            document.series.retain(|series| series.id != series_id);
+            document.updated_at = Some(at.to_owned());
+            document.version = version;
+            Projection::Update(document)
+        }
+        ChartEventDataDocumentData::SeriesRenamed(
+            chart_event_data_document_data::SeriesRenamed { name, series_id },
+        ) => {
+            let mut document = document.ok_or("not found")?;
+            let series = document
+                .series
+                .iter_mut()
+                .find(|series| series.id == series_id)
+                .ok_or("not found")?;
+            series.name = name;
+            document.updated_at = Some(at.to_owned());
+            document.version = version;
+            Projection::Update(document)
+        }
+        ChartEventDataDocumentData::Updated(chart_event_data_document_data::Updated { title }) => {
+            let document = document.ok_or("not found")?;
+            Projection::Update(ChartDocumentData {
+                created_at: document.created_at,
+                series: document.series,
+                title,
+                updated_at: Some(at.to_owned()),
+                version,
+            })
+        }
+    })
+}
+
+/// Applies a data point event to the data point document (`None` if it does not exist)
+pub(crate) fn project_data_point_event(
+    data_point_id: DataPointId,
+    document: Option<DataPointDocumentData>,
+    at: &str,
+    version: i64,
+    event_data: DataPointEventDataDocumentData,
+) -> Result<Projection<DataPointDocumentData>, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(match event_data {
+        DataPointEventDataDocumentData::Created(data_point_event_data_document_data::Created {
+            value,
+        }) => Projection::Create(DataPointDocumentData {
+            chart_id: data_point_id.chart_id().to_string(),
+            created_at: at.to_owned(),
+            series_id: data_point_id
+                .series_id()
+                .map(|series_id| series_id.to_string()),
+            version,
+            x_value: data_point_id.x_value().to_string(),
+            y_value: value,
+        }),
+        DataPointEventDataDocumentData::Deleted(_) => Projection::Delete,
+        DataPointEventDataDocumentData::Updated(data_point_event_data_document_data::Updated {
+            value,
+        }) => {
+            let document = document.ok_or("not found")?;
+            Projection::Update(DataPointDocumentData {
+                chart_id: data_point_id.chart_id().to_string(),
+                created_at: document.created_at,
+                series_id: data_point_id
+                    .series_id()
+                    .map(|series_id| series_id.to_string()),
+                version,
+                x_value: data_point_id.x_value().to_string(),
+                y_value: value,
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::value_object::{ChartId, SeriesId, XValue};
+
+    use crate::schema::YValueDocumentData;
+
+    use super::*;
+
+    #[test]
+    fn test_project_chart_event() -> anyhow::Result<()> {
+        let events = [
+            (
+                "2020-01-02T03:04:05.000Z",
+                ChartEventDataDocumentData::Created(chart_event_data_document_data::Created {
+                    title: "title1".to_owned(),
+                }),
+            ),
+            (
+                "2020-01-02T03:04:06.000Z",
+                ChartEventDataDocumentData::SeriesCreated(
+                    chart_event_data_document_data::SeriesCreated {
+                        name: "series1".to_owned(),
+                        series_id: "s1".to_owned(),
+                    },
+                ),
+            ),
+            (
+                "2020-01-02T03:04:07.000Z",
+                ChartEventDataDocumentData::SeriesRenamed(
+                    chart_event_data_document_data::SeriesRenamed {
+                        name: "series2".to_owned(),
+                        series_id: "s1".to_owned(),
+                    },
+                ),
+            ),
+            (
+                "2020-01-02T03:04:08.000Z",
+                ChartEventDataDocumentData::Updated(chart_event_data_document_data::Updated {
+                    title: "title2".to_owned(),
+                }),
+            ),
+        ];
+        let mut document = None;
+        for (version, (at, event_data)) in (1..).zip(events) {
+            document = project_chart_event(document, at, version, event_data)
+                .map_err(|e| anyhow::anyhow!(e))?
+                .into_document();
+        }
+        let document = document.ok_or_else(|| anyhow::anyhow!("not found"))?;
+        assert_eq!(document.created_at, "2020-01-02T03:04:05.000Z");
+        assert_eq!(document.title, "title2");
+        assert_eq!(document.series.len(), 1);
+        assert_eq!(document.series[0].name, "series2");
+        assert_eq!(
+            document.updated_at,
+            Some("2020-01-02T03:04:08.000Z".to_owned())
+        );
+        assert_eq!(document.version, 4);
+
+        let deleted = project_chart_event(
+            Some(document),
+            "2020-01-02T03:04:09.000Z",
+            5,
+            ChartEventDataDocumentData::Deleted(chart_event_data_document_data::Deleted {}),
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
+        assert!(deleted.into_document().is_none());
+
+        assert!(project_chart_event(
+            None,
+            "2020-01-02T03:04:05.000Z",
+            2,
+            ChartEventDataDocumentData::Updated(chart_event_data_document_data::Updated {
+                title: "title".to_owned(),
+            }),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_data_point_event() -> anyhow::Result<()> {
+        let data_point_id = DataPointId::new(
+            ChartId::generate(),
+            Some(SeriesId::generate()),
+            XValue::from_str("2020-01-02")?,
+        );
+        let created = project_data_point_event(
+            data_point_id,
+            None,
+            "2020-01-02T03:04:05.000Z",
+            1,
+            DataPointEventDataDocumentData::Created(data_point_event_data_document_data::Created {
+                value: YValueDocumentData::Decimal("1.5".to_owned()),
+            }),
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
+        let updated = project_data_point_event(
+            data_point_id,
+            created.into_document(),
+            "2020-01-02T03:04:06.000Z",
+            2,
+            DataPointEventDataDocumentData::Updated(data_point_event_data_document_data::Updated {
+                value: YValueDocumentData::Decimal("2".to_owned()),
+            }),
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
+        let document = updated
+            .into_document()
+            .ok_or_else(|| anyhow::anyhow!("not found"))?;
+        assert_eq!(document.chart_id, data_point_id.chart_id().to_string());
+        assert_eq!(document.created_at, "2020-01-02T03:04:05.000Z");
+        assert_eq!(document.x_value, "2020-01-02");
+        assert_eq!(document.version, 2);
+        assert!(matches!(document.y_value, YValueDocumentData::Decimal(s) if s == "2"));
+        Ok(())
+    }
+}
@@ -7,15 +7,38 @@ pub(crate) use data_point_event_data_document_data::DataPointEventDataDocumentDa
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct ChartDocumentData {
     pub(crate) created_at: String,
+    #[serde(default)]
+    pub(crate) series: Vec<SeriesDocumentData>,
     pub(crate) title: String,
+    /// `None` for documents written before the update time was projected. Firestore does not
+    /// list such documents when sorting by `updated_at`, so the updater backfills it from
+    /// `created_at` (see `UpdatedAtBackfillDocumentData`).
+    #[serde(default)]
+    pub(crate) updated_at: Option<String>,
+    /// `0` for documents written before the version was projected. The version of such
+    /// documents is read from the event stream of the chart
+    #[serde(default)]
+    pub(crate) version: i64,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub(crate) struct SeriesDocumentData {
+    pub(crate) id: String,
+    pub(crate) name: String,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct DataPointDocumentData {
     pub(crate) chart_id: String,
     pub(crate) created_at: String,
+    #[serde(default)]
+    pub(crate) series_id: Option<String>,
+    /// `0` for documents written before the version was projected. The version of such
+    /// documents is read from the event stream of the data point
+    #[serde(default)]
+    pub(crate) version: i64,
     pub(crate) x_value: String,
-    pub(crate) y_value: i64,
+    pub(crate) y_value: YValueDocumentData,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -42,6 +65,14 @@ pub(crate) enum EventDataDocumentData {
     DataPoint(DataPointEventDataDocumentData),
 }
 
+/// `Integer` is the format written before `YValue` supported signed decimals.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub(crate) enum YValueDocumentData {
+    Integer(i64),
+    Decimal(String),
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 pub(crate) struct UpdaterMetadataDocumentData {
     pub(crate) last_processed_event_at: String,
@@ -50,6 +81,19 @@ pub(crate) struct UpdaterMetadataDocumentData {
 #[derive(serde::Deserialize, serde::Serialize)]
 pub(crate) struct UpdaterMetadataProcessedEventDocumentData {}
 
+/// The root collection holding the chart documents, `charts` when missing.
+/// A rebuild into a shadow collection points it at the shadow collection
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct ChartCollectionDocumentData {
+    pub(crate) collection_id: String,
+}
+
+/// Written once every chart document has an `updated_at`
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct UpdatedAtBackfillDocumentData {
+    pub(crate) completed_at: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,9 +114,7 @@ mod tests {
             })?,
             serde_json::json!({
                 "at": "2020-01-02T03:04:05.678Z",
-                "data": {
-                    "title":"title"
-                },
+                "title": "title",
                 "id": "id",
                 "stream_id": "stream_id",
                 "stream_type": "chart",
@@ -97,7 +139,6 @@ mod tests {
             })?,
             serde_json::json!({
                 "at": "2020-01-02T03:04:05.678Z",
-                "data": {},
                 "id": "id",
                 "stream_id": "stream_id",
                 "stream_type": "chart",
@@ -124,9 +165,7 @@ mod tests {
             })?,
             serde_json::json!({
                 "at": "2020-01-02T03:04:05.678Z",
-                "data": {
-                    "title": "title"
-                },
+                "title": "title",
                 "id": "id",
                 "stream_id": "stream_id",
                 "stream_type": "chart",
@@ -136,4 +175,84 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_series_created() -> anyhow::Result<()> {
+        assert_eq!(
+            serde_json::to_value(EventDocumentData {
+                at: "2020-01-02T03:04:05.678Z".to_owned(),
+                data: EventDataDocumentData::Chart(ChartEventDataDocumentData::SeriesCreated(
+                    chart_event_data_document_data::SeriesCreated {
+                        name: "name".to_owned(),
+                        series_id: "series_id".to_owned(),
+                    }
+                )),
+                id: "id".to_owned(),
+                stream_id: "stream_id".to_owned(),
+                version: 1,
+            })?,
+            serde_json::json!({
+                "at": "2020-01-02T03:04:05.678Z",
+                "id": "id",
+                "name": "name",
+                "series_id": "series_id",
+                "stream_id": "stream_id",
+                "stream_type": "chart",
+                "type": "series_created",
+                "version": 1,
+            }),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_chart_document_data_without_series() -> anyhow::Result<()> {
+        let document_data = serde_json::from_value::<ChartDocumentData>(serde_json::json!({
+            "created_at": "2020-01-02T03:04:05.678Z",
+            "title": "title",
+        }))?;
+        assert!(document_data.series.is_empty());
+        assert_eq!(document_data.version, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_point_created() -> anyhow::Result<()> {
+        assert_eq!(
+            serde_json::to_value(EventDocumentData {
+                at: "2020-01-02T03:04:05.678Z".to_owned(),
+                data: EventDataDocumentData::DataPoint(DataPointEventDataDocumentData::Created(
+                    data_point_event_data_document_data::Created {
+                        value: YValueDocumentData::Decimal("-12.5".to_owned()),
+                    }
+                )),
+                id: "id".to_owned(),
+                stream_id: "stream_id".to_owned(),
+                version: 1,
+            })?,
+            serde_json::json!({
+                "at": "2020-01-02T03:04:05.678Z",
+                "id": "id",
+                "stream_id": "stream_id",
+                "stream_type": "data_point",
+                "type": "created",
+                "value": "-12.5",
+                "version": 1,
+            }),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_y_value_document_data() -> anyhow::Result<()> {
+        assert!(matches!(
+            serde_json::from_value::<YValueDocumentData>(serde_json::json!(123))?,
+            YValueDocumentData::Integer(123)
+        ));
+        assert!(matches!(
+            serde_json::from_value::<YValueDocumentData>(serde_json::json!("-12.5"))?,
+            YValueDocumentData::Decimal(s) if s == "-12.5"
+        ));
+        Ok(())
+    }
 }
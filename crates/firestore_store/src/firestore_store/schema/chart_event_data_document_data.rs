@@ -3,6 +3,9 @@
 pub(crate) enum ChartEventDataDocumentData {
     Created(Created),
     Deleted(Deleted),
+    SeriesCreated(SeriesCreated),
+    SeriesDeleted(SeriesDeleted),
+    SeriesRenamed(SeriesRenamed),
     Updated(Updated),
 }
 
@@ -14,6 +17,23 @@ pub(crate) struct Created {
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Deleted {}
 
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub(crate) struct SeriesCreated {
+    pub(crate) name: String,
+    pub(crate) series_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub(crate) struct SeriesDeleted {
+    pub(crate) series_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub(crate) struct SeriesRenamed {
+    pub(crate) name: String,
+    pub(crate) series_id: String,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Updated {
     pub(crate) title: String,
@@ -1,3 +1,5 @@
+use super::YValueDocumentData;
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub(crate) enum DataPointEventDataDocumentData {
@@ -8,7 +10,7 @@ pub(crate) enum DataPointEventDataDocumentData {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Created {
-    pub(crate) value: i64,
+    pub(crate) value: YValueDocumentData,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -16,5 +18,5 @@ pub(crate) struct Deleted {}
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Updated {
-    pub(crate) value: i64,
+    pub(crate) value: YValueDocumentData,
 }
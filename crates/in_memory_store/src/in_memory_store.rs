@@ -1,3 +1,4 @@
+mod error;
 mod in_memory_chart_store;
 mod in_memory_data_point_store;
 
@@ -0,0 +1,27 @@
+use store_error::{BoxError, ErrorKind};
+
+fn error_kind(error: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    ErrorKind::of(error, |_| None)
+}
+
+pub(crate) fn chart_repository_error(
+    error: BoxError,
+) -> command_use_case::port::chart_repository::Error {
+    command_use_case::port::chart_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_repository_error(
+    error: BoxError,
+) -> command_use_case::port::data_point_repository::Error {
+    command_use_case::port::data_point_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn chart_reader_error(error: BoxError) -> query_use_case::port::chart_reader::Error {
+    query_use_case::port::chart_reader::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_reader_error(
+    error: BoxError,
+) -> query_use_case::port::data_point_reader::Error {
+    query_use_case::port::data_point_reader::Error::new(error_kind(&*error), error)
+}
@@ -1,5 +1,7 @@
 use std::{collections::BTreeMap, sync::Arc};
 
+use query_use_case::projection;
+use store_error::{check_version, CorruptedError};
 use tokio::sync::Mutex;
 use write_model::{
     aggregate::Chart,
@@ -7,6 +9,8 @@ use write_model::{
     value_object::{ChartId, Version},
 };
 
+use crate::in_memory_store::error::{chart_reader_error, chart_repository_error};
+
 pub struct InMemoryChartStore {
     command_data: Arc<Mutex<BTreeMap<ChartId, Vec<ChartEvent>>>>,
     query_data: Arc<Mutex<Vec<query_use_case::port::ChartQueryData>>>,
@@ -28,10 +32,32 @@ impl InMemoryChartStore {
         let command_data = self.command_data.lock().await;
         Ok(match command_data.get(&id) {
             None => None,
-            Some(events) => Some(Chart::from_events(events)?),
+            Some(events) => Some(Chart::from_events(events).map_err(|e| CorruptedError(e.into()))?),
         })
     }
 
+    async fn find_all_events_impl(
+        &self,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.values().flatten().cloned().collect())
+    }
+
+    async fn find_events_impl(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.get(&id).cloned().unwrap_or_default())
+    }
+
+    async fn find_ids_impl(
+        &self,
+    ) -> Result<Vec<ChartId>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.keys().copied().collect())
+    }
+
     async fn get_impl(
         &self,
         id: ChartId,
@@ -53,45 +79,18 @@ impl InMemoryChartStore {
         if events.is_empty() {
             return Ok(());
         }
-        match current {
-            None => {
-                let id = events[0].stream_id;
-                command_data.insert(id, events.to_vec());
-            }
-            Some(_version) => {
-                let id = events[0].stream_id;
-                let stored_events = command_data.get_mut(&id).ok_or("not found")?;
-                // TODO: check version
-                stored_events.extend(events.to_vec());
-            }
-        }
+        let id = events[0].stream_id;
+        let stored_version = command_data
+            .get(&id)
+            .and_then(|stored_events| stored_events.last())
+            .map(|stored_event| stored_event.version);
+        check_version(current, stored_version)?;
+        command_data.entry(id).or_default().extend(events.to_vec());
 
         // query writer
         for event in events {
-            match &event.data {
-                write_model::event::ChartEventData::Created(data) => {
-                    query_data.push(query_use_case::port::ChartQueryData {
-                        created_at: event.at,
-                        id: event.stream_id,
-                        title: data.title.clone(),
-                    });
-                }
-                write_model::event::ChartEventData::Deleted(_) => {
-                    if let Some(index) = query_data
-                        .iter()
-                        .position(|chart| chart.id == event.stream_id)
-                    {
-                        query_data.remove(index);
-                    }
-                }
-                write_model::event::ChartEventData::Updated(data) => {
-                    let index = query_data
-                        .iter()
-                        .position(|chart| chart.id == event.stream_id)
-                        .ok_or("not found")?;
-                    query_data[index].title.clone_from(&data.title);
-                }
-            }
+            projection::apply_chart_event(&mut query_data, event)
+                .map_err(|e| CorruptedError(e.into()))?;
         }
 
         Ok(())
@@ -107,20 +106,16 @@ impl query_use_case::port::ChartReader for InMemoryChartStore {
         Option<query_use_case::port::ChartQueryData>,
         query_use_case::port::chart_reader::Error,
     > {
-        self.get_impl(id)
-            .await
-            .map_err(query_use_case::port::chart_reader::Error::from)
+        self.get_impl(id).await.map_err(chart_reader_error)
     }
 
     async fn list(
         &self,
+        query: query_use_case::port::ChartListQuery,
     ) -> Result<Vec<query_use_case::port::ChartQueryData>, query_use_case::port::chart_reader::Error>
     {
         let query_data = self.query_data.lock().await;
-        Ok(query_data
-            .iter()
-            .cloned()
-            .collect::<Vec<query_use_case::port::ChartQueryData>>())
+        Ok(query.apply(query_data.iter().cloned()))
     }
 }
 
@@ -130,9 +125,30 @@ impl command_use_case::port::ChartRepository for InMemoryChartStore {
         &self,
         id: ChartId,
     ) -> Result<Option<Chart>, command_use_case::port::chart_repository::Error> {
-        self.find_impl(id)
+        self.find_impl(id).await.map_err(chart_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_events_impl(id)
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<ChartId>, command_use_case::port::chart_repository::Error> {
+        self.find_ids_impl().await.map_err(chart_repository_error)
     }
 
     async fn store(
@@ -142,6 +158,16 @@ impl command_use_case::port::ChartRepository for InMemoryChartStore {
     ) -> Result<(), command_use_case::port::chart_repository::Error> {
         self.store_impl(current, events)
             .await
-            .map_err(command_use_case::port::chart_repository::Error::from)
+            .map_err(chart_repository_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        store_test_kit::test_chart_store(&InMemoryChartStore::new()).await
     }
 }
@@ -1,5 +1,7 @@
 use std::{collections::BTreeMap, sync::Arc};
 
+use query_use_case::projection;
+use store_error::{check_version, CorruptedError};
 use tokio::sync::Mutex;
 use write_model::{
     aggregate::DataPoint,
@@ -7,6 +9,8 @@ use write_model::{
     value_object::{ChartId, DataPointId, Version},
 };
 
+use crate::in_memory_store::error::{data_point_reader_error, data_point_repository_error};
+
 pub struct InMemoryDataPointStore {
     command_data: Arc<Mutex<BTreeMap<DataPointId, Vec<DataPointEvent>>>>,
     query_data: Arc<Mutex<Vec<query_use_case::port::DataPointQueryData>>>,
@@ -28,10 +32,34 @@ impl InMemoryDataPointStore {
         let command_data = self.command_data.lock().await;
         Ok(match command_data.get(&id) {
             None => None,
-            Some(events) => Some(DataPoint::from_events(events)?),
+            Some(events) => {
+                Some(DataPoint::from_events(events).map_err(|e| CorruptedError(e.into()))?)
+            }
         })
     }
 
+    async fn find_all_events_impl(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.values().flatten().cloned().collect())
+    }
+
+    async fn find_events_impl(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.get(&id).cloned().unwrap_or_default())
+    }
+
+    async fn find_ids_impl(
+        &self,
+    ) -> Result<Vec<DataPointId>, Box<dyn std::error::Error + Send + Sync>> {
+        let command_data = self.command_data.lock().await;
+        Ok(command_data.keys().copied().collect())
+    }
+
     async fn get_impl(
         &self,
         id: DataPointId,
@@ -43,7 +71,9 @@ impl InMemoryDataPointStore {
         Ok(query_data
             .iter()
             .find(|data_point| {
-                data_point.chart_id == id.chart_id() && data_point.x_value == id.x_value()
+                data_point.chart_id == id.chart_id()
+                    && data_point.series_id == id.series_id()
+                    && data_point.x_value == id.x_value()
             })
             .cloned())
     }
@@ -51,6 +81,8 @@ impl InMemoryDataPointStore {
     async fn list_impl(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         Box<dyn std::error::Error + Send + Sync>,
@@ -58,7 +90,11 @@ impl InMemoryDataPointStore {
         let query_data = self.query_data.lock().await;
         Ok(query_data
             .iter()
-            .filter(|data_point| data_point.chart_id == chart_id)
+            .filter(|data_point| {
+                data_point.chart_id == chart_id
+                    && series.matches(data_point.series_id)
+                    && range.contains(data_point.x_value)
+            })
             .cloned()
             .collect::<Vec<query_use_case::port::DataPointQueryData>>())
     }
@@ -73,49 +109,18 @@ impl InMemoryDataPointStore {
         if events.is_empty() {
             return Ok(());
         }
-        match current {
-            None => {
-                let id = events[0].stream_id;
-                command_data.insert(id, events.to_vec());
-            }
-            Some(_version) => {
-                let id = events[0].stream_id;
-                let stored_events = command_data.get_mut(&id).ok_or("not found")?;
-                // TODO: check version
-                stored_events.extend(events.to_vec());
-            }
-        }
+        let id = events[0].stream_id;
+        let stored_version = command_data
+            .get(&id)
+            .and_then(|stored_events| stored_events.last())
+            .map(|stored_event| stored_event.version);
+        check_version(current, stored_version)?;
+        command_data.entry(id).or_default().extend(events.to_vec());
 
         // query writer
         for event in events {
-            match &event.data {
-                write_model::event::DataPointEventData::Created(data) => {
-                    query_data.push(query_use_case::port::DataPointQueryData {
-                        created_at: event.at,
-                        chart_id: event.stream_id.chart_id(),
-                        x_value: event.stream_id.x_value(),
-                        y_value: data.value,
-                    });
-                }
-                write_model::event::DataPointEventData::Deleted(_) => {
-                    if let Some(index) = query_data.iter().position(|data_point| {
-                        data_point.chart_id == event.stream_id.chart_id()
-                            && data_point.x_value == event.stream_id.x_value()
-                    }) {
-                        query_data.remove(index);
-                    }
-                }
-                write_model::event::DataPointEventData::Updated(data) => {
-                    let index = query_data
-                        .iter()
-                        .position(|data_point| {
-                            data_point.chart_id == event.stream_id.chart_id()
-                                && data_point.x_value == event.stream_id.x_value()
-                        })
-                        .ok_or("not found")?;
-                    query_data[index].y_value = data.value;
-                }
-            }
+            projection::apply_data_point_event(&mut query_data, event)
+                .map_err(|e| CorruptedError(e.into()))?;
         }
 
         Ok(())
@@ -131,21 +136,21 @@ impl query_use_case::port::DataPointReader for InMemoryDataPointStore {
         Option<query_use_case::port::DataPointQueryData>,
         query_use_case::port::data_point_reader::Error,
     > {
-        self.get_impl(id)
-            .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+        self.get_impl(id).await.map_err(data_point_reader_error)
     }
 
     async fn list(
         &self,
         chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
     ) -> Result<
         Vec<query_use_case::port::DataPointQueryData>,
         query_use_case::port::data_point_reader::Error,
     > {
-        self.list_impl(chart_id)
+        self.list_impl(chart_id, series, range)
             .await
-            .map_err(query_use_case::port::data_point_reader::Error::from)
+            .map_err(data_point_reader_error)
     }
 }
 
@@ -157,7 +162,32 @@ impl command_use_case::port::DataPointRepository for InMemoryDataPointStore {
     ) -> Result<Option<DataPoint>, command_use_case::port::data_point_repository::Error> {
         self.find_impl(id)
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_events_impl(id)
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<DataPointId>, command_use_case::port::data_point_repository::Error> {
+        self.find_ids_impl()
+            .await
+            .map_err(data_point_repository_error)
     }
 
     async fn store(
@@ -167,6 +197,16 @@ impl command_use_case::port::DataPointRepository for InMemoryDataPointStore {
     ) -> Result<(), command_use_case::port::data_point_repository::Error> {
         self.store_impl(current, events)
             .await
-            .map_err(command_use_case::port::data_point_repository::Error::from)
+            .map_err(data_point_repository_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        store_test_kit::test_data_point_store(&InMemoryDataPointStore::new()).await
     }
 }
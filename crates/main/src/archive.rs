@@ -0,0 +1,291 @@
+use std::str::FromStr as _;
+
+use write_model::{
+    event::{
+        BaseEvent, ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated,
+        ChartSeriesDeleted, ChartSeriesRenamed, ChartUpdated, DataPointCreated, DataPointDeleted,
+        DataPointEvent, DataPointEventData, DataPointUpdated, Event,
+    },
+    value_object::{SeriesId, Version, YValue},
+};
+
+/// One line of a backup archive (JSON Lines). Ids, versions and `at` are kept as they are
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "stream_type")]
+pub enum ArchiveEvent {
+    Chart(ArchiveChartEvent),
+    DataPoint(ArchiveDataPointEvent),
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ArchiveChartEvent {
+    at: String,
+    data: ArchiveChartEventData,
+    id: String,
+    stream_id: String,
+    version: u32,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+enum ArchiveChartEventData {
+    Created { title: String },
+    Deleted {},
+    SeriesCreated { name: String, series_id: String },
+    SeriesDeleted { series_id: String },
+    SeriesRenamed { name: String, series_id: String },
+    Updated { title: String },
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ArchiveDataPointEvent {
+    at: String,
+    data: ArchiveDataPointEventData,
+    id: String,
+    stream_id: String,
+    version: u32,
+}
+
+/// `value` is a decimal string to keep the precision of `YValue`
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+enum ArchiveDataPointEventData {
+    Created { value: String },
+    Deleted {},
+    Updated { value: String },
+}
+
+impl From<&Event> for ArchiveEvent {
+    fn from(event: &Event) -> Self {
+        match event {
+            Event::Chart(event) => Self::Chart(ArchiveChartEvent::from(event)),
+            Event::DataPoint(event) => Self::DataPoint(ArchiveDataPointEvent::from(event)),
+        }
+    }
+}
+
+impl TryFrom<ArchiveEvent> for Event {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(event: ArchiveEvent) -> Result<Self, Self::Error> {
+        Ok(match event {
+            ArchiveEvent::Chart(event) => Event::Chart(ChartEvent::try_from(event)?),
+            ArchiveEvent::DataPoint(event) => Event::DataPoint(DataPointEvent::try_from(event)?),
+        })
+    }
+}
+
+impl From<&ChartEvent> for ArchiveChartEvent {
+    fn from(
+        BaseEvent {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: &ChartEvent,
+    ) -> Self {
+        Self {
+            at: at.to_string(),
+            data: match data {
+                ChartEventData::Created(ChartCreated { title }) => ArchiveChartEventData::Created {
+                    title: title.clone(),
+                },
+                ChartEventData::Deleted(ChartDeleted {}) => ArchiveChartEventData::Deleted {},
+                ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id }) => {
+                    ArchiveChartEventData::SeriesCreated {
+                        name: name.clone(),
+                        series_id: series_id.to_string(),
+                    }
+                }
+                ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+                    ArchiveChartEventData::SeriesDeleted {
+                        series_id: series_id.to_string(),
+                    }
+                }
+                ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+                    ArchiveChartEventData::SeriesRenamed {
+                        name: name.clone(),
+                        series_id: series_id.to_string(),
+                    }
+                }
+                ChartEventData::Updated(ChartUpdated { title }) => ArchiveChartEventData::Updated {
+                    title: title.clone(),
+                },
+            },
+            id: id.to_string(),
+            stream_id: stream_id.to_string(),
+            version: u32::from(*version),
+        }
+    }
+}
+
+impl TryFrom<ArchiveChartEvent> for ChartEvent {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(
+        ArchiveChartEvent {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: ArchiveChartEvent,
+    ) -> Result<Self, Self::Error> {
+        Ok(ChartEvent {
+            at: at.parse()?,
+            data: match data {
+                ArchiveChartEventData::Created { title } => {
+                    ChartEventData::Created(ChartCreated { title })
+                }
+                ArchiveChartEventData::Deleted {} => ChartEventData::Deleted(ChartDeleted {}),
+                ArchiveChartEventData::SeriesCreated { name, series_id } => {
+                    ChartEventData::SeriesCreated(ChartSeriesCreated {
+                        name,
+                        series_id: SeriesId::from_str(&series_id)?,
+                    })
+                }
+                ArchiveChartEventData::SeriesDeleted { series_id } => {
+                    ChartEventData::SeriesDeleted(ChartSeriesDeleted {
+                        series_id: SeriesId::from_str(&series_id)?,
+                    })
+                }
+                ArchiveChartEventData::SeriesRenamed { name, series_id } => {
+                    ChartEventData::SeriesRenamed(ChartSeriesRenamed {
+                        name,
+                        series_id: SeriesId::from_str(&series_id)?,
+                    })
+                }
+                ArchiveChartEventData::Updated { title } => {
+                    ChartEventData::Updated(ChartUpdated { title })
+                }
+            },
+            id: id.parse()?,
+            stream_id: stream_id.parse()?,
+            version: Version::try_from(version)?,
+        })
+    }
+}
+
+impl From<&DataPointEvent> for ArchiveDataPointEvent {
+    fn from(
+        BaseEvent {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: &DataPointEvent,
+    ) -> Self {
+        Self {
+            at: at.to_string(),
+            data: match data {
+                DataPointEventData::Created(DataPointCreated { value }) => {
+                    ArchiveDataPointEventData::Created {
+                        value: value.to_string(),
+                    }
+                }
+                DataPointEventData::Deleted(DataPointDeleted {}) => {
+                    ArchiveDataPointEventData::Deleted {}
+                }
+                DataPointEventData::Updated(DataPointUpdated { value }) => {
+                    ArchiveDataPointEventData::Updated {
+                        value: value.to_string(),
+                    }
+                }
+            },
+            id: id.to_string(),
+            stream_id: stream_id.to_string(),
+            version: u32::from(*version),
+        }
+    }
+}
+
+impl TryFrom<ArchiveDataPointEvent> for DataPointEvent {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(
+        ArchiveDataPointEvent {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: ArchiveDataPointEvent,
+    ) -> Result<Self, Self::Error> {
+        Ok(DataPointEvent {
+            at: at.parse()?,
+            data: match data {
+                ArchiveDataPointEventData::Created { value } => {
+                    DataPointEventData::Created(DataPointCreated {
+                        value: YValue::from_str(&value)?,
+                    })
+                }
+                ArchiveDataPointEventData::Deleted {} => {
+                    DataPointEventData::Deleted(DataPointDeleted {})
+                }
+                ArchiveDataPointEventData::Updated { value } => {
+                    DataPointEventData::Updated(DataPointUpdated {
+                        value: YValue::from_str(&value)?,
+                    })
+                }
+            },
+            id: id.parse()?,
+            stream_id: stream_id.parse()?,
+            version: Version::try_from(version)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use write_model::{
+        aggregate::{Chart, DataPoint},
+        value_object::XValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> anyhow::Result<()> {
+        let (chart, created) = Chart::create("title1".to_owned())?;
+        let (chart, series_created) = chart.create_series("series1".to_owned())?;
+        let (data_point, data_point_created) = DataPoint::create(
+            chart.id(),
+            Some(chart.series()[0].id()),
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("-1.25")?,
+        )?;
+        let (_, data_point_deleted) = data_point.delete()?;
+        let events = created
+            .into_iter()
+            .chain(series_created)
+            .map(Event::from)
+            .chain(
+                data_point_created
+                    .into_iter()
+                    .chain(data_point_deleted)
+                    .map(Event::from),
+            )
+            .collect::<Vec<Event>>();
+        for event in events {
+            let line = serde_json::to_string(&ArchiveEvent::from(&event))?;
+            let decoded = serde_json::from_str::<ArchiveEvent>(&line)?;
+            assert_eq!(
+                Event::try_from(decoded).map_err(|e| anyhow::anyhow!(e))?,
+                event
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() -> anyhow::Result<()> {
+        let line = r#"{"stream_type":"data_point","at":"2020-01-02T03:04:05.678Z","data":{"type":"Updated","value":"1.5"},"id":"5ad4a90d-b6b9-4e18-b5ad-9c2398273d75","stream_id":"1748b794-4294-4652-8cda-f357a1179372:2020-01-02","version":2}"#;
+        let event = Event::try_from(serde_json::from_str::<ArchiveEvent>(line)?)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(event.version(), Version::try_from(2_u32)?);
+        assert_eq!(serde_json::to_string(&ArchiveEvent::from(&event))?, line);
+        Ok(())
+    }
+}
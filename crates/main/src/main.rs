@@ -1,5 +1,8 @@
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt as _};
 
+mod archive;
+mod replay;
+mod store;
 mod subcommand;
 
 #[derive(clap::Parser)]
@@ -10,7 +13,22 @@ struct Args {
 
 #[derive(clap::Subcommand)]
 enum Subcommand {
-    Server,
+    /// Write every event of the store to a JSON Lines archive
+    Backup(subcommand::backup::Args),
+    /// Print the events of the store
+    Events(subcommand::events::Args),
+    /// Import data points into a chart from a CSV file
+    Import(subcommand::import::Args),
+    /// Copy every event from one store to another. Safe to rerun after an interruption
+    Migrate(subcommand::migrate::Args),
+    /// Rebuild the query data from the whole event log
+    RebuildProjections(subcommand::rebuild_projections::Args),
+    /// Replay the events of an archive written by `backup` into the store
+    Restore(subcommand::restore::Args),
+    /// Run the HTTP server
+    Server(subcommand::server::Args),
+    /// Check the integrity of the event store and its query data
+    Verify(subcommand::verify::Args),
 }
 
 #[tokio::main]
@@ -21,6 +39,13 @@ async fn main() -> anyhow::Result<()> {
         .init();
     let args = <Args as clap::Parser>::parse();
     match args.subcommand {
-        Subcommand::Server => subcommand::server::run().await,
+        Subcommand::Backup(args) => subcommand::backup::run(args).await,
+        Subcommand::Events(args) => subcommand::events::run(args).await,
+        Subcommand::Import(args) => subcommand::import::run(args).await,
+        Subcommand::Migrate(args) => subcommand::migrate::run(args).await,
+        Subcommand::RebuildProjections(args) => subcommand::rebuild_projections::run(args).await,
+        Subcommand::Restore(args) => subcommand::restore::run(args).await,
+        Subcommand::Server(args) => subcommand::server::run(args).await,
+        Subcommand::Verify(args) => subcommand::verify::run(args).await,
     }
 }
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use command_use_case::port::{ChartRepository, DataPointRepository};
+use write_model::{
+    event::{BaseEvent, ChartEvent, DataPointEvent, EventStream},
+    value_object::Version,
+};
+
+/// The number of events stored at once. Firestore limits the writes in a transaction
+const BATCH_SIZE: usize = 100;
+
+/// Groups the events by stream, keeping their order within each stream
+pub fn group_by_stream<ES: EventStream>(
+    events: impl IntoIterator<Item = BaseEvent<ES>>,
+) -> BTreeMap<ES::Id, Vec<BaseEvent<ES>>>
+where
+    ES::Id: Copy + Ord,
+{
+    let mut streams = BTreeMap::<ES::Id, Vec<BaseEvent<ES>>>::new();
+    for event in events {
+        streams.entry(event.stream_id).or_default().push(event);
+    }
+    streams
+}
+
+/// Stores the events of a chart stream as they are, after the `current` version
+/// (`None` means the stream must not exist yet)
+pub async fn replay_chart_events(
+    repository: &(dyn ChartRepository + Send + Sync),
+    mut current: Option<Version>,
+    events: &[ChartEvent],
+) -> anyhow::Result<()> {
+    for batch in events.chunks(BATCH_SIZE) {
+        repository.store(current, batch).await?;
+        current = batch.last().map(|event| event.version);
+    }
+    Ok(())
+}
+
+/// Stores the events of a data point stream as they are, after the `current` version
+/// (`None` means the stream must not exist yet)
+pub async fn replay_data_point_events(
+    repository: &(dyn DataPointRepository + Send + Sync),
+    mut current: Option<Version>,
+    events: &[DataPointEvent],
+) -> anyhow::Result<()> {
+    for batch in events.chunks(BATCH_SIZE) {
+        repository.store(current, batch).await?;
+        current = batch.last().map(|event| event.version);
+    }
+    Ok(())
+}
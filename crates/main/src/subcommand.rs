@@ -1 +1,8 @@
+pub mod backup;
+pub mod events;
+pub mod import;
+pub mod migrate;
+pub mod rebuild_projections;
+pub mod restore;
 pub mod server;
+pub mod verify;
@@ -0,0 +1,68 @@
+use std::{
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use anyhow::Context as _;
+use command_use_case::port::{HasChartRepository as _, HasDataPointRepository as _};
+use write_model::event::Event;
+
+use crate::{archive::ArchiveEvent, store};
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+    /// Archive file (JSON Lines) to write. `-` writes to the standard output
+    #[arg(long)]
+    output: PathBuf,
+}
+
+pub async fn run(Args { output, store }: Args) -> anyhow::Result<()> {
+    let app = store::build_app(store).await?;
+    let writer: Box<dyn Write> = if output.as_os_str() == "-" {
+        Box::new(std::io::stdout().lock())
+    } else {
+        Box::new(
+            std::fs::File::create(&output)
+                .with_context(|| format!("create {}", output.display()))?,
+        )
+    };
+    let (chart_count, data_point_count) = write_archive(&app, writer).await?;
+    eprintln!(
+        "backed up {} chart events and {} data point events",
+        chart_count, data_point_count
+    );
+    Ok(())
+}
+
+/// Writes every event of the store as JSON Lines, the chart events first.
+/// Returns the number of chart events and data point events
+pub(crate) async fn write_archive(
+    app: &server::App,
+    writer: impl Write,
+) -> anyhow::Result<(usize, usize)> {
+    let chart_events = app
+        .chart_repository()
+        .find_all_events()
+        .await
+        .context("find chart events")?;
+    let data_point_events = app
+        .data_point_repository()
+        .find_all_events()
+        .await
+        .context("find data point events")?;
+
+    let mut writer = BufWriter::new(writer);
+    let (chart_count, data_point_count) = (chart_events.len(), data_point_events.len());
+    for event in chart_events
+        .into_iter()
+        .map(Event::from)
+        .chain(data_point_events.into_iter().map(Event::from))
+    {
+        serde_json::to_writer(&mut writer, &ArchiveEvent::from(&event))?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok((chart_count, data_point_count))
+}
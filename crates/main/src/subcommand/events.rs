@@ -0,0 +1,321 @@
+use std::{
+    io::{BufWriter, Write},
+    str::FromStr as _,
+};
+
+use anyhow::Context as _;
+use command_use_case::port::{HasChartRepository as _, HasDataPointRepository as _};
+use write_model::{
+    event::{
+        ChartCreated, ChartEventData, ChartSeriesCreated, ChartSeriesDeleted, ChartSeriesRenamed,
+        ChartUpdated, DataPointCreated, DataPointEventData, DataPointUpdated, Event,
+    },
+    value_object::{ChartId, DataPointId, DateTime},
+};
+
+use crate::{archive::ArchiveEvent, store};
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Print the events of every stream, oldest first
+    List(ListArgs),
+    /// Print the events of a chart or data point stream
+    Show(ShowArgs),
+}
+
+#[derive(clap::Args)]
+struct ListArgs {
+    #[command(flatten)]
+    store: store::Args,
+    #[command(flatten)]
+    options: Options,
+    /// Only events of this stream type
+    #[arg(long, value_enum)]
+    stream_type: Option<StreamType>,
+}
+
+#[derive(clap::Args)]
+struct ShowArgs {
+    #[command(flatten)]
+    store: store::Args,
+    #[command(flatten)]
+    options: Options,
+    /// Chart id or data point id (`<chart_id>:<date>` or `<chart_id>:<series_id>:<date>`)
+    stream_id: String,
+}
+
+#[derive(clap::Args)]
+struct Options {
+    /// Only events of this type. Can be repeated
+    #[arg(long, value_enum)]
+    event_type: Vec<EventType>,
+    /// Output format. `json` writes JSON Lines in the `backup` format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Only events at or after this time (RFC 3339)
+    #[arg(long)]
+    since: Option<DateTime>,
+    /// Only events before this time (RFC 3339)
+    #[arg(long)]
+    until: Option<DateTime>,
+}
+
+impl Options {
+    fn matches(&self, event: &Event) -> bool {
+        (self.event_type.is_empty() || self.event_type.contains(&EventType::of(event)))
+            && self.since.map_or(true, |since| since <= event.at())
+            && self.until.map_or(true, |until| event.at() < until)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+enum StreamType {
+    Chart,
+    DataPoint,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+enum EventType {
+    Created,
+    Deleted,
+    SeriesCreated,
+    SeriesDeleted,
+    SeriesRenamed,
+    Updated,
+}
+
+impl EventType {
+    fn of(event: &Event) -> Self {
+        match event {
+            Event::Chart(event) => match event.data {
+                ChartEventData::Created(_) => Self::Created,
+                ChartEventData::Deleted(_) => Self::Deleted,
+                ChartEventData::SeriesCreated(_) => Self::SeriesCreated,
+                ChartEventData::SeriesDeleted(_) => Self::SeriesDeleted,
+                ChartEventData::SeriesRenamed(_) => Self::SeriesRenamed,
+                ChartEventData::Updated(_) => Self::Updated,
+            },
+            Event::DataPoint(event) => match event.data {
+                DataPointEventData::Created(_) => Self::Created,
+                DataPointEventData::Deleted(_) => Self::Deleted,
+                DataPointEventData::Updated(_) => Self::Updated,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    Json,
+    Text,
+}
+
+pub async fn run(Args { command }: Args) -> anyhow::Result<()> {
+    match command {
+        Command::List(args) => list(args).await,
+        Command::Show(args) => show(args).await,
+    }
+}
+
+async fn list(
+    ListArgs {
+        options,
+        store,
+        stream_type,
+    }: ListArgs,
+) -> anyhow::Result<()> {
+    let app = store::build_app(store).await?;
+    let mut events = vec![];
+    if stream_type != Some(StreamType::DataPoint) {
+        events.extend(
+            app.chart_repository()
+                .find_all_events()
+                .await
+                .context("find chart events")?
+                .into_iter()
+                .map(Event::from),
+        );
+    }
+    if stream_type != Some(StreamType::Chart) {
+        events.extend(
+            app.data_point_repository()
+                .find_all_events()
+                .await
+                .context("find data point events")?
+                .into_iter()
+                .map(Event::from),
+        );
+    }
+    // stable: the events of a stream with the same `at` stay in version order
+    events.sort_by_key(Event::at);
+    print(&options, events)
+}
+
+async fn show(
+    ShowArgs {
+        options,
+        store,
+        stream_id,
+    }: ShowArgs,
+) -> anyhow::Result<()> {
+    let app = store::build_app(store).await?;
+    let events = if let Ok(chart_id) = ChartId::from_str(&stream_id) {
+        app.chart_repository()
+            .find_events(chart_id)
+            .await
+            .context("find chart events")?
+            .into_iter()
+            .map(Event::from)
+            .collect::<Vec<Event>>()
+    } else if let Ok(data_point_id) = DataPointId::from_str(&stream_id) {
+        app.data_point_repository()
+            .find_events(data_point_id)
+            .await
+            .context("find data point events")?
+            .into_iter()
+            .map(Event::from)
+            .collect::<Vec<Event>>()
+    } else {
+        anyhow::bail!(
+            "stream id must be a chart id or a data point id (stream_id = {})",
+            stream_id
+        );
+    };
+    if events.is_empty() {
+        anyhow::bail!("stream not found (stream_id = {})", stream_id);
+    }
+    print(&options, events)
+}
+
+fn print(options: &Options, events: Vec<Event>) -> anyhow::Result<()> {
+    let mut writer = BufWriter::new(std::io::stdout().lock());
+    for event in events.iter().filter(|event| options.matches(event)) {
+        match options.format {
+            Format::Json => serde_json::to_writer(&mut writer, &ArchiveEvent::from(event))?,
+            Format::Text => write!(writer, "{}", format_text(event))?,
+        }
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// `<at> <stream type> <stream id> v<version> <event type> [<key>=<value> ...]`
+fn format_text(event: &Event) -> String {
+    let (stream_type, payload) = match event {
+        Event::Chart(event) => (
+            "chart",
+            match &event.data {
+                ChartEventData::Created(ChartCreated { title })
+                | ChartEventData::Updated(ChartUpdated { title }) => {
+                    format!(" title={:?}", title)
+                }
+                ChartEventData::Deleted(_) => String::new(),
+                ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id })
+                | ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+                    format!(" series_id={} name={:?}", series_id, name)
+                }
+                ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+                    format!(" series_id={}", series_id)
+                }
+            },
+        ),
+        Event::DataPoint(event) => (
+            "data_point",
+            match &event.data {
+                DataPointEventData::Created(DataPointCreated { value })
+                | DataPointEventData::Updated(DataPointUpdated { value }) => {
+                    format!(" value={}", value)
+                }
+                DataPointEventData::Deleted(_) => String::new(),
+            },
+        ),
+    };
+    format!(
+        "{} {} {} v{} {}{}",
+        event.at(),
+        stream_type,
+        event.stream_id(),
+        u32::from(event.version()),
+        clap::ValueEnum::to_possible_value(&EventType::of(event))
+            .expect("event type not to be skipped")
+            .get_name(),
+        payload
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use write_model::{
+        aggregate::{Chart, DataPoint},
+        value_object::{XValue, YValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_format_text() -> anyhow::Result<()> {
+        let (chart, created) = Chart::create("title1".to_owned())?;
+        let (chart, series_created) = chart.create_series("series1".to_owned())?;
+        let series_id = chart.series()[0].id();
+        let (_, data_point_created) = DataPoint::create(
+            chart.id(),
+            None,
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("-1.25")?,
+        )?;
+        let created = Event::from(created[0].clone());
+        assert_eq!(
+            format_text(&created),
+            format!(
+                "{} chart {} v1 created title=\"title1\"",
+                created.at(),
+                chart.id()
+            )
+        );
+        let series_created = Event::from(series_created[0].clone());
+        assert_eq!(
+            format_text(&series_created),
+            format!(
+                "{} chart {} v2 series-created series_id={} name=\"series1\"",
+                series_created.at(),
+                chart.id(),
+                series_id
+            )
+        );
+        let data_point_created = Event::from(data_point_created[0].clone());
+        assert_eq!(
+            format_text(&data_point_created),
+            format!(
+                "{} data_point {}:2020-01-02 v1 created value=-1.25",
+                data_point_created.at(),
+                chart.id()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_matches() -> anyhow::Result<()> {
+        let (_, created) = Chart::create("title1".to_owned())?;
+        let event = Event::from(created[0].clone());
+        let options = |event_type: Vec<EventType>, since: Option<DateTime>, until| Options {
+            event_type,
+            format: Format::Text,
+            since,
+            until,
+        };
+        assert!(options(vec![], None, None).matches(&event));
+        assert!(options(vec![EventType::Updated, EventType::Created], None, None).matches(&event));
+        assert!(!options(vec![EventType::Deleted], None, None).matches(&event));
+        assert!(options(vec![], Some(event.at()), None).matches(&event));
+        assert!(!options(vec![], None, Some(event.at())).matches(&event));
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+
+use command_use_case::import_data_points::ImportDataPoints as _;
+
+use crate::store;
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+    /// Chart to import the data points into
+    #[arg(long)]
+    chart_id: String,
+    /// CSV file (`date,value`). `-` reads the standard input
+    #[arg(long)]
+    file: PathBuf,
+    /// Header name of the date column
+    #[arg(long)]
+    date_column: Option<String>,
+    /// Header name of the value column
+    #[arg(long)]
+    value_column: Option<String>,
+    /// Date format. `YYYY`, `MM` and `DD` stand for the year, the month and the day [default: YYYY-MM-DD]
+    #[arg(long)]
+    date_format: Option<String>,
+    /// Treat the first row as a header (detected from the first row by default)
+    #[arg(long, conflicts_with = "no_header")]
+    header: bool,
+    /// Treat the first row as data
+    #[arg(long)]
+    no_header: bool,
+    /// What to do with the dates that already have a data point
+    #[arg(long, value_enum, default_value_t = Mode::Fail)]
+    mode: Mode,
+    /// Series to import the data points into (the default series if omitted)
+    #[arg(long)]
+    series_id: Option<String>,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Mode {
+    /// Import nothing if any date already has a data point
+    Fail,
+    /// Update the existing data points
+    Overwrite,
+    /// Keep the existing data points
+    Skip,
+}
+
+pub async fn run(
+    Args {
+        store,
+        chart_id,
+        file,
+        date_column,
+        value_column,
+        date_format,
+        header,
+        no_header,
+        mode,
+        series_id,
+    }: Args,
+) -> anyhow::Result<()> {
+    let csv = if file.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("read stdin")?
+    } else {
+        std::fs::read_to_string(&file).with_context(|| format!("read {}", file.display()))?
+    };
+    let app = store::build_app(store).await?;
+    let output = app
+        .execute(command_use_case::import_data_points::Input {
+            chart_id,
+            csv,
+            date_column,
+            date_format,
+            header: match (header, no_header) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            mode: match mode {
+                Mode::Fail => command_use_case::import_data_points::Mode::Fail,
+                Mode::Overwrite => command_use_case::import_data_points::Mode::Overwrite,
+                Mode::Skip => command_use_case::import_data_points::Mode::Skip,
+            },
+            series_id,
+            value_column,
+        })
+        .await
+        .map_err(|e| match &e {
+            command_use_case::import_data_points::Error::DataPointCreate { line, .. }
+            | command_use_case::import_data_points::Error::DataPointUpdate { line, .. } => {
+                let message = format!(
+                    "the rows before line {} have been imported. Run again with --mode skip to resume",
+                    line
+                );
+                anyhow::Error::new(e).context(message)
+            }
+            _ => anyhow::Error::new(e),
+        })?;
+    for error in &output.errors {
+        eprintln!("line {}: {}", error.line, error.message);
+    }
+    if !output.errors.is_empty() {
+        anyhow::bail!("{} row(s) cannot be imported", output.errors.len());
+    }
+    println!(
+        "created: {}, overwritten: {}, skipped: {}",
+        output.created, output.overwritten, output.skipped
+    );
+    Ok(())
+}
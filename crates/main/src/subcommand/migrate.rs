@@ -0,0 +1,472 @@
+use std::fmt::Display;
+
+use anyhow::Context as _;
+use command_use_case::port::{
+    ChartRepository, DataPointRepository, HasChartRepository as _, HasDataPointRepository as _,
+};
+use write_model::{
+    event::{
+        BaseEvent, ChartEvent, ChartEventStream, DataPointEvent, DataPointEventStream, EventStream,
+    },
+    value_object::{ChartId, DataPointId, Version},
+};
+
+use crate::{
+    replay::{replay_chart_events, replay_data_point_events},
+    store::Spec,
+    subcommand::rebuild_projections,
+};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Store to read: `memory`, `fs:<dir>`, `sqlite:<path>`, `postgres://<url>` or `firestore`
+    #[arg(long)]
+    from: Spec,
+    /// Store to write: `memory`, `fs:<dir>`, `sqlite:<path>`, `postgres://<url>` or `firestore`
+    #[arg(long)]
+    to: Spec,
+    /// Print what would be copied without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// What to do with a source stream, judging from the events already in the target
+#[derive(Debug, Eq, PartialEq)]
+enum Step {
+    /// The stream does not exist in the target
+    Create,
+    /// The target has the first events of the stream (an interrupted run). Stores the rest after `Version`
+    Append(Version, usize),
+    UpToDate,
+    /// The target has other events for the stream
+    Diverged,
+}
+
+fn plan<ES: EventStream>(source: &[BaseEvent<ES>], target: Option<&[BaseEvent<ES>]>) -> Step
+where
+    BaseEvent<ES>: PartialEq,
+{
+    match target {
+        None => Step::Create,
+        Some(target) if target == source => Step::UpToDate,
+        Some(target) if source.starts_with(target) => match target.last() {
+            Some(last) => Step::Append(last.version, target.len()),
+            None => Step::Create,
+        },
+        Some(_) => Step::Diverged,
+    }
+}
+
+/// The per-stream reads and writes of one aggregate type in a store
+#[async_trait::async_trait]
+trait Streams<ES: EventStream> {
+    /// "chart" or "data point"
+    const KIND: &'static str;
+
+    async fn ids(&self) -> anyhow::Result<Vec<ES::Id>>;
+
+    /// Empty if the stream does not exist
+    async fn events(&self, id: ES::Id) -> anyhow::Result<Vec<BaseEvent<ES>>>;
+
+    async fn replay(
+        &self,
+        current: Option<Version>,
+        events: &[BaseEvent<ES>],
+    ) -> anyhow::Result<()>;
+}
+
+#[async_trait::async_trait]
+impl Streams<ChartEventStream> for dyn ChartRepository + Send + Sync {
+    const KIND: &'static str = "chart";
+
+    async fn ids(&self) -> anyhow::Result<Vec<ChartId>> {
+        Ok(self.find_ids().await?)
+    }
+
+    async fn events(&self, id: ChartId) -> anyhow::Result<Vec<ChartEvent>> {
+        Ok(self.find_events(id).await?)
+    }
+
+    async fn replay(&self, current: Option<Version>, events: &[ChartEvent]) -> anyhow::Result<()> {
+        replay_chart_events(self, current, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Streams<DataPointEventStream> for dyn DataPointRepository + Send + Sync {
+    const KIND: &'static str = "data point";
+
+    async fn ids(&self) -> anyhow::Result<Vec<DataPointId>> {
+        Ok(self.find_ids().await?)
+    }
+
+    async fn events(&self, id: DataPointId) -> anyhow::Result<Vec<DataPointEvent>> {
+        Ok(self.find_events(id).await?)
+    }
+
+    async fn replay(
+        &self,
+        current: Option<Version>,
+        events: &[DataPointEvent],
+    ) -> anyhow::Result<()> {
+        replay_data_point_events(self, current, events).await
+    }
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    appended: usize,
+    created: usize,
+    events: usize,
+    up_to_date: usize,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} to create, {} to resume, {} up to date ({} events to copy)",
+            self.created, self.appended, self.up_to_date, self.events
+        )
+    }
+}
+
+/// Plans every source stream, reading one stream at a time from each store.
+/// Fails if any stream has diverged, before anything is written
+async fn plan_all<ES, S>(ids: &[ES::Id], source: &S, target: &S) -> anyhow::Result<Summary>
+where
+    ES: EventStream,
+    ES::Id: Copy + Display + Send,
+    BaseEvent<ES>: PartialEq,
+    S: Streams<ES> + ?Sized,
+{
+    let mut summary = Summary::default();
+    let mut diverged = vec![];
+    for stream_id in ids {
+        let events = source
+            .events(*stream_id)
+            .await
+            .with_context(|| format!("find source {} {}", S::KIND, stream_id))?;
+        let target_events = target
+            .events(*stream_id)
+            .await
+            .with_context(|| format!("find target {} {}", S::KIND, stream_id))?;
+        match plan(&events, existing(&target_events)) {
+            Step::Create => {
+                summary.created += 1;
+                summary.events += events.len();
+            }
+            Step::Append(_, skip) => {
+                summary.appended += 1;
+                summary.events += events.len() - skip;
+            }
+            Step::UpToDate => summary.up_to_date += 1,
+            Step::Diverged => diverged.push(stream_id.to_string()),
+        }
+    }
+    if !diverged.is_empty() {
+        anyhow::bail!(
+            "the target has other events for {} {}s: {}",
+            diverged.len(),
+            S::KIND,
+            diverged.join(", ")
+        );
+    }
+    Ok(summary)
+}
+
+/// Copies every source stream, one stream at a time, and compares the event count and
+/// the last version of each stream with the target once it has been written.
+/// Returns the mismatches and the number of source events
+async fn copy_all<ES, S>(
+    ids: &[ES::Id],
+    source: &S,
+    target: &S,
+) -> anyhow::Result<(Vec<String>, usize)>
+where
+    ES: EventStream,
+    ES::Id: Copy + Display + Send,
+    BaseEvent<ES>: PartialEq,
+    S: Streams<ES> + ?Sized,
+{
+    let summarize = |events: &[BaseEvent<ES>]| (events.len(), events.last().map(|e| e.version));
+    let mut mismatches = vec![];
+    let mut count = 0;
+    for stream_id in ids {
+        let events = source
+            .events(*stream_id)
+            .await
+            .with_context(|| format!("find source {} {}", S::KIND, stream_id))?;
+        let target_events = target
+            .events(*stream_id)
+            .await
+            .with_context(|| format!("find target {} {}", S::KIND, stream_id))?;
+        // planned again, the target may have been written since `plan_all`
+        let result = match plan(&events, existing(&target_events)) {
+            Step::Create => target.replay(None, &events).await,
+            Step::Append(current, skip) => target.replay(Some(current), &events[skip..]).await,
+            Step::UpToDate => Ok(()),
+            Step::Diverged => {
+                anyhow::bail!("the target has other events for {} {}", S::KIND, stream_id)
+            }
+        };
+        result.with_context(|| format!("store {} {}", S::KIND, stream_id))?;
+
+        let expected = summarize(&events);
+        let actual = summarize(
+            &target
+                .events(*stream_id)
+                .await
+                .with_context(|| format!("find target {} {}", S::KIND, stream_id))?,
+        );
+        if expected != actual {
+            mismatches.push(format!(
+                "{} {}: expected {} events (version {:?}), found {} events (version {:?})",
+                S::KIND,
+                stream_id,
+                expected.0,
+                expected.1.map(u32::from),
+                actual.0,
+                actual.1.map(u32::from)
+            ));
+        }
+        count += events.len();
+    }
+    Ok((mismatches, count))
+}
+
+/// The events of the target stream, `None` if it does not exist
+fn existing<ES: EventStream>(events: &[BaseEvent<ES>]) -> Option<&[BaseEvent<ES>]> {
+    (!events.is_empty()).then_some(events)
+}
+
+pub async fn run(Args { dry_run, from, to }: Args) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        from.canonicalize() != to.canonicalize(),
+        "--from and --to must be different stores"
+    );
+    let source = from.clone().build_app().await?;
+    let target = to.clone().build_app().await?;
+    migrate(&source, &target, dry_run).await?;
+    if dry_run {
+        return Ok(());
+    }
+    eprintln!("migrated {} to {}", from, to);
+    if to == Spec::Firestore {
+        rebuild_projections::rebuild(None).await?;
+    }
+    Ok(())
+}
+
+/// Plans every stream and, unless `dry_run`, copies and verifies them.
+/// Returns the chart plan and the data point plan
+async fn migrate(
+    source: &server::App,
+    target: &server::App,
+    dry_run: bool,
+) -> anyhow::Result<(Summary, Summary)> {
+    let (source_charts, target_charts) = (source.chart_repository(), target.chart_repository());
+    let (source_data_points, target_data_points) = (
+        source.data_point_repository(),
+        target.data_point_repository(),
+    );
+    let chart_ids = source_charts.ids().await.context("find source chart ids")?;
+    let data_point_ids = source_data_points
+        .ids()
+        .await
+        .context("find source data point ids")?;
+
+    let chart_summary =
+        plan_all(&chart_ids, source_charts.as_ref(), target_charts.as_ref()).await?;
+    let data_point_summary = plan_all(
+        &data_point_ids,
+        source_data_points.as_ref(),
+        target_data_points.as_ref(),
+    )
+    .await?;
+    eprintln!("charts: {}", chart_summary);
+    eprintln!("data points: {}", data_point_summary);
+    if dry_run {
+        return Ok((chart_summary, data_point_summary));
+    }
+
+    let (chart_mismatches, chart_events) =
+        copy_all(&chart_ids, source_charts.as_ref(), target_charts.as_ref()).await?;
+    let (data_point_mismatches, data_point_events) = copy_all(
+        &data_point_ids,
+        source_data_points.as_ref(),
+        target_data_points.as_ref(),
+    )
+    .await?;
+    let mismatches = chart_mismatches
+        .into_iter()
+        .chain(data_point_mismatches)
+        .collect::<Vec<String>>();
+    if !mismatches.is_empty() {
+        anyhow::bail!("verification failed:\n{}", mismatches.join("\n"));
+    }
+    eprintln!(
+        "verified {} charts ({} events) and {} data points ({} events)",
+        chart_ids.len(),
+        chart_events,
+        data_point_ids.len(),
+        data_point_events
+    );
+    Ok((chart_summary, data_point_summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, str::FromStr as _};
+
+    use write_model::{
+        aggregate::{Chart, DataPoint},
+        event::ChartEvent,
+        value_object::{XValue, YValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_plan() -> anyhow::Result<()> {
+        let (chart, created) = Chart::create("title1".to_owned())?;
+        let (chart, updated) = chart.update("title2".to_owned())?;
+        let events = created
+            .into_iter()
+            .chain(updated)
+            .collect::<Vec<ChartEvent>>();
+        assert_eq!(plan(&events, None), Step::Create);
+        assert_eq!(plan(&events, Some(&events)), Step::UpToDate);
+        assert_eq!(
+            plan(&events, Some(&events[..1])),
+            Step::Append(events[0].version, 1)
+        );
+        let (_, mut other) = Chart::create("title1".to_owned())?;
+        other[0].stream_id = chart.id();
+        assert_eq!(plan(&events, Some(&other)), Step::Diverged);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate() -> anyhow::Result<()> {
+        let (chart1, chart1_created) = Chart::create("title1".to_owned())?;
+        let (chart1, chart1_updated) = chart1.update("title2".to_owned())?;
+        let (chart2, chart2_created) = Chart::create("title3".to_owned())?;
+        let (chart2, series_created) = chart2.create_series("series1".to_owned())?;
+        let (data_point1, data_point1_created) = DataPoint::create(
+            chart1.id(),
+            None,
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("1")?,
+        )?;
+        let (_, data_point1_updated) = data_point1.update(YValue::from_str("2")?)?;
+        let (_, data_point2_created) = DataPoint::create(
+            chart2.id(),
+            Some(chart2.series()[0].id()),
+            XValue::from_str("2020-01-03")?,
+            YValue::from_str("3")?,
+        )?;
+        let source = Spec::Memory.build_app().await?;
+        for events in [
+            [chart1_created.clone(), chart1_updated].concat(),
+            [chart2_created.clone(), series_created].concat(),
+        ] {
+            source.chart_repository().store(None, &events).await?;
+        }
+        for events in [
+            [data_point1_created.clone(), data_point1_updated].concat(),
+            data_point2_created,
+        ] {
+            source.data_point_repository().store(None, &events).await?;
+        }
+
+        // interrupted after the first chart, the first event of the second chart and
+        // the first event of the first data point
+        let dir = std::env::temp_dir().join("date_line_charts_test_migrate");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        let target = Spec::Fs(dir.clone()).build_app().await?;
+        target
+            .chart_repository()
+            .store(
+                None,
+                &source.chart_repository().find_events(chart1.id()).await?,
+            )
+            .await?;
+        target
+            .chart_repository()
+            .store(None, &chart2_created)
+            .await?;
+        target
+            .data_point_repository()
+            .store(None, &data_point1_created)
+            .await?;
+
+        let (charts, data_points) = migrate(&source, &target, false).await?;
+        assert_eq!(
+            (
+                charts.created,
+                charts.appended,
+                charts.up_to_date,
+                charts.events
+            ),
+            (0, 1, 1, 1)
+        );
+        assert_eq!(
+            (
+                data_points.created,
+                data_points.appended,
+                data_points.up_to_date,
+                data_points.events
+            ),
+            (1, 1, 0, 2)
+        );
+        // reloaded from the files, each event is stored once
+        let target = Spec::Fs(dir.clone()).build_app().await?;
+        assert_eq!(
+            target.chart_repository().find_all_events().await?,
+            source.chart_repository().find_all_events().await?
+        );
+        assert_eq!(
+            target.data_point_repository().find_all_events().await?,
+            source.data_point_repository().find_all_events().await?
+        );
+        let line_count = |path: &Path| -> anyhow::Result<usize> {
+            Ok(std::fs::read_to_string(path)?.lines().count())
+        };
+        assert_eq!(line_count(&dir.join("charts.jsonl"))?, 4);
+        assert_eq!(line_count(&dir.join("data_points.jsonl"))?, 3);
+
+        let (charts, data_points) = migrate(&source, &target, false).await?;
+        assert_eq!((charts.up_to_date, charts.events), (2, 0));
+        assert_eq!((data_points.up_to_date, data_points.events), (2, 0));
+        assert_eq!(line_count(&dir.join("charts.jsonl"))?, 4);
+
+        // the target has another first event for the first chart
+        let dir = std::env::temp_dir().join("date_line_charts_test_migrate_diverged");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        let target = Spec::Fs(dir.clone()).build_app().await?;
+        let (_, mut other) = Chart::create("title1".to_owned())?;
+        other[0].stream_id = chart1.id();
+        target.chart_repository().store(None, &other).await?;
+        let error = migrate(&source, &target, false).await.unwrap_err();
+        assert!(error.to_string().contains(&chart1.id().to_string()));
+        // nothing is written
+        assert_eq!(line_count(&dir.join("charts.jsonl"))?, 1);
+        assert!(!dir.join("data_points.jsonl").exists());
+        // diverged after `plan_all`
+        let (source_charts, target_charts) = (source.chart_repository(), target.chart_repository());
+        assert!(copy_all(
+            &[chart1.id()],
+            source_charts.as_ref(),
+            target_charts.as_ref()
+        )
+        .await
+        .is_err());
+        assert_eq!(line_count(&dir.join("charts.jsonl"))?, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use firestore_store::{FirestoreQueryDataRebuilder, RebuildProgress};
+
+use crate::store::{self, Spec};
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+    /// Build into this root collection (or back into `charts` if it is in use) and switch the
+    /// readers to it when complete
+    #[arg(long)]
+    shadow_collection: Option<String>,
+}
+
+pub async fn run(
+    Args {
+        shadow_collection,
+        store,
+    }: Args,
+) -> anyhow::Result<()> {
+    match Spec::try_from(store)? {
+        Spec::Firestore => {}
+        spec @ (Spec::Memory | Spec::Fs(_)) => {
+            eprintln!(
+                "the {} store builds its query data from the events when loaded. nothing to rebuild",
+                spec
+            );
+            return Ok(());
+        }
+        spec @ (Spec::Sqlite(_) | Spec::Postgres(_)) => {
+            eprintln!(
+                "the {} store updates its query data in the same transaction as the events. nothing to rebuild",
+                spec
+            );
+            return Ok(());
+        }
+    }
+    rebuild(shadow_collection.as_deref()).await
+}
+
+/// Rebuilds the Firestore query data from the whole event log.
+///
+/// `restore` and `migrate` replay the events with their original `at`. The query updater only
+/// reads the events after the last one it processed, so it skips them; they are projected here
+pub(crate) async fn rebuild(shadow_collection: Option<&str>) -> anyhow::Result<()> {
+    let rebuilder = FirestoreQueryDataRebuilder::new()
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    rebuilder
+        .rebuild(shadow_collection, print_progress)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    Ok(())
+}
+
+pub(crate) fn print_progress(progress: RebuildProgress) {
+    match progress {
+        RebuildProgress::Projected {
+            charts,
+            data_points,
+            events,
+        } => eprintln!(
+            "projected {} events into {} charts and {} data points",
+            events, charts, data_points
+        ),
+        RebuildProgress::Deleted {
+            collection,
+            done,
+            total,
+        } => eprintln!("deleted {}/{} documents in {}", done, total, collection),
+        RebuildProgress::Written {
+            collection,
+            done,
+            total,
+        } => eprintln!("wrote {}/{} documents in {}", done, total, collection),
+        RebuildProgress::Swapped { collection } => {
+            eprintln!("switched the readers to {}", collection)
+        }
+        RebuildProgress::UpdaterReset => eprintln!("reset the query updater"),
+    }
+}
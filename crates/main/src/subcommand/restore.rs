@@ -0,0 +1,211 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+};
+
+use anyhow::Context as _;
+use command_use_case::port::{HasChartRepository as _, HasDataPointRepository as _};
+use write_model::{
+    aggregate::{Chart, DataPoint},
+    event::{ChartEvent, DataPointEvent, Event},
+    value_object::{ChartId, DataPointId},
+};
+
+use crate::{
+    archive::ArchiveEvent,
+    replay::{replay_chart_events, replay_data_point_events},
+    store::{self, Spec},
+    subcommand::rebuild_projections,
+};
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+    /// Archive file (JSON Lines) written by `backup`. `-` reads the standard input
+    #[arg(long)]
+    input: PathBuf,
+}
+
+pub async fn run(Args { input, store }: Args) -> anyhow::Result<()> {
+    let reader: Box<dyn Read> = if input.as_os_str() == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(std::fs::File::open(&input).with_context(|| format!("open {}", input.display()))?)
+    };
+    let (charts, data_points) = read_archive(reader)?;
+
+    let spec = Spec::try_from(store)?;
+    let firestore = spec == Spec::Firestore;
+    let app = spec.build_app().await?;
+    restore(&app, &charts, &data_points).await?;
+    eprintln!(
+        "restored {} charts ({} events) and {} data points ({} events)",
+        charts.len(),
+        charts.values().map(Vec::len).sum::<usize>(),
+        data_points.len(),
+        data_points.values().map(Vec::len).sum::<usize>()
+    );
+    if firestore {
+        rebuild_projections::rebuild(None).await?;
+    }
+    Ok(())
+}
+
+type Streams = (
+    BTreeMap<ChartId, Vec<ChartEvent>>,
+    BTreeMap<DataPointId, Vec<DataPointEvent>>,
+);
+
+/// Reads the events of an archive by stream. Fails on a broken archive
+fn read_archive(reader: impl Read) -> anyhow::Result<Streams> {
+    let mut charts = BTreeMap::<ChartId, Vec<ChartEvent>>::new();
+    let mut data_points = BTreeMap::<DataPointId, Vec<DataPointEvent>>::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str::<ArchiveEvent>(&line)
+            .map_err(anyhow::Error::from)
+            .and_then(|event| Event::try_from(event).map_err(|e| anyhow::anyhow!(e)))
+            .with_context(|| format!("invalid event at line {}", index + 1))?;
+        match event {
+            Event::Chart(event) => charts.entry(event.stream_id).or_default().push(event),
+            Event::DataPoint(event) => data_points.entry(event.stream_id).or_default().push(event),
+        }
+    }
+    // rejects a broken archive before writing anything
+    for (chart_id, events) in &charts {
+        Chart::from_events(events).with_context(|| format!("invalid chart {}", chart_id))?;
+    }
+    for (data_point_id, events) in &data_points {
+        DataPoint::from_events(events)
+            .with_context(|| format!("invalid data point {}", data_point_id))?;
+    }
+    Ok((charts, data_points))
+}
+
+/// Stores the events as they are. The streams must not exist in the store
+async fn restore(
+    app: &server::App,
+    charts: &BTreeMap<ChartId, Vec<ChartEvent>>,
+    data_points: &BTreeMap<DataPointId, Vec<DataPointEvent>>,
+) -> anyhow::Result<()> {
+    let chart_repository = app.chart_repository();
+    for (chart_id, events) in charts {
+        replay_chart_events(chart_repository.as_ref(), None, events)
+            .await
+            .with_context(|| format!("store chart {}", chart_id))?;
+    }
+    let data_point_repository = app.data_point_repository();
+    for (data_point_id, events) in data_points {
+        replay_data_point_events(data_point_repository.as_ref(), None, events)
+            .await
+            .with_context(|| format!("store data point {}", data_point_id))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use query_use_case::port::{
+        ChartQueryData, DataPointQueryData, HasChartReader as _, HasDataPointReader as _,
+        SeriesFilter, XValueRange,
+    };
+    use write_model::value_object::{XValue, YValue};
+
+    use crate::subcommand::backup::write_archive;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_round_trip() -> anyhow::Result<()> {
+        let (chart, chart_created) = Chart::create("title1".to_owned())?;
+        let (chart, series_created) = chart.create_series("series1".to_owned())?;
+        let series_id = chart.series()[0].id();
+        let (deleted_chart, deleted_chart_created) = Chart::create("title2".to_owned())?;
+        let (_, chart_deleted) = deleted_chart.delete()?;
+        let (data_point1, data_point1_created) = DataPoint::create(
+            chart.id(),
+            None,
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("1")?,
+        )?;
+        let (_, data_point1_updated) = data_point1.update(YValue::from_str("1.5")?)?;
+        let (_, data_point2_created) = DataPoint::create(
+            chart.id(),
+            Some(series_id),
+            XValue::from_str("2020-01-03")?,
+            YValue::from_str("2")?,
+        )?;
+        let (data_point3, data_point3_created) = DataPoint::create(
+            chart.id(),
+            None,
+            XValue::from_str("2020-01-04")?,
+            YValue::from_str("3")?,
+        )?;
+        let (_, data_point3_deleted) = data_point3.delete()?;
+
+        let source = Spec::Memory.build_app().await?;
+        for events in [
+            [chart_created, series_created].concat(),
+            [deleted_chart_created, chart_deleted].concat(),
+        ] {
+            source.chart_repository().store(None, &events).await?;
+        }
+        for events in [
+            [data_point1_created, data_point1_updated].concat(),
+            data_point2_created,
+            [data_point3_created, data_point3_deleted].concat(),
+        ] {
+            source.data_point_repository().store(None, &events).await?;
+        }
+        let mut archive = vec![];
+        assert_eq!(write_archive(&source, &mut archive).await?, (4, 5));
+
+        let dir = std::env::temp_dir().join("date_line_charts_test_restore");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        let (charts, data_points) = read_archive(archive.as_slice())?;
+        restore(
+            &Spec::Fs(dir.clone()).build_app().await?,
+            &charts,
+            &data_points,
+        )
+        .await?;
+
+        // reloaded from the files
+        let target = Spec::Fs(dir).build_app().await?;
+        let charts = [chart.id(), deleted_chart.id()];
+        let expected = query_data(&source, charts).await?;
+        assert_eq!(
+            expected.0[0].as_ref().map(|chart| chart.series.len()),
+            Some(1)
+        );
+        assert_eq!(expected.0[1], None);
+        assert_eq!(expected.1.len(), 2);
+        assert_eq!(query_data(&target, charts).await?, expected);
+        Ok(())
+    }
+
+    /// The charts and the data points of the first chart
+    async fn query_data(
+        app: &server::App,
+        chart_ids: [ChartId; 2],
+    ) -> anyhow::Result<(Vec<Option<ChartQueryData>>, Vec<DataPointQueryData>)> {
+        let mut charts = vec![];
+        for chart_id in chart_ids {
+            charts.push(app.chart_reader().get(chart_id).await?);
+        }
+        let data_points = app
+            .data_point_reader()
+            .list(chart_ids[0], SeriesFilter::All, XValueRange::default())
+            .await?;
+        Ok((charts, data_points))
+    }
+}
@@ -1,23 +1,12 @@
-use std::sync::Arc;
+use crate::store;
 
-use firestore_store::{FirestoreChartStore, FirestoreDataPointStore};
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+}
 
-pub async fn run() -> anyhow::Result<()> {
-    let chart_store = Arc::new(
-        FirestoreChartStore::new()
-            .await
-            .map_err(|e| anyhow::anyhow!(e))?,
-    );
-    let data_point_store = Arc::new(
-        FirestoreDataPointStore::new()
-            .await
-            .map_err(|e| anyhow::anyhow!(e))?,
-    );
-    let app = server::App::new(
-        chart_store.clone(),
-        chart_store,
-        data_point_store.clone(),
-        data_point_store,
-    );
+pub async fn run(Args { store }: Args) -> anyhow::Result<()> {
+    let app = store::build_app(store).await?;
     Ok(server::run(app).await?)
 }
@@ -0,0 +1,736 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Debug, Display},
+};
+
+use anyhow::Context as _;
+use command_use_case::port::{HasChartRepository as _, HasDataPointRepository as _};
+use file_system_store::{FileSystemChartStore, FileSystemDataPointStore, UnparsableLine};
+use firestore_store::FirestoreQueryDataRebuilder;
+use query_use_case::{
+    port::{
+        ChartListQuery, ChartQueryData, ChartSortKey, DataPointQueryData, HasChartReader as _,
+        HasDataPointReader as _, SeriesFilter, SortOrder, XValueRange,
+    },
+    projection,
+};
+use write_model::{
+    aggregate::{Chart, DataPoint},
+    event::{
+        BaseEvent, ChartEvent, ChartEventData, DataPointEvent, DataPointEventData, EventStream,
+    },
+    value_object::{ChartId, DataPointId},
+};
+
+use crate::{
+    replay::group_by_stream,
+    store::{self, Spec},
+    subcommand::rebuild_projections::print_progress,
+};
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    store: store::Args,
+    /// Rebuild what can be rebuilt: drop the unparsable lines of the `fs` store (with
+    /// `--drop-unparsable`) and rebuild the Firestore query data. Broken streams are only reported
+    #[arg(long)]
+    repair: bool,
+    /// With `--repair`, drop the unparsable trailing lines of the `fs` store (keeping a `.bak`
+    /// copy). Refused if an unparsable line is followed by a parsable one
+    #[arg(long, requires = "repair")]
+    drop_unparsable: bool,
+}
+
+/// A problem found in the store
+#[derive(Debug, Eq, PartialEq)]
+enum Issue {
+    /// A line of a JSON Lines file of the `fs` store cannot be read as an event
+    UnparsableLine {
+        file: &'static str,
+        line: usize,
+        message: String,
+    },
+    /// The versions of the stream are not 1, 2, 3, ...
+    VersionGap {
+        stream_id: String,
+        expected: u32,
+        actual: u32,
+    },
+    NoCreatedEvent {
+        stream_id: String,
+    },
+    CreatedAfterDeleted {
+        stream_id: String,
+        version: u32,
+    },
+    /// The stream cannot be replayed for another reason
+    InvalidStream {
+        stream_id: String,
+        message: String,
+    },
+    DuplicateEventId {
+        event_id: String,
+        stream_ids: Vec<String>,
+    },
+    /// A data point (not deleted) whose chart has no events
+    MissingChart {
+        data_point_id: String,
+    },
+    /// A data point (not deleted) whose chart is deleted. Reported only: `DeleteChart` keeps the
+    /// data points, hidden like those of a deleted series
+    DeletedChart {
+        data_point_id: String,
+    },
+    /// The read model cannot be read (e.g. the `fs` store cannot build its cache)
+    ReadModelUnavailable {
+        message: String,
+    },
+    /// The read model differs from the projection of the events
+    ReadModelMismatch {
+        id: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Issue {
+    /// Whether the issue fails the verification
+    fn is_failure(&self) -> bool {
+        !matches!(self, Self::DeletedChart { .. })
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnparsableLine {
+                file,
+                line,
+                message,
+            } => write!(f, "{}:{}: unparsable line ({})", file, line, message),
+            Self::VersionGap {
+                stream_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: versions are not contiguous (expected = {}, actual = {})",
+                stream_id, expected, actual
+            ),
+            Self::NoCreatedEvent { stream_id } => {
+                write!(f, "{}: the first event is not created", stream_id)
+            }
+            Self::CreatedAfterDeleted { stream_id, version } => write!(
+                f,
+                "{}: created after deleted (version = {})",
+                stream_id, version
+            ),
+            Self::InvalidStream { stream_id, message } => {
+                write!(f, "{}: cannot be replayed ({})", stream_id, message)
+            }
+            Self::DuplicateEventId {
+                event_id,
+                stream_ids,
+            } => write!(
+                f,
+                "event {}: duplicate event id (stream_ids = {})",
+                event_id,
+                stream_ids.join(", ")
+            ),
+            Self::MissingChart { data_point_id } => {
+                write!(f, "{}: the chart does not exist", data_point_id)
+            }
+            Self::DeletedChart { data_point_id } => {
+                write!(f, "{}: the chart is deleted (kept)", data_point_id)
+            }
+            Self::ReadModelUnavailable { message } => {
+                write!(f, "the read model cannot be read ({})", message)
+            }
+            Self::ReadModelMismatch {
+                id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: the read model differs from the events (expected = {}, actual = {})",
+                id, expected, actual
+            ),
+        }
+    }
+}
+
+pub async fn run(
+    Args {
+        drop_unparsable,
+        repair,
+        store,
+    }: Args,
+) -> anyhow::Result<()> {
+    verify(&Spec::try_from(store)?, repair, drop_unparsable).await
+}
+
+async fn verify(spec: &Spec, repair: bool, drop_unparsable: bool) -> anyhow::Result<()> {
+    let mut issues = check(spec).await?;
+    print_issues(&issues);
+    if repair && issues.iter().any(Issue::is_failure) {
+        if self::repair(spec, &issues, drop_unparsable).await? {
+            issues = check(spec).await?;
+            eprintln!("after repair:");
+            print_issues(&issues);
+        } else {
+            eprintln!("nothing can be repaired");
+        }
+    }
+    let failures = issues.iter().filter(|issue| issue.is_failure()).count();
+    if failures > 0 {
+        anyhow::bail!("{} issues found", failures);
+    }
+    eprintln!("no issues found");
+    Ok(())
+}
+
+fn print_issues(issues: &[Issue]) {
+    for issue in issues {
+        match issue.is_failure() {
+            true => println!("{}", issue),
+            false => println!("note: {}", issue),
+        }
+    }
+}
+
+async fn check(spec: &Spec) -> anyhow::Result<Vec<Issue>> {
+    let app = spec.clone().build_app().await?;
+    let mut issues = vec![];
+    let (chart_events, data_point_events) = match spec {
+        // scans the files because the repositories fail on the first unparsable line
+        Spec::Fs(dir) => {
+            let charts = FileSystemChartStore::new(dir.clone())
+                .scan()
+                .context("scan charts.jsonl")?;
+            let data_points = FileSystemDataPointStore::new(dir.clone())
+                .scan()
+                .context("scan data_points.jsonl")?;
+            issues.extend(unparsable_line_issues(
+                "charts.jsonl",
+                charts.unparsable_lines,
+            ));
+            issues.extend(unparsable_line_issues(
+                "data_points.jsonl",
+                data_points.unparsable_lines,
+            ));
+            (charts.events, data_points.events)
+        }
+        Spec::Memory | Spec::Sqlite(_) | Spec::Postgres(_) | Spec::Firestore => (
+            app.chart_repository()
+                .find_all_events()
+                .await
+                .context("find chart events")?,
+            app.data_point_repository()
+                .find_all_events()
+                .await
+                .context("find data point events")?,
+        ),
+    };
+    let charts = group_by_stream(chart_events);
+    let data_points = group_by_stream(data_point_events);
+    issues.extend(check_events(&charts, &data_points));
+    match check_read_models(&app, &charts, &data_points).await {
+        Ok(read_model_issues) => issues.extend(read_model_issues),
+        Err(e) => issues.push(Issue::ReadModelUnavailable {
+            message: format!("{:#}", e),
+        }),
+    }
+    Ok(issues)
+}
+
+/// Returns `true` if something was repaired
+async fn repair(spec: &Spec, issues: &[Issue], drop_unparsable: bool) -> anyhow::Result<bool> {
+    match spec {
+        Spec::Fs(dir) => {
+            if !issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::UnparsableLine { .. }))
+            {
+                return Ok(false);
+            }
+            anyhow::ensure!(
+                drop_unparsable,
+                "repairing drops the unparsable lines. run again with --drop-unparsable"
+            );
+            let dropped = FileSystemChartStore::new(dir.clone())
+                .drop_trailing_unparsable_lines()
+                .await
+                .context("drop unparsable lines of charts.jsonl")?
+                + FileSystemDataPointStore::new(dir.clone())
+                    .drop_trailing_unparsable_lines()
+                    .await
+                    .context("drop unparsable lines of data_points.jsonl")?;
+            eprintln!("dropped {} unparsable lines", dropped);
+            Ok(true)
+        }
+        Spec::Firestore => {
+            if !issues.iter().any(|issue| {
+                matches!(
+                    issue,
+                    Issue::ReadModelMismatch { .. } | Issue::ReadModelUnavailable { .. }
+                )
+            }) {
+                return Ok(false);
+            }
+            FirestoreQueryDataRebuilder::new()
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?
+                .rebuild(None, print_progress)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))
+                .context("rebuild the query data")?;
+            Ok(true)
+        }
+        Spec::Memory | Spec::Sqlite(_) | Spec::Postgres(_) => Ok(false),
+    }
+}
+
+fn unparsable_line_issues(
+    file: &'static str,
+    unparsable_lines: Vec<UnparsableLine>,
+) -> impl Iterator<Item = Issue> {
+    unparsable_lines
+        .into_iter()
+        .map(
+            move |UnparsableLine { line, message }| Issue::UnparsableLine {
+                file,
+                line,
+                message,
+            },
+        )
+}
+
+enum EventKind {
+    Created,
+    Deleted,
+    Other,
+}
+
+fn chart_event_kind(data: &ChartEventData) -> EventKind {
+    match data {
+        ChartEventData::Created(_) => EventKind::Created,
+        ChartEventData::Deleted(_) => EventKind::Deleted,
+        _ => EventKind::Other,
+    }
+}
+
+fn data_point_event_kind(data: &DataPointEventData) -> EventKind {
+    match data {
+        DataPointEventData::Created(_) => EventKind::Created,
+        DataPointEventData::Deleted(_) => EventKind::Deleted,
+        DataPointEventData::Updated(_) => EventKind::Other,
+    }
+}
+
+/// Checks the events of a stream in the stored order. `replay` reports the other problems
+fn check_stream<ES: EventStream>(
+    stream_id: &ES::Id,
+    events: &[BaseEvent<ES>],
+    kind: impl Fn(&ES::Data) -> EventKind,
+    replay: impl Fn(&[BaseEvent<ES>]) -> Option<String>,
+) -> Vec<Issue>
+where
+    ES::Id: Display,
+{
+    let mut issues = vec![];
+    for (expected, event) in (1..).zip(events) {
+        let actual = u32::from(event.version);
+        if actual != expected {
+            issues.push(Issue::VersionGap {
+                stream_id: stream_id.to_string(),
+                expected,
+                actual,
+            });
+            break;
+        }
+    }
+    if !events
+        .first()
+        .is_some_and(|event| matches!(kind(&event.data), EventKind::Created))
+    {
+        issues.push(Issue::NoCreatedEvent {
+            stream_id: stream_id.to_string(),
+        });
+    }
+    let mut deleted = false;
+    for event in events {
+        match kind(&event.data) {
+            EventKind::Created if deleted => issues.push(Issue::CreatedAfterDeleted {
+                stream_id: stream_id.to_string(),
+                version: u32::from(event.version),
+            }),
+            EventKind::Deleted => deleted = true,
+            EventKind::Created | EventKind::Other => {}
+        }
+    }
+    if issues.is_empty() {
+        if let Some(message) = replay(events) {
+            issues.push(Issue::InvalidStream {
+                stream_id: stream_id.to_string(),
+                message,
+            });
+        }
+    }
+    issues
+}
+
+fn check_events(
+    charts: &BTreeMap<ChartId, Vec<ChartEvent>>,
+    data_points: &BTreeMap<DataPointId, Vec<DataPointEvent>>,
+) -> Vec<Issue> {
+    let mut issues = vec![];
+    for (chart_id, events) in charts {
+        issues.extend(check_stream(chart_id, events, chart_event_kind, |events| {
+            Chart::from_events(events).err().map(|e| e.to_string())
+        }));
+    }
+    for (data_point_id, events) in data_points {
+        issues.extend(check_stream(
+            data_point_id,
+            events,
+            data_point_event_kind,
+            |events| DataPoint::from_events(events).err().map(|e| e.to_string()),
+        ));
+    }
+
+    let mut stream_ids_by_event_id = BTreeMap::<String, Vec<String>>::new();
+    for (event_id, stream_id) in charts
+        .values()
+        .flatten()
+        .map(|event| (event.id, event.stream_id.to_string()))
+        .chain(
+            data_points
+                .values()
+                .flatten()
+                .map(|event| (event.id, event.stream_id.to_string())),
+        )
+    {
+        stream_ids_by_event_id
+            .entry(event_id.to_string())
+            .or_default()
+            .push(stream_id);
+    }
+    issues.extend(
+        stream_ids_by_event_id
+            .into_iter()
+            .filter(|(_, stream_ids)| stream_ids.len() > 1)
+            .map(|(event_id, stream_ids)| Issue::DuplicateEventId {
+                event_id,
+                stream_ids,
+            }),
+    );
+
+    for (data_point_id, events) in data_points {
+        let Ok(data_point) = DataPoint::from_events(events) else {
+            continue;
+        };
+        if data_point.deleted_at().is_some() {
+            continue;
+        }
+        match charts.get(&data_point_id.chart_id()) {
+            None => issues.push(Issue::MissingChart {
+                data_point_id: data_point_id.to_string(),
+            }),
+            Some(events) => {
+                if Chart::from_events(events).is_ok_and(|chart| chart.deleted_at().is_some()) {
+                    issues.push(Issue::DeletedChart {
+                        data_point_id: data_point_id.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// The chart read model projected from a valid stream (`None` if deleted)
+fn expected_chart(events: &[ChartEvent]) -> Option<Option<ChartQueryData>> {
+    Chart::from_events(events).ok()?;
+    events.iter().try_fold(None, projection::project_chart).ok()
+}
+
+/// The data point read model projected from a valid stream (`None` if deleted)
+fn expected_data_point(events: &[DataPointEvent]) -> Option<Option<DataPointQueryData>> {
+    DataPoint::from_events(events).ok()?;
+    events
+        .iter()
+        .try_fold(None, projection::project_data_point)
+        .ok()
+}
+
+/// Compares the read model with the projection of the events. `expected` has the valid streams
+/// and `stream_ids` has every stream. The read model of an invalid stream is not compared
+fn compare<Id, T>(
+    stream_ids: &BTreeSet<Id>,
+    expected: &BTreeMap<Id, Option<T>>,
+    actual: &BTreeMap<Id, T>,
+) -> Vec<Issue>
+where
+    Id: Display + Ord,
+    T: Debug + PartialEq,
+{
+    let ids = expected
+        .keys()
+        .chain(actual.keys())
+        .collect::<BTreeSet<&Id>>();
+    let format =
+        |value: Option<&T>| value.map_or_else(|| "none".to_owned(), |v| format!("{:?}", v));
+    ids.into_iter()
+        .filter_map(|id| {
+            let expected = match expected.get(id) {
+                Some(expected) => expected.as_ref(),
+                None if stream_ids.contains(id) => return None,
+                None => None,
+            };
+            let actual = actual.get(id);
+            (expected != actual).then(|| Issue::ReadModelMismatch {
+                id: id.to_string(),
+                expected: format(expected),
+                actual: format(actual),
+            })
+        })
+        .collect()
+}
+
+async fn check_read_models(
+    app: &server::App,
+    charts: &BTreeMap<ChartId, Vec<ChartEvent>>,
+    data_points: &BTreeMap<DataPointId, Vec<DataPointEvent>>,
+) -> anyhow::Result<Vec<Issue>> {
+    let chart_reader = app.chart_reader();
+    let mut actual_charts = BTreeMap::new();
+    let mut query = ChartListQuery {
+        after: None,
+        limit: 100,
+        order: SortOrder::Ascending,
+        sort_key: ChartSortKey::CreatedAt,
+    };
+    loop {
+        let page = chart_reader
+            .list(query.clone())
+            .await
+            .context("list charts")?;
+        query.after = page.last().map(|chart| query.cursor(chart));
+        let is_end = page.len() < query.limit;
+        actual_charts.extend(page.into_iter().map(|chart| (chart.id, chart)));
+        if is_end {
+            break;
+        }
+    }
+
+    let data_point_reader = app.data_point_reader();
+    let mut actual_data_points = BTreeMap::new();
+    let chart_ids = charts
+        .keys()
+        .copied()
+        .chain(actual_charts.keys().copied())
+        .chain(data_points.keys().map(DataPointId::chart_id))
+        .collect::<BTreeSet<ChartId>>();
+    for chart_id in chart_ids {
+        let page = data_point_reader
+            .list(chart_id, SeriesFilter::All, XValueRange::default())
+            .await
+            .with_context(|| format!("list data points of chart {}", chart_id))?;
+        actual_data_points.extend(page.into_iter().map(|data_point| {
+            (
+                DataPointId::new(
+                    data_point.chart_id,
+                    data_point.series_id,
+                    data_point.x_value,
+                ),
+                data_point,
+            )
+        }));
+    }
+
+    let expected_charts = charts
+        .iter()
+        .filter_map(|(chart_id, events)| Some((*chart_id, expected_chart(events)?)))
+        .collect::<BTreeMap<ChartId, Option<ChartQueryData>>>();
+    let expected_data_points = data_points
+        .iter()
+        .filter_map(|(data_point_id, events)| Some((*data_point_id, expected_data_point(events)?)))
+        .collect::<BTreeMap<DataPointId, Option<DataPointQueryData>>>();
+    Ok(compare(
+        &charts.keys().copied().collect(),
+        &expected_charts,
+        &actual_charts,
+    )
+    .into_iter()
+    .chain(compare(
+        &data_points.keys().copied().collect(),
+        &expected_data_points,
+        &actual_data_points,
+    ))
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use command_use_case::{
+        create_chart::HasCreateChart as _, create_data_point::HasCreateDataPoint as _,
+        delete_chart::HasDeleteChart as _,
+    };
+    use write_model::{
+        event::{DataPointCreated, DataPointEventData},
+        value_object::{EventId, Version, XValue, YValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_check_events() -> anyhow::Result<()> {
+        let (chart, chart_created) = Chart::create("title1".to_owned())?;
+        let (chart, chart_updated) = chart.update("title2".to_owned())?;
+        let (data_point, data_point_created) = DataPoint::create(
+            chart.id(),
+            None,
+            XValue::from_str("2020-01-02")?,
+            YValue::from_str("1")?,
+        )?;
+        let charts = group_by_stream([chart_created.clone(), chart_updated.clone()].concat());
+        let data_points = group_by_stream(data_point_created.clone());
+        assert_eq!(check_events(&charts, &data_points), vec![]);
+
+        // the first event is lost
+        let charts = group_by_stream(chart_updated.clone());
+        assert_eq!(
+            check_events(&charts, &BTreeMap::new()),
+            vec![
+                Issue::VersionGap {
+                    stream_id: chart.id().to_string(),
+                    expected: 1,
+                    actual: 2,
+                },
+                Issue::NoCreatedEvent {
+                    stream_id: chart.id().to_string(),
+                },
+            ]
+        );
+
+        // the chart does not exist
+        assert_eq!(
+            check_events(&BTreeMap::new(), &data_points),
+            vec![Issue::MissingChart {
+                data_point_id: data_point.id().to_string(),
+            }]
+        );
+
+        // the chart is deleted
+        let (_, chart_deleted) = chart.delete()?;
+        let charts = group_by_stream([chart_created, chart_updated, chart_deleted].concat());
+        assert_eq!(
+            check_events(&charts, &data_points),
+            vec![Issue::DeletedChart {
+                data_point_id: data_point.id().to_string(),
+            }]
+        );
+
+        // created after deleted, with the id of another event
+        let (data_point, data_point_deleted) = data_point.delete()?;
+        let recreated = DataPointEvent {
+            at: data_point_created[0].at,
+            data: DataPointEventData::Created(DataPointCreated {
+                value: YValue::from_str("2")?,
+            }),
+            id: data_point_created[0].id,
+            stream_id: data_point.id(),
+            version: Version::try_from(3_u32)?,
+        };
+        let data_points = group_by_stream(
+            [
+                data_point_created.clone(),
+                data_point_deleted,
+                vec![recreated],
+            ]
+            .concat(),
+        );
+        assert_eq!(
+            check_events(&charts, &data_points),
+            vec![
+                Issue::CreatedAfterDeleted {
+                    stream_id: data_point.id().to_string(),
+                    version: 3,
+                },
+                Issue::DuplicateEventId {
+                    event_id: data_point_created[0].id.to_string(),
+                    stream_ids: vec![data_point.id().to_string(); 2],
+                },
+            ]
+        );
+        assert_ne!(data_point_created[0].id, EventId::generate());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare() {
+        let stream_ids = BTreeSet::from([1, 2, 3, 4]);
+        let expected = BTreeMap::from([(1, Some("a")), (2, None), (3, Some("c"))]);
+        let actual = BTreeMap::from([(1, "a"), (2, "b"), (4, "d"), (5, "e")]);
+        assert_eq!(
+            compare(&stream_ids, &expected, &actual),
+            vec![
+                Issue::ReadModelMismatch {
+                    id: "2".to_owned(),
+                    expected: "none".to_owned(),
+                    actual: "\"b\"".to_owned(),
+                },
+                Issue::ReadModelMismatch {
+                    id: "3".to_owned(),
+                    expected: "\"c\"".to_owned(),
+                    actual: "none".to_owned(),
+                },
+                Issue::ReadModelMismatch {
+                    id: "5".to_owned(),
+                    expected: "none".to_owned(),
+                    actual: "\"e\"".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_deleted_chart() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("date_line_charts_test_verify_deleted_chart");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        let spec = Spec::Fs(dir);
+        let app = spec.clone().build_app().await?;
+        let chart_id = app
+            .create_chart()
+            .execute(command_use_case::create_chart::Input {
+                title: "title1".to_owned(),
+            })
+            .await?
+            .chart_id;
+        app.create_data_point()
+            .execute(command_use_case::create_data_point::Input {
+                chart_id: chart_id.clone(),
+                series_id: None,
+                x_value: "2020-01-02".to_owned(),
+                y_value: "1".to_owned(),
+            })
+            .await?;
+        app.delete_chart()
+            .execute(command_use_case::delete_chart::Input {
+                chart_id,
+                expected_version: None,
+            })
+            .await?;
+
+        let issues = check(&spec).await?;
+        assert!(matches!(issues.as_slice(), [Issue::DeletedChart { .. }]));
+        assert!(!issues[0].is_failure());
+        verify(&spec, false, false).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod postgres_store;
+
+pub use self::postgres_store::*;
@@ -0,0 +1,10 @@
+mod error;
+mod event_table;
+mod migration;
+mod postgres_chart_store;
+mod postgres_data_point_store;
+mod postgres_pool;
+
+pub use self::postgres_chart_store::*;
+pub use self::postgres_data_point_store::*;
+pub use self::postgres_pool::*;
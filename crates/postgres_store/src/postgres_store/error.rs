@@ -0,0 +1,46 @@
+use store_error::{BoxError, ErrorKind};
+
+fn error_kind(error: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    ErrorKind::of(error, |error| {
+        if error.is::<deadpool_postgres::PoolError>() {
+            return Some(ErrorKind::Unavailable);
+        }
+        let error = error.downcast_ref::<tokio_postgres::Error>()?;
+        match error.code() {
+            // a concurrent writer stored the same version first
+            Some(&tokio_postgres::error::SqlState::UNIQUE_VIOLATION) => Some(ErrorKind::Conflict),
+            // connection exceptions
+            Some(code) if code.code().starts_with("08") => Some(ErrorKind::Unavailable),
+            Some(_) => None,
+            None if error.is_closed()
+                || std::error::Error::source(error)
+                    .is_some_and(|source| source.is::<std::io::Error>()) =>
+            {
+                Some(ErrorKind::Unavailable)
+            }
+            None => None,
+        }
+    })
+}
+
+pub(crate) fn chart_repository_error(
+    error: BoxError,
+) -> command_use_case::port::chart_repository::Error {
+    command_use_case::port::chart_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_repository_error(
+    error: BoxError,
+) -> command_use_case::port::data_point_repository::Error {
+    command_use_case::port::data_point_repository::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn chart_reader_error(error: BoxError) -> query_use_case::port::chart_reader::Error {
+    query_use_case::port::chart_reader::Error::new(error_kind(&*error), error)
+}
+
+pub(crate) fn data_point_reader_error(
+    error: BoxError,
+) -> query_use_case::port::data_point_reader::Error {
+    query_use_case::port::data_point_reader::Error::new(error_kind(&*error), error)
+}
@@ -0,0 +1,137 @@
+use deadpool_postgres::GenericClient;
+use store_error::{BoxError, CorruptedError};
+use tokio_postgres::Row;
+use write_model::value_object::Version;
+
+/// The value of the `stream_type` column
+#[derive(Clone, Copy)]
+pub(crate) enum StreamType {
+    Chart,
+    DataPoint,
+}
+
+impl StreamType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Chart => "chart",
+            Self::DataPoint => "data_point",
+        }
+    }
+}
+
+/// A row of the `events` table. `data` is the event data in JSON
+pub(crate) struct EventRow {
+    pub(crate) at: String,
+    pub(crate) data: String,
+    pub(crate) id: String,
+    pub(crate) stream_id: String,
+    pub(crate) version: i64,
+}
+
+impl EventRow {
+    fn from_row(row: &Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self {
+            at: row.try_get("at")?,
+            data: row.try_get("data")?,
+            id: row.try_get("id")?,
+            stream_id: row.try_get("stream_id")?,
+            version: row.try_get("version")?,
+        })
+    }
+}
+
+const EVENT_COLUMNS: &str = "at, data::TEXT AS data, id, stream_id, version";
+
+/// Returns the version of the last event of the stream (`None` if the stream does not exist)
+pub(crate) async fn last_version<C: GenericClient>(
+    client: &C,
+    stream_id: &str,
+) -> Result<Option<Version>, BoxError> {
+    let version = client
+        .query_one(
+            "SELECT MAX(version) FROM events WHERE stream_id = $1",
+            &[&stream_id],
+        )
+        .await?
+        .try_get::<_, Option<i64>>(0)?;
+    version
+        .map(Version::try_from)
+        .transpose()
+        .map_err(|e| CorruptedError(e.into()).into())
+}
+
+/// Inserts the event. A concurrent insert of the same version fails with a unique violation
+pub(crate) async fn insert<C: GenericClient>(
+    client: &C,
+    stream_type: StreamType,
+    event_row: &EventRow,
+) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute(
+            "INSERT INTO events (stream_id, version, at, data, id, stream_type) VALUES ($1, $2, $3, $4::TEXT::JSONB, $5, $6)",
+            &[
+                &event_row.stream_id,
+                &event_row.version,
+                &event_row.at,
+                &event_row.data,
+                &event_row.id,
+                &stream_type.as_str(),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Returns the events of the stream in version order
+pub(crate) async fn select_by_stream_id<C: GenericClient>(
+    client: &C,
+    stream_id: &str,
+) -> Result<Vec<EventRow>, tokio_postgres::Error> {
+    client
+        .query(
+            &format!(
+                "SELECT {} FROM events WHERE stream_id = $1 ORDER BY version",
+                EVENT_COLUMNS
+            ),
+            &[&stream_id],
+        )
+        .await?
+        .iter()
+        .map(EventRow::from_row)
+        .collect()
+}
+
+/// Returns the id of every stream of the type. The order is not specified
+pub(crate) async fn select_stream_ids<C: GenericClient>(
+    client: &C,
+    stream_type: StreamType,
+) -> Result<Vec<String>, tokio_postgres::Error> {
+    client
+        .query(
+            "SELECT DISTINCT stream_id FROM events WHERE stream_type = $1",
+            &[&stream_type.as_str()],
+        )
+        .await?
+        .iter()
+        .map(|row| row.try_get(0))
+        .collect()
+}
+
+/// Returns the events of every stream of the type. The order is not specified
+pub(crate) async fn select_by_stream_type<C: GenericClient>(
+    client: &C,
+    stream_type: StreamType,
+) -> Result<Vec<EventRow>, tokio_postgres::Error> {
+    client
+        .query(
+            &format!(
+                "SELECT {} FROM events WHERE stream_type = $1",
+                EVENT_COLUMNS
+            ),
+            &[&stream_type.as_str()],
+        )
+        .await?
+        .iter()
+        .map(EventRow::from_row)
+        .collect()
+}
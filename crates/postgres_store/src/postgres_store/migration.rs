@@ -0,0 +1,108 @@
+use deadpool_postgres::{Client, GenericClient as _};
+use store_error::BoxError;
+
+/// The schema changes, applied in order and recorded in `schema_migrations`.
+/// Append new migrations; never edit the applied ones.
+/// The text columns that are sorted or compared use the "C" collation (byte order) to match
+/// the string order of `ChartSortKey::value` and `XValue`
+const MIGRATIONS: &[&str] = &[r#"
+CREATE TABLE events (
+    stream_id TEXT NOT NULL,
+    version BIGINT NOT NULL,
+    at TEXT NOT NULL,
+    data JSONB NOT NULL,
+    id TEXT NOT NULL UNIQUE,
+    stream_type TEXT NOT NULL,
+    PRIMARY KEY (stream_id, version)
+);
+CREATE INDEX events_stream_type ON events (stream_type);
+CREATE TABLE charts (
+    id TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    created_at TEXT COLLATE "C" NOT NULL,
+    series JSONB NOT NULL,
+    title TEXT COLLATE "C" NOT NULL,
+    updated_at TEXT COLLATE "C" NOT NULL,
+    version BIGINT NOT NULL
+);
+CREATE INDEX charts_created_at_id ON charts (created_at, id);
+CREATE INDEX charts_title_id ON charts (title, id);
+CREATE INDEX charts_updated_at_id ON charts (updated_at, id);
+CREATE TABLE data_points (
+    id TEXT NOT NULL PRIMARY KEY,
+    chart_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    series_id TEXT,
+    version BIGINT NOT NULL,
+    x_value TEXT COLLATE "C" NOT NULL,
+    y_value TEXT NOT NULL
+);
+CREATE INDEX data_points_chart_id_x_value ON data_points (chart_id, x_value);
+"#];
+
+/// The key of the advisory lock that serializes the migrations of concurrent processes
+const LOCK_KEY: i64 = 0x6461_7465_6c69_6e65;
+
+#[derive(Debug, thiserror::Error)]
+#[error("the database is newer than this version (applied migrations = {0})")]
+struct UnknownSchemaVersionError(i64);
+
+/// Applies the pending migrations
+pub(crate) async fn migrate(client: &mut Client) -> Result<(), BoxError> {
+    let transaction = client.transaction().await?;
+    transaction
+        .execute("SELECT pg_advisory_xact_lock($1)", &[&LOCK_KEY])
+        .await?;
+    transaction
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (version BIGINT NOT NULL PRIMARY KEY, applied_at TIMESTAMPTZ NOT NULL DEFAULT now())",
+        )
+        .await?;
+    let applied = transaction
+        .query_one(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            &[],
+        )
+        .await?
+        .try_get::<_, i64>(0)?;
+    if applied > i64::try_from(MIGRATIONS.len())? {
+        return Err(UnknownSchemaVersionError(applied).into());
+    }
+    for (version, migration) in (1_i64..).zip(MIGRATIONS).skip(usize::try_from(applied)?) {
+        transaction.batch_execute(migration).await?;
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version) VALUES ($1)",
+                &[&version],
+            )
+            .await?;
+    }
+    transaction.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::postgres_store::postgres_pool::PostgresPool;
+
+    use super::*;
+
+    #[ignore = "requires PostgreSQL (e.g. DATABASE_URL=postgres://postgres@localhost/date_line_charts_test)"]
+    #[tokio::test]
+    async fn test_migrate() -> anyhow::Result<()> {
+        let database_url = std::env::var("DATABASE_URL")?;
+        // connecting twice (concurrently) applies the migrations once
+        let (pool1, pool2) = tokio::join!(
+            PostgresPool::connect(&database_url),
+            PostgresPool::connect(&database_url)
+        );
+        pool1.map_err(|e| anyhow::anyhow!(e))?;
+        let pool = pool2.map_err(|e| anyhow::anyhow!(e))?;
+        let client = pool.get().await.map_err(|e| anyhow::anyhow!(e))?;
+        let applied = client
+            .query_one("SELECT COUNT(*) FROM schema_migrations", &[])
+            .await?
+            .try_get::<_, i64>(0)?;
+        assert_eq!(applied, i64::try_from(MIGRATIONS.len())?);
+        Ok(())
+    }
+}
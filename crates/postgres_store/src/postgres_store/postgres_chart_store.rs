@@ -0,0 +1,447 @@
+use std::str::FromStr as _;
+
+use deadpool_postgres::GenericClient;
+use query_use_case::projection;
+use store_error::{check_version, BoxError, CorruptedError};
+use tokio_postgres::Row;
+use write_model::{
+    aggregate::Chart,
+    event::{
+        ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated,
+        ChartSeriesDeleted, ChartSeriesRenamed, ChartUpdated,
+    },
+    value_object::{ChartId, SeriesId, Version},
+};
+
+use crate::postgres_store::{
+    error::{chart_reader_error, chart_repository_error},
+    event_table::{self, EventRow, StreamType},
+    postgres_pool::PostgresPool,
+};
+
+/// The `data` column of the `events` table
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+enum EventDataJson {
+    Created { title: String },
+    Deleted {},
+    SeriesCreated { name: String, series_id: String },
+    SeriesDeleted { series_id: String },
+    SeriesRenamed { name: String, series_id: String },
+    Updated { title: String },
+}
+
+impl From<&ChartEventData> for EventDataJson {
+    fn from(data: &ChartEventData) -> Self {
+        match data {
+            ChartEventData::Created(ChartCreated { title }) => Self::Created {
+                title: title.to_owned(),
+            },
+            ChartEventData::Deleted(ChartDeleted {}) => Self::Deleted {},
+            ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id }) => {
+                Self::SeriesCreated {
+                    name: name.to_owned(),
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+                Self::SeriesDeleted {
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+                Self::SeriesRenamed {
+                    name: name.to_owned(),
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::Updated(ChartUpdated { title }) => Self::Updated {
+                title: title.to_owned(),
+            },
+        }
+    }
+}
+
+impl TryFrom<EventDataJson> for ChartEventData {
+    type Error = BoxError;
+
+    fn try_from(data: EventDataJson) -> Result<Self, Self::Error> {
+        Ok(match data {
+            EventDataJson::Created { title } => Self::Created(ChartCreated { title }),
+            EventDataJson::Deleted {} => Self::Deleted(ChartDeleted {}),
+            EventDataJson::SeriesCreated { name, series_id } => {
+                Self::SeriesCreated(ChartSeriesCreated {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventDataJson::SeriesDeleted { series_id } => Self::SeriesDeleted(ChartSeriesDeleted {
+                series_id: SeriesId::from_str(&series_id)?,
+            }),
+            EventDataJson::SeriesRenamed { name, series_id } => {
+                Self::SeriesRenamed(ChartSeriesRenamed {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventDataJson::Updated { title } => Self::Updated(ChartUpdated { title }),
+        })
+    }
+}
+
+impl TryFrom<&ChartEvent> for EventRow {
+    type Error = BoxError;
+
+    fn try_from(event: &ChartEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            at: event.at.to_string(),
+            data: serde_json::to_string(&EventDataJson::from(&event.data))?,
+            id: event.id.to_string(),
+            stream_id: event.stream_id.to_string(),
+            version: i64::from(event.version),
+        })
+    }
+}
+
+impl TryFrom<EventRow> for ChartEvent {
+    type Error = BoxError;
+
+    fn try_from(
+        EventRow {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: EventRow,
+    ) -> Result<Self, Self::Error> {
+        Ok(ChartEvent {
+            at: at.parse()?,
+            data: ChartEventData::try_from(serde_json::from_str::<EventDataJson>(&data)?)?,
+            id: id.parse()?,
+            stream_id: stream_id.parse()?,
+            version: Version::try_from(version)?,
+        })
+    }
+}
+
+/// An element of the `series` column of the `charts` table
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct SeriesJson {
+    id: String,
+    name: String,
+}
+
+const CHART_COLUMNS: &str = "id, created_at, series::TEXT AS series, title, updated_at, version";
+
+fn chart_from_row(row: &Row) -> Result<query_use_case::port::ChartQueryData, BoxError> {
+    let series = serde_json::from_str::<Vec<SeriesJson>>(row.try_get("series")?)?;
+    Ok(query_use_case::port::ChartQueryData {
+        created_at: row.try_get::<_, &str>("created_at")?.parse()?,
+        id: row.try_get::<_, &str>("id")?.parse()?,
+        series: series
+            .into_iter()
+            .map(|SeriesJson { id, name }| {
+                Ok(query_use_case::port::SeriesQueryData {
+                    id: SeriesId::from_str(&id)?,
+                    name,
+                })
+            })
+            .collect::<Result<Vec<_>, BoxError>>()?,
+        title: row.try_get("title")?,
+        updated_at: row.try_get::<_, &str>("updated_at")?.parse()?,
+        version: Version::try_from(row.try_get::<_, i64>("version")?)?,
+    })
+}
+
+async fn select_chart<C: GenericClient>(
+    client: &C,
+    id: ChartId,
+) -> Result<Option<query_use_case::port::ChartQueryData>, BoxError> {
+    client
+        .query_opt(
+            &format!("SELECT {} FROM charts WHERE id = $1", CHART_COLUMNS),
+            &[&id.to_string()],
+        )
+        .await?
+        .map(|row| chart_from_row(&row))
+        .transpose()
+        .map_err(|e| CorruptedError(e).into())
+}
+
+/// Writes the chart to the `charts` table (`None` deletes it)
+async fn write_chart<C: GenericClient>(
+    client: &C,
+    id: ChartId,
+    chart: Option<&query_use_case::port::ChartQueryData>,
+) -> Result<(), BoxError> {
+    match chart {
+        None => {
+            client
+                .execute("DELETE FROM charts WHERE id = $1", &[&id.to_string()])
+                .await?;
+        }
+        Some(chart) => {
+            let series = chart
+                .series
+                .iter()
+                .map(|series| SeriesJson {
+                    id: series.id.to_string(),
+                    name: series.name.clone(),
+                })
+                .collect::<Vec<SeriesJson>>();
+            client
+                .execute(
+                    "INSERT INTO charts (id, created_at, series, title, updated_at, version) VALUES ($1, $2, $3::TEXT::JSONB, $4, $5, $6) ON CONFLICT (id) DO UPDATE SET created_at = EXCLUDED.created_at, series = EXCLUDED.series, title = EXCLUDED.title, updated_at = EXCLUDED.updated_at, version = EXCLUDED.version",
+                    &[
+                        &chart.id.to_string(),
+                        &chart.created_at.to_string(),
+                        &serde_json::to_string(&series)?,
+                        &chart.title,
+                        &chart.updated_at.to_string(),
+                        &i64::from(chart.version),
+                    ],
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub struct PostgresChartStore {
+    pool: PostgresPool,
+}
+
+impl PostgresChartStore {
+    pub fn new(pool: PostgresPool) -> Self {
+        Self { pool }
+    }
+
+    async fn find_impl(&self, id: ChartId) -> Result<Option<Chart>, BoxError> {
+        let client = self.pool.get().await?;
+        let events = event_table::select_by_stream_id(&client, &id.to_string())
+            .await?
+            .into_iter()
+            .map(ChartEvent::try_from)
+            .collect::<Result<Vec<ChartEvent>, BoxError>>()
+            .map_err(CorruptedError)?;
+        if events.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            Chart::from_events(&events).map_err(|e| CorruptedError(e.into()))?,
+        ))
+    }
+
+    async fn find_events_impl(&self, id: ChartId) -> Result<Vec<ChartEvent>, BoxError> {
+        let client = self.pool.get().await?;
+        event_table::select_by_stream_id(&client, &id.to_string())
+            .await?
+            .into_iter()
+            .map(ChartEvent::try_from)
+            .collect::<Result<Vec<ChartEvent>, BoxError>>()
+            .map_err(|e| CorruptedError(e).into())
+    }
+
+    async fn find_ids_impl(&self) -> Result<Vec<ChartId>, BoxError> {
+        let client = self.pool.get().await?;
+        let mut ids = event_table::select_stream_ids(&client, StreamType::Chart)
+            .await?
+            .iter()
+            .map(|id| ChartId::from_str(id).map_err(BoxError::from))
+            .collect::<Result<Vec<ChartId>, BoxError>>()
+            .map_err(CorruptedError)?;
+        // the ids are read in no particular order
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn find_all_events_impl(&self) -> Result<Vec<ChartEvent>, BoxError> {
+        let client = self.pool.get().await?;
+        let mut events = event_table::select_by_stream_type(&client, StreamType::Chart)
+            .await?
+            .into_iter()
+            .map(ChartEvent::try_from)
+            .collect::<Result<Vec<ChartEvent>, BoxError>>()
+            .map_err(CorruptedError)?;
+        events.sort_by_key(|event| (event.stream_id, event.version));
+        Ok(events)
+    }
+
+    async fn get_impl(
+        &self,
+        id: ChartId,
+    ) -> Result<Option<query_use_case::port::ChartQueryData>, BoxError> {
+        let client = self.pool.get().await?;
+        select_chart(&client, id).await
+    }
+
+    async fn list_impl(
+        &self,
+        query: query_use_case::port::ChartListQuery,
+    ) -> Result<Vec<query_use_case::port::ChartQueryData>, BoxError> {
+        let client = self.pool.get().await?;
+        // the timestamps are stored in the string form of `ChartSortKey::value`
+        let column = match query.sort_key {
+            query_use_case::port::ChartSortKey::CreatedAt => "created_at",
+            query_use_case::port::ChartSortKey::Title => "title",
+            query_use_case::port::ChartSortKey::UpdatedAt => "updated_at",
+        };
+        let (operator, order) = match query.order {
+            query_use_case::port::SortOrder::Ascending => (">", "ASC"),
+            query_use_case::port::SortOrder::Descending => ("<", "DESC"),
+        };
+        let after = query.after.as_ref();
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {columns} FROM charts WHERE $1::TEXT IS NULL OR ({column}, id) {operator} ($1, $2) ORDER BY {column} {order}, id {order} LIMIT $3",
+                    columns = CHART_COLUMNS,
+                ),
+                &[
+                    &after.map(|cursor| cursor.key.as_str()),
+                    &after.map(|cursor| cursor.id.to_string()),
+                    &i64::try_from(query.limit)?,
+                ],
+            )
+            .await?;
+        rows.iter()
+            .map(chart_from_row)
+            .collect::<Result<Vec<_>, BoxError>>()
+            .map_err(|e| CorruptedError(e).into())
+    }
+
+    async fn store_impl(
+        &self,
+        current: Option<Version>,
+        events: &[ChartEvent],
+    ) -> Result<(), BoxError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let id = events[0].stream_id;
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        check_version(
+            current,
+            event_table::last_version(&transaction, &id.to_string()).await?,
+        )?;
+        // the query data is updated in the same transaction
+        let mut chart = select_chart(&transaction, id).await?;
+        for event in events {
+            event_table::insert(&transaction, StreamType::Chart, &EventRow::try_from(event)?)
+                .await?;
+            chart =
+                projection::project_chart(chart, event).map_err(|e| CorruptedError(e.into()))?;
+        }
+        write_chart(&transaction, id, chart.as_ref()).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl command_use_case::port::ChartRepository for PostgresChartStore {
+    async fn find(
+        &self,
+        id: ChartId,
+    ) -> Result<Option<Chart>, command_use_case::port::chart_repository::Error> {
+        self.find_impl(id).await.map_err(chart_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_events_impl(id)
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<ChartId>, command_use_case::port::chart_repository::Error> {
+        self.find_ids_impl().await.map_err(chart_repository_error)
+    }
+
+    async fn store(
+        &self,
+        current: Option<Version>,
+        events: &[ChartEvent],
+    ) -> Result<(), command_use_case::port::chart_repository::Error> {
+        self.store_impl(current, events)
+            .await
+            .map_err(chart_repository_error)
+    }
+}
+
+#[async_trait::async_trait]
+impl query_use_case::port::ChartReader for PostgresChartStore {
+    async fn get(
+        &self,
+        id: ChartId,
+    ) -> Result<
+        Option<query_use_case::port::ChartQueryData>,
+        query_use_case::port::chart_reader::Error,
+    > {
+        self.get_impl(id).await.map_err(chart_reader_error)
+    }
+
+    async fn list(
+        &self,
+        query: query_use_case::port::ChartListQuery,
+    ) -> Result<Vec<query_use_case::port::ChartQueryData>, query_use_case::port::chart_reader::Error>
+    {
+        self.list_impl(query).await.map_err(chart_reader_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_use_case::port::ChartRepository as _;
+
+    use super::*;
+
+    async fn store() -> anyhow::Result<PostgresChartStore> {
+        let database_url = std::env::var("DATABASE_URL")?;
+        let pool = PostgresPool::connect(&database_url)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(PostgresChartStore::new(pool))
+    }
+
+    #[ignore = "requires PostgreSQL (e.g. DATABASE_URL=postgres://postgres@localhost/date_line_charts_test)"]
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        store_test_kit::test_chart_store(&store().await?).await
+    }
+
+    #[ignore = "requires PostgreSQL (e.g. DATABASE_URL=postgres://postgres@localhost/date_line_charts_test)"]
+    #[tokio::test]
+    async fn test_concurrent_store() -> anyhow::Result<()> {
+        let (store1, store2) = (store().await?, store().await?);
+        let (chart, created) = Chart::create("title1".to_owned())?;
+        store1.store(None, &created).await?;
+        let (_, updated1) = chart.update("title2".to_owned())?;
+        let (_, updated2) = chart.update("title3".to_owned())?;
+        let (result1, result2) = tokio::join!(
+            store1.store(Some(chart.version()), &updated1),
+            store2.store(Some(chart.version()), &updated2)
+        );
+        let results = [result1, result2];
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results.iter().any(|result| matches!(
+            result,
+            Err(command_use_case::port::chart_repository::Error::Conflict(_))
+        )));
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+use std::str::FromStr as _;
+
+use deadpool_postgres::GenericClient;
+use query_use_case::projection;
+use store_error::{check_version, BoxError, CorruptedError};
+use tokio_postgres::Row;
+use write_model::{
+    aggregate::DataPoint,
+    event::{
+        DataPointCreated, DataPointDeleted, DataPointEvent, DataPointEventData, DataPointUpdated,
+    },
+    value_object::{ChartId, DataPointId, SeriesId, Version, YValue},
+};
+
+use crate::postgres_store::{
+    error::{data_point_reader_error, data_point_repository_error},
+    event_table::{self, EventRow, StreamType},
+    postgres_pool::PostgresPool,
+};
+
+/// The `data` column of the `events` table. `value` is the string form of `YValue`
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+enum EventDataJson {
+    Created { value: String },
+    Deleted {},
+    Updated { value: String },
+}
+
+impl From<&DataPointEventData> for EventDataJson {
+    fn from(data: &DataPointEventData) -> Self {
+        match data {
+            DataPointEventData::Created(DataPointCreated { value }) => Self::Created {
+                value: value.to_string(),
+            },
+            DataPointEventData::Deleted(DataPointDeleted {}) => Self::Deleted {},
+            DataPointEventData::Updated(DataPointUpdated { value }) => Self::Updated {
+                value: value.to_string(),
+            },
+        }
+    }
+}
+
+impl TryFrom<EventDataJson> for DataPointEventData {
+    type Error = BoxError;
+
+    fn try_from(data: EventDataJson) -> Result<Self, Self::Error> {
+        Ok(match data {
+            EventDataJson::Created { value } => Self::Created(DataPointCreated {
+                value: YValue::from_str(&value)?,
+            }),
+            EventDataJson::Deleted {} => Self::Deleted(DataPointDeleted {}),
+            EventDataJson::Updated { value } => Self::Updated(DataPointUpdated {
+                value: YValue::from_str(&value)?,
+            }),
+        })
+    }
+}
+
+impl TryFrom<&DataPointEvent> for EventRow {
+    type Error = BoxError;
+
+    fn try_from(event: &DataPointEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            at: event.at.to_string(),
+            data: serde_json::to_string(&EventDataJson::from(&event.data))?,
+            id: event.id.to_string(),
+            stream_id: event.stream_id.to_string(),
+            version: i64::from(event.version),
+        })
+    }
+}
+
+impl TryFrom<EventRow> for DataPointEvent {
+    type Error = BoxError;
+
+    fn try_from(
+        EventRow {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: EventRow,
+    ) -> Result<Self, Self::Error> {
+        Ok(DataPointEvent {
+            at: at.parse()?,
+            data: DataPointEventData::try_from(serde_json::from_str::<EventDataJson>(&data)?)?,
+            id: id.parse()?,
+            stream_id: stream_id.parse()?,
+            version: Version::try_from(version)?,
+        })
+    }
+}
+
+const DATA_POINT_COLUMNS: &str = "id, chart_id, created_at, series_id, version, x_value, y_value";
+
+fn data_point_from_row(row: &Row) -> Result<query_use_case::port::DataPointQueryData, BoxError> {
+    Ok(query_use_case::port::DataPointQueryData {
+        chart_id: row.try_get::<_, &str>("chart_id")?.parse()?,
+        created_at: row.try_get::<_, &str>("created_at")?.parse()?,
+        series_id: row
+            .try_get::<_, Option<&str>>("series_id")?
+            .map(SeriesId::from_str)
+            .transpose()?,
+        version: Version::try_from(row.try_get::<_, i64>("version")?)?,
+        x_value: row.try_get::<_, &str>("x_value")?.parse()?,
+        y_value: row.try_get::<_, &str>("y_value")?.parse()?,
+    })
+}
+
+async fn select_data_point<C: GenericClient>(
+    client: &C,
+    id: DataPointId,
+) -> Result<Option<query_use_case::port::DataPointQueryData>, BoxError> {
+    client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM data_points WHERE id = $1",
+                DATA_POINT_COLUMNS
+            ),
+            &[&id.to_string()],
+        )
+        .await?
+        .map(|row| data_point_from_row(&row))
+        .transpose()
+        .map_err(|e| CorruptedError(e).into())
+}
+
+/// Writes the data point to the `data_points` table (`None` deletes it)
+async fn write_data_point<C: GenericClient>(
+    client: &C,
+    id: DataPointId,
+    data_point: Option<&query_use_case::port::DataPointQueryData>,
+) -> Result<(), BoxError> {
+    match data_point {
+        None => {
+            client
+                .execute("DELETE FROM data_points WHERE id = $1", &[&id.to_string()])
+                .await?;
+        }
+        Some(data_point) => {
+            client
+                .execute(
+                    &format!(
+                        "INSERT INTO data_points ({}) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (id) DO UPDATE SET version = EXCLUDED.version, y_value = EXCLUDED.y_value",
+                        DATA_POINT_COLUMNS
+                    ),
+                    &[
+                        &id.to_string(),
+                        &data_point.chart_id.to_string(),
+                        &data_point.created_at.to_string(),
+                        &data_point.series_id.map(|series_id| series_id.to_string()),
+                        &i64::from(data_point.version),
+                        &data_point.x_value.to_string(),
+                        &data_point.y_value.to_string(),
+                    ],
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub struct PostgresDataPointStore {
+    pool: PostgresPool,
+}
+
+impl PostgresDataPointStore {
+    pub fn new(pool: PostgresPool) -> Self {
+        Self { pool }
+    }
+
+    async fn find_impl(&self, id: DataPointId) -> Result<Option<DataPoint>, BoxError> {
+        let client = self.pool.get().await?;
+        let events = event_table::select_by_stream_id(&client, &id.to_string())
+            .await?
+            .into_iter()
+            .map(DataPointEvent::try_from)
+            .collect::<Result<Vec<DataPointEvent>, BoxError>>()
+            .map_err(CorruptedError)?;
+        if events.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            DataPoint::from_events(&events).map_err(|e| CorruptedError(e.into()))?,
+        ))
+    }
+
+    async fn find_events_impl(&self, id: DataPointId) -> Result<Vec<DataPointEvent>, BoxError> {
+        let client = self.pool.get().await?;
+        event_table::select_by_stream_id(&client, &id.to_string())
+            .await?
+            .into_iter()
+            .map(DataPointEvent::try_from)
+            .collect::<Result<Vec<DataPointEvent>, BoxError>>()
+            .map_err(|e| CorruptedError(e).into())
+    }
+
+    async fn find_ids_impl(&self) -> Result<Vec<DataPointId>, BoxError> {
+        let client = self.pool.get().await?;
+        let mut ids = event_table::select_stream_ids(&client, StreamType::DataPoint)
+            .await?
+            .iter()
+            .map(|id| DataPointId::from_str(id).map_err(BoxError::from))
+            .collect::<Result<Vec<DataPointId>, BoxError>>()
+            .map_err(CorruptedError)?;
+        // the ids are read in no particular order
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn find_all_events_impl(&self) -> Result<Vec<DataPointEvent>, BoxError> {
+        let client = self.pool.get().await?;
+        let mut events = event_table::select_by_stream_type(&client, StreamType::DataPoint)
+            .await?
+            .into_iter()
+            .map(DataPointEvent::try_from)
+            .collect::<Result<Vec<DataPointEvent>, BoxError>>()
+            .map_err(CorruptedError)?;
+        events.sort_by_key(|event| (event.stream_id, event.version));
+        Ok(events)
+    }
+
+    async fn get_impl(
+        &self,
+        id: DataPointId,
+    ) -> Result<Option<query_use_case::port::DataPointQueryData>, BoxError> {
+        let client = self.pool.get().await?;
+        select_data_point(&client, id).await
+    }
+
+    /// Lists the data points in the order of `DataPointCursor`. `page` is the cursor and the limit
+    async fn list_impl(
+        &self,
+        chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
+        page: Option<(Option<query_use_case::port::DataPointCursor>, usize)>,
+    ) -> Result<Vec<query_use_case::port::DataPointQueryData>, BoxError> {
+        let client = self.pool.get().await?;
+        // `NULL` is the default series
+        let (all_series, series_id) = match series {
+            query_use_case::port::SeriesFilter::All => (true, None),
+            query_use_case::port::SeriesFilter::Default => (false, None),
+            query_use_case::port::SeriesFilter::Series(series_id) => {
+                (false, Some(series_id.to_string()))
+            }
+        };
+        // the default series (`NULL`) is ordered first as `''`
+        let (after, limit) = match page {
+            None => (None, None),
+            Some((after, limit)) => (after, Some(i64::try_from(limit)?)),
+        };
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM data_points WHERE chart_id = $1 AND ($2 OR series_id IS NOT DISTINCT FROM $3::TEXT) AND ($4::TEXT IS NULL OR x_value >= $4) AND ($5::TEXT IS NULL OR x_value < $5) AND ($6::TEXT IS NULL OR (x_value, COALESCE(series_id, '')) > ($6, $7::TEXT)) ORDER BY x_value, COALESCE(series_id, '') LIMIT $8",
+                    DATA_POINT_COLUMNS
+                ),
+                &[
+                    &chart_id.to_string(),
+                    &all_series,
+                    &series_id,
+                    &range.from.map(|x_value| x_value.to_string()),
+                    &range.to.map(|x_value| x_value.to_string()),
+                    &after.map(|after| after.x_value.to_string()),
+                    &after.map(|after| {
+                        after
+                            .series_id
+                            .map(|series_id| series_id.to_string())
+                            .unwrap_or_default()
+                    }),
+                    &limit,
+                ],
+            )
+            .await?;
+        rows.iter()
+            .map(data_point_from_row)
+            .collect::<Result<Vec<_>, BoxError>>()
+            .map_err(|e| CorruptedError(e).into())
+    }
+
+    async fn store_impl(
+        &self,
+        current: Option<Version>,
+        events: &[DataPointEvent],
+    ) -> Result<(), BoxError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let id = events[0].stream_id;
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        check_version(
+            current,
+            event_table::last_version(&transaction, &id.to_string()).await?,
+        )?;
+        // the query data is updated in the same transaction
+        let mut data_point = select_data_point(&transaction, id).await?;
+        for event in events {
+            event_table::insert(
+                &transaction,
+                StreamType::DataPoint,
+                &EventRow::try_from(event)?,
+            )
+            .await?;
+            data_point = projection::project_data_point(data_point, event)
+                .map_err(|e| CorruptedError(e.into()))?;
+        }
+        write_data_point(&transaction, id, data_point.as_ref()).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl command_use_case::port::DataPointRepository for PostgresDataPointStore {
+    async fn find(
+        &self,
+        id: DataPointId,
+    ) -> Result<Option<DataPoint>, command_use_case::port::data_point_repository::Error> {
+        self.find_impl(id)
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: DataPointId,
+    ) -> Result<Vec<DataPointEvent>, command_use_case::port::data_point_repository::Error> {
+        self.find_events_impl(id)
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<DataPointId>, command_use_case::port::data_point_repository::Error> {
+        self.find_ids_impl()
+            .await
+            .map_err(data_point_repository_error)
+    }
+
+    async fn store(
+        &self,
+        current: Option<Version>,
+        events: &[DataPointEvent],
+    ) -> Result<(), command_use_case::port::data_point_repository::Error> {
+        self.store_impl(current, events)
+            .await
+            .map_err(data_point_repository_error)
+    }
+}
+
+#[async_trait::async_trait]
+impl query_use_case::port::DataPointReader for PostgresDataPointStore {
+    async fn get(
+        &self,
+        id: DataPointId,
+    ) -> Result<
+        Option<query_use_case::port::DataPointQueryData>,
+        query_use_case::port::data_point_reader::Error,
+    > {
+        self.get_impl(id).await.map_err(data_point_reader_error)
+    }
+
+    async fn list(
+        &self,
+        chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
+    ) -> Result<
+        Vec<query_use_case::port::DataPointQueryData>,
+        query_use_case::port::data_point_reader::Error,
+    > {
+        self.list_impl(chart_id, series, range, None)
+            .await
+            .map_err(data_point_reader_error)
+    }
+
+    async fn list_page(
+        &self,
+        chart_id: ChartId,
+        series: query_use_case::port::SeriesFilter,
+        range: query_use_case::port::XValueRange,
+        after: Option<query_use_case::port::DataPointCursor>,
+        limit: usize,
+    ) -> Result<
+        Vec<query_use_case::port::DataPointQueryData>,
+        query_use_case::port::data_point_reader::Error,
+    > {
+        self.list_impl(chart_id, series, range, Some((after, limit)))
+            .await
+            .map_err(data_point_reader_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ignore = "requires PostgreSQL (e.g. DATABASE_URL=postgres://postgres@localhost/date_line_charts_test)"]
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let pool = PostgresPool::connect(&std::env::var("DATABASE_URL")?)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        store_test_kit::test_data_point_store(&PostgresDataPointStore::new(pool)).await
+    }
+}
@@ -1,5 +1,8 @@
+pub mod aggregate_data_points;
 pub mod get_chart;
+pub mod get_chart_stats;
 pub mod get_data_point;
 pub mod list_charts;
 pub mod list_data_points;
 pub mod port;
+pub mod projection;
@@ -0,0 +1,473 @@
+use std::{collections::BTreeMap, str::FromStr as _, sync::Arc};
+
+use write_model::value_object::{ChartId, SeriesId, XValue, YValue};
+
+use crate::port::{
+    DataPointQueryData, HasChartReader, HasDataPointReader, SeriesFilter, XValueRange,
+};
+#[cfg(any(test, feature = "test-util"))]
+use crate::query_use_case::port::{ChartReader, DataPointReader};
+
+pub struct Input {
+    pub chart_id: String,
+    /// The inclusive lower bound of `x_value`
+    pub from: Option<String>,
+    /// `"count"`, `"max"`, `"mean"`, `"min"` or `"sum"`. Empty computes every function
+    pub functions: Vec<String>,
+    /// `"week"` (ISO week), `"month"` or `"year"`
+    pub interval: String,
+    /// `None` aggregates the default series. The series are never pooled
+    pub series_id: Option<String>,
+    /// The exclusive upper bound of `x_value`
+    pub to: Option<String>,
+}
+
+pub struct Output(pub Vec<Bucket>);
+
+/// The aggregates of the data points in a bucket. Functions that were not requested are `None`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bucket {
+    pub count: Option<u32>,
+    /// `"2020-W01"` (week), `"2020-01"` (month) or `"2020"` (year)
+    pub key: String,
+    pub max: Option<String>,
+    /// Truncated toward zero to 6 fractional digits
+    pub mean: Option<String>,
+    pub min: Option<String>,
+    pub sum: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart get")]
+    ChartGet(#[source] crate::query_use_case::port::chart_reader::Error),
+    #[error("chart id")]
+    ChartId(#[source] write_model::value_object::chart_id::Error),
+    #[error("chart not found (id = {0})")]
+    ChartNotFound(ChartId),
+    #[error("data point list")]
+    DataPointList(#[source] crate::query_use_case::port::data_point_reader::Error),
+    #[error("from")]
+    From(#[source] write_model::value_object::x_value::Error),
+    #[error("function (count, max, mean, min or sum)")]
+    Function,
+    #[error("interval (week, month or year)")]
+    Interval,
+    #[error("overflow")]
+    Overflow,
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+    #[error("series not found (id = {0})")]
+    SeriesNotFound(SeriesId),
+    #[error("to")]
+    To(#[source] write_model::value_object::x_value::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Interval {
+    Month,
+    Week,
+    Year,
+}
+
+impl Interval {
+    /// Returns the sortable bucket of the date
+    fn bucket(&self, x_value: XValue) -> (i32, u32) {
+        match self {
+            Interval::Month => (i32::from(x_value.year()), u32::from(x_value.month())),
+            Interval::Week => {
+                let (year, week) = x_value.iso_week();
+                (year, u32::from(week))
+            }
+            Interval::Year => (i32::from(x_value.year()), 0),
+        }
+    }
+
+    fn key(&self, (year, n): (i32, u32)) -> String {
+        match self {
+            Interval::Month => format!("{:04}-{:02}", year, n),
+            Interval::Week => format!("{:04}-W{:02}", year, n),
+            Interval::Year => format!("{:04}", year),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Functions {
+    count: bool,
+    max: bool,
+    mean: bool,
+    min: bool,
+    sum: bool,
+}
+
+struct Accumulator {
+    count: u32,
+    max: YValue,
+    min: YValue,
+    sum: YValue,
+}
+
+#[async_trait::async_trait]
+pub trait AggregateDataPoints: HasChartReader + HasDataPointReader {
+    async fn execute(
+        &self,
+        Input {
+            chart_id,
+            from,
+            functions,
+            interval,
+            series_id,
+            to,
+        }: Input,
+    ) -> Result<Output, Error> {
+        let chart_reader = self.chart_reader();
+        let data_point_reader = self.data_point_reader();
+        let chart_id = ChartId::from_str(&chart_id).map_err(Error::ChartId)?;
+        let series_id = series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()
+            .map_err(Error::SeriesId)?;
+        let range = XValueRange {
+            from: from
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::From)?,
+            to: to
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::To)?,
+        };
+        let interval = match interval.as_str() {
+            "month" => Interval::Month,
+            "week" => Interval::Week,
+            "year" => Interval::Year,
+            _ => return Err(Error::Interval),
+        };
+        let functions = if functions.is_empty() {
+            Functions {
+                count: true,
+                max: true,
+                mean: true,
+                min: true,
+                sum: true,
+            }
+        } else {
+            let mut parsed = Functions::default();
+            for function in functions {
+                match function.as_str() {
+                    "count" => parsed.count = true,
+                    "max" => parsed.max = true,
+                    "mean" => parsed.mean = true,
+                    "min" => parsed.min = true,
+                    "sum" => parsed.sum = true,
+                    _ => return Err(Error::Function),
+                }
+            }
+            parsed
+        };
+
+        // a deleted chart is not found. a deleted series is not found, its data points are hidden
+        let chart = chart_reader
+            .get(chart_id)
+            .await
+            .map_err(Error::ChartGet)?
+            .ok_or(Error::ChartNotFound(chart_id))?;
+        if let Some(series_id) = series_id {
+            if !chart.shows_series(Some(series_id)) {
+                return Err(Error::SeriesNotFound(series_id));
+            }
+        }
+        let series = series_id.map_or(SeriesFilter::Default, SeriesFilter::Series);
+        let data_points = data_point_reader
+            .list(chart_id, series, range)
+            .await
+            .map_err(Error::DataPointList)?;
+        aggregate(interval, functions, data_points).map(Output)
+    }
+}
+
+fn aggregate(
+    interval: Interval,
+    functions: Functions,
+    data_points: Vec<DataPointQueryData>,
+) -> Result<Vec<Bucket>, Error> {
+    let mut accumulators = BTreeMap::<(i32, u32), Accumulator>::new();
+    for data_point in data_points {
+        let bucket = interval.bucket(data_point.x_value);
+        let y_value = data_point.y_value;
+        match accumulators.get_mut(&bucket) {
+            None => {
+                accumulators.insert(
+                    bucket,
+                    Accumulator {
+                        count: 1,
+                        max: y_value,
+                        min: y_value,
+                        sum: y_value,
+                    },
+                );
+            }
+            Some(accumulator) => {
+                accumulator.count = accumulator.count.checked_add(1).ok_or(Error::Overflow)?;
+                accumulator.max = accumulator.max.max(y_value);
+                accumulator.min = accumulator.min.min(y_value);
+                accumulator.sum = accumulator
+                    .sum
+                    .checked_add(y_value)
+                    .ok_or(Error::Overflow)?;
+            }
+        }
+    }
+
+    Ok(accumulators
+        .into_iter()
+        .map(|(bucket, accumulator)| Bucket {
+            count: functions.count.then_some(accumulator.count),
+            key: interval.key(bucket),
+            max: functions.max.then(|| accumulator.max.to_string()),
+            mean: functions.mean.then(|| {
+                accumulator
+                    .sum
+                    .checked_div(accumulator.count)
+                    .expect("count to be greater than 0")
+                    .to_string()
+            }),
+            min: functions.min.then(|| accumulator.min.to_string()),
+            sum: functions.sum.then(|| accumulator.sum.to_string()),
+        })
+        .collect())
+}
+
+#[cfg(any(test, feature = "test-util"))]
+mockall::mock! {
+    pub AggregateDataPoints {}
+
+    impl HasChartReader for AggregateDataPoints {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync>;
+    }
+
+    impl HasDataPointReader for AggregateDataPoints {
+        fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync>;
+    }
+
+    #[async_trait::async_trait]
+    impl AggregateDataPoints for AggregateDataPoints {
+        async fn execute(&self, input: Input) -> Result<Output, Error>;
+    }
+}
+
+pub trait HasAggregateDataPoints {
+    fn aggregate_data_points(&self) -> Arc<dyn AggregateDataPoints + Send + Sync>;
+}
+
+#[cfg(test)]
+mod tests {
+    use write_model::value_object::{DataPointId, DateTime, Version};
+
+    use crate::port::{chart_reader, data_point_reader, ChartListQuery, ChartQueryData};
+
+    use super::*;
+
+    struct InMemoryChartReader(ChartQueryData);
+
+    #[async_trait::async_trait]
+    impl ChartReader for InMemoryChartReader {
+        async fn get(&self, id: ChartId) -> Result<Option<ChartQueryData>, chart_reader::Error> {
+            Ok(Some(self.0.clone()).filter(|chart| chart.id == id))
+        }
+
+        async fn list(
+            &self,
+            _: ChartListQuery,
+        ) -> Result<Vec<ChartQueryData>, chart_reader::Error> {
+            unreachable!()
+        }
+    }
+
+    struct InMemoryDataPointReader(Vec<DataPointQueryData>);
+
+    #[async_trait::async_trait]
+    impl DataPointReader for InMemoryDataPointReader {
+        async fn get(
+            &self,
+            _: DataPointId,
+        ) -> Result<Option<DataPointQueryData>, data_point_reader::Error> {
+            unreachable!()
+        }
+
+        async fn list(
+            &self,
+            chart_id: ChartId,
+            series: SeriesFilter,
+            range: XValueRange,
+        ) -> Result<Vec<DataPointQueryData>, data_point_reader::Error> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|data_point| {
+                    data_point.chart_id == chart_id
+                        && series.matches(data_point.series_id)
+                        && range.contains(data_point.x_value)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    struct TestAggregateDataPoints(Arc<InMemoryChartReader>, Arc<InMemoryDataPointReader>);
+
+    impl HasChartReader for TestAggregateDataPoints {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync> {
+            self.0.clone()
+        }
+    }
+
+    impl HasDataPointReader for TestAggregateDataPoints {
+        fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync> {
+            self.1.clone()
+        }
+    }
+
+    impl AggregateDataPoints for TestAggregateDataPoints {}
+
+    #[tokio::test]
+    async fn test_series() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let series_id = SeriesId::generate();
+        let chart = ChartQueryData {
+            created_at: DateTime::now(),
+            id: chart_id,
+            series: vec![crate::port::SeriesQueryData {
+                id: series_id,
+                name: "name1".to_string(),
+            }],
+            title: "title1".to_string(),
+            updated_at: DateTime::now(),
+            version: Version::new(),
+        };
+        let data_points = [(None, "1"), (Some(series_id), "10")]
+            .into_iter()
+            .map(|(series_id, y_value)| {
+                Ok(DataPointQueryData {
+                    chart_id,
+                    created_at: DateTime::now(),
+                    series_id,
+                    version: Version::new(),
+                    x_value: XValue::from_str("2020-01-01")?,
+                    y_value: YValue::from_str(y_value)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<DataPointQueryData>>>()?;
+        let use_case = TestAggregateDataPoints(
+            Arc::new(InMemoryChartReader(chart)),
+            Arc::new(InMemoryDataPointReader(data_points)),
+        );
+        let sum = |series_id: Option<SeriesId>| {
+            use_case.execute(Input {
+                chart_id: chart_id.to_string(),
+                from: None,
+                functions: vec!["sum".to_string()],
+                interval: "year".to_string(),
+                series_id: series_id.map(|series_id| series_id.to_string()),
+                to: None,
+            })
+        };
+        // the default series and the series are not pooled
+        let Output(buckets) = sum(None).await?;
+        assert_eq!(buckets[0].sum, Some("1".to_string()));
+        let Output(buckets) = sum(Some(series_id)).await?;
+        assert_eq!(buckets[0].sum, Some("10".to_string()));
+        let unknown_series_id = SeriesId::generate();
+        assert!(matches!(
+            sum(Some(unknown_series_id)).await,
+            Err(Error::SeriesNotFound(id)) if id == unknown_series_id
+        ));
+
+        let unknown_chart_id = ChartId::generate();
+        assert!(matches!(
+            use_case
+                .execute(Input {
+                    chart_id: unknown_chart_id.to_string(),
+                    from: None,
+                    functions: vec![],
+                    interval: "year".to_string(),
+                    series_id: None,
+                    to: None,
+                })
+                .await,
+            Err(Error::ChartNotFound(id)) if id == unknown_chart_id
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_week_key() -> anyhow::Result<()> {
+        for (date, expected) in [
+            ("2020-01-01", "2020-W01"),
+            ("2021-01-03", "2020-W53"),
+            ("2024-12-30", "2025-W01"),
+        ] {
+            let x_value = XValue::from_str(date)?;
+            assert_eq!(
+                Interval::Week.key(Interval::Week.bucket(x_value)),
+                expected,
+                "{}",
+                date
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let data_points = [
+            ("2020-01-01", "1"),
+            ("2020-01-31", "2.5"),
+            ("2020-02-01", "-1"),
+        ]
+        .into_iter()
+        .map(|(x_value, y_value)| {
+            Ok(DataPointQueryData {
+                chart_id,
+                created_at: DateTime::now(),
+                series_id: None,
+                version: Version::new(),
+                x_value: XValue::from_str(x_value)?,
+                y_value: YValue::from_str(y_value)?,
+            })
+        })
+        .collect::<anyhow::Result<Vec<DataPointQueryData>>>()?;
+        let functions = Functions {
+            mean: true,
+            sum: true,
+            ..Functions::default()
+        };
+        assert_eq!(
+            aggregate(Interval::Month, functions, data_points)?,
+            vec![
+                Bucket {
+                    count: None,
+                    key: "2020-01".to_string(),
+                    max: None,
+                    mean: Some("1.75".to_string()),
+                    min: None,
+                    sum: Some("3.5".to_string()),
+                },
+                Bucket {
+                    count: None,
+                    key: "2020-02".to_string(),
+                    max: None,
+                    mean: Some("-1".to_string()),
+                    min: None,
+                    sum: Some("-1".to_string()),
+                },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -15,7 +15,14 @@ pub struct Output(pub Option<OutputItem>);
 pub struct OutputItem {
     pub created_at: String,
     pub id: String,
+    pub series: Vec<OutputItemSeries>,
     pub title: String,
+    pub version: u32,
+}
+
+pub struct OutputItemSeries {
+    pub id: String,
+    pub name: String,
 }
 
 impl From<ChartQueryData> for OutputItem {
@@ -23,13 +30,24 @@ impl From<ChartQueryData> for OutputItem {
         ChartQueryData {
             created_at,
             id,
+            series,
             title,
+            updated_at: _,
+            version,
         }: ChartQueryData,
     ) -> Self {
         Self {
             created_at: created_at.to_string(),
             id: id.to_string(),
+            series: series
+                .into_iter()
+                .map(|series| OutputItemSeries {
+                    id: series.id.to_string(),
+                    name: series.name,
+                })
+                .collect(),
             title,
+            version: u32::from(version),
         }
     }
 }
@@ -86,7 +104,12 @@ mod tests {
             Ok(Output(Some(OutputItem {
                 created_at: "created_at".to_string(),
                 id: "id".to_string(),
+                series: vec![OutputItemSeries {
+                    id: "series_id".to_string(),
+                    name: "name".to_string(),
+                }],
                 title: "title".to_string(),
+                version: 1,
             })))
         });
     }
@@ -0,0 +1,562 @@
+use std::{str::FromStr as _, sync::Arc};
+
+use write_model::value_object::{ChartId, SeriesId, XValue, YValue};
+
+use crate::port::{
+    DataPointQueryData, HasChartReader, HasDataPointReader, SeriesFilter, XValueRange,
+};
+#[cfg(any(test, feature = "test-util"))]
+use crate::query_use_case::port::{ChartReader, DataPointReader};
+
+pub struct Input {
+    pub chart_id: String,
+    /// The inclusive lower bound of `x_value`
+    pub from: Option<String>,
+    /// `None` computes the statistics of the default series. The series are never pooled
+    pub series_id: Option<String>,
+    /// The exclusive upper bound of `x_value`
+    pub to: Option<String>,
+}
+
+/// The statistics of the data points. Every field but the counts is `None` if there are no data points
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output {
+    pub count: usize,
+    /// The number of consecutive days with data points ending at `last_x_value`. The streak is
+    /// not broken by the days after the last data point: the dates have no time zone, so the
+    /// current date of the reader is unknown
+    pub current_streak: u32,
+    pub first_x_value: Option<String>,
+    pub last: Option<Last>,
+    pub last_x_value: Option<String>,
+    /// The longest number of consecutive days with data points
+    pub longest_streak: u32,
+    /// The earliest data point with the maximum value
+    pub max: Option<Extremum>,
+    /// Truncated toward zero to 6 fractional digits
+    pub mean: Option<String>,
+    /// Truncated toward zero to 6 fractional digits
+    pub median: Option<String>,
+    /// The earliest data point with the minimum value
+    pub min: Option<Extremum>,
+    /// The population standard deviation
+    pub standard_deviation: Option<f64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Extremum {
+    pub x_value: String,
+    pub y_value: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Last {
+    /// The difference from the previous data point. `None` if there is only one data point
+    pub delta: Option<String>,
+    pub x_value: String,
+    pub y_value: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart get")]
+    ChartGet(#[source] crate::query_use_case::port::chart_reader::Error),
+    #[error("chart id")]
+    ChartId(#[source] write_model::value_object::chart_id::Error),
+    #[error("chart not found (id = {0})")]
+    ChartNotFound(ChartId),
+    #[error("data point list")]
+    DataPointList(#[source] crate::query_use_case::port::data_point_reader::Error),
+    #[error("from")]
+    From(#[source] write_model::value_object::x_value::Error),
+    #[error("overflow")]
+    Overflow,
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+    #[error("series not found (id = {0})")]
+    SeriesNotFound(SeriesId),
+    #[error("to")]
+    To(#[source] write_model::value_object::x_value::Error),
+}
+
+#[async_trait::async_trait]
+pub trait GetChartStats: HasChartReader + HasDataPointReader {
+    async fn execute(
+        &self,
+        Input {
+            chart_id,
+            from,
+            series_id,
+            to,
+        }: Input,
+    ) -> Result<Output, Error> {
+        let chart_reader = self.chart_reader();
+        let data_point_reader = self.data_point_reader();
+        let chart_id = ChartId::from_str(&chart_id).map_err(Error::ChartId)?;
+        let series_id = series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()
+            .map_err(Error::SeriesId)?;
+        let range = XValueRange {
+            from: from
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::From)?,
+            to: to
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::To)?,
+        };
+        // a deleted chart is not found. a deleted series is not found, its data points are hidden
+        let chart = chart_reader
+            .get(chart_id)
+            .await
+            .map_err(Error::ChartGet)?
+            .ok_or(Error::ChartNotFound(chart_id))?;
+        if let Some(series_id) = series_id {
+            if !chart.shows_series(Some(series_id)) {
+                return Err(Error::SeriesNotFound(series_id));
+            }
+        }
+        let series = series_id.map_or(SeriesFilter::Default, SeriesFilter::Series);
+        let data_points = data_point_reader
+            .list(chart_id, series, range)
+            .await
+            .map_err(Error::DataPointList)?;
+        stats(data_points)
+    }
+}
+
+fn stats(mut data_points: Vec<DataPointQueryData>) -> Result<Output, Error> {
+    data_points.sort_by_key(|data_point| data_point.x_value);
+    let count = data_points.len();
+    let (current_streak, longest_streak) = streaks(&data_points);
+    let (Some(first), Some(last)) = (data_points.first(), data_points.last()) else {
+        return Ok(Output {
+            count,
+            current_streak,
+            first_x_value: None,
+            last: None,
+            last_x_value: None,
+            longest_streak,
+            max: None,
+            mean: None,
+            median: None,
+            min: None,
+            standard_deviation: None,
+        });
+    };
+
+    let extremum = |data_point: &DataPointQueryData| Extremum {
+        x_value: data_point.x_value.to_string(),
+        y_value: data_point.y_value.to_string(),
+    };
+    // `min_by_key` / `max_by_key` return the first / last element of equal elements
+    let min = data_points
+        .iter()
+        .min_by_key(|data_point| data_point.y_value)
+        .map(extremum);
+    let max = data_points
+        .iter()
+        .rev()
+        .max_by_key(|data_point| data_point.y_value)
+        .map(extremum);
+
+    let divisor = u32::try_from(count).map_err(|_| Error::Overflow)?;
+    let sum = data_points
+        .iter()
+        .try_fold(YValue::from(0), |sum, data_point| {
+            sum.checked_add(data_point.y_value)
+        })
+        .ok_or(Error::Overflow)?;
+    let mean = sum.checked_div(divisor).ok_or(Error::Overflow)?;
+
+    let mut y_values = data_points
+        .iter()
+        .map(|data_point| data_point.y_value)
+        .collect::<Vec<YValue>>();
+    y_values.sort();
+    let median = if count % 2 == 1 {
+        y_values[count / 2]
+    } else {
+        y_values[count / 2 - 1]
+            .checked_add(y_values[count / 2])
+            .and_then(|sum| sum.checked_div(2))
+            .ok_or(Error::Overflow)?
+    };
+
+    let mean_f64 = mean.to_f64();
+    let variance = y_values
+        .iter()
+        .map(|y_value| (y_value.to_f64() - mean_f64).powi(2))
+        .sum::<f64>()
+        / count as f64;
+
+    let delta = match data_points.len().checked_sub(2) {
+        None => None,
+        Some(index) => Some(
+            last.y_value
+                .checked_sub(data_points[index].y_value)
+                .ok_or(Error::Overflow)?
+                .to_string(),
+        ),
+    };
+
+    Ok(Output {
+        count,
+        current_streak,
+        first_x_value: Some(first.x_value.to_string()),
+        last: Some(Last {
+            delta,
+            x_value: last.x_value.to_string(),
+            y_value: last.y_value.to_string(),
+        }),
+        last_x_value: Some(last.x_value.to_string()),
+        longest_streak,
+        max,
+        mean: Some(mean.to_string()),
+        median: Some(median.to_string()),
+        min,
+        standard_deviation: Some(variance.sqrt()),
+    })
+}
+
+/// Returns the current and the longest daily streak of the data points of a series sorted by date
+fn streaks(data_points: &[DataPointQueryData]) -> (u32, u32) {
+    let mut current = 0_u32;
+    let mut longest = 0_u32;
+    let mut previous: Option<XValue> = None;
+    for data_point in data_points {
+        let x_value = data_point.x_value;
+        current = match previous {
+            Some(previous) if previous.next_day() == Some(x_value) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(x_value);
+    }
+    (current, longest)
+}
+
+#[cfg(any(test, feature = "test-util"))]
+mockall::mock! {
+    pub GetChartStats {}
+
+    impl HasChartReader for GetChartStats {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync>;
+    }
+
+    impl HasDataPointReader for GetChartStats {
+        fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync>;
+    }
+
+    #[async_trait::async_trait]
+    impl GetChartStats for GetChartStats {
+        async fn execute(&self, input: Input) -> Result<Output, Error>;
+    }
+}
+
+pub trait HasGetChartStats {
+    fn get_chart_stats(&self) -> Arc<dyn GetChartStats + Send + Sync>;
+}
+
+#[cfg(test)]
+mod tests {
+    use write_model::value_object::{DataPointId, DateTime, Version};
+
+    use crate::port::{
+        chart_reader, data_point_reader, ChartListQuery, ChartQueryData, SeriesQueryData,
+    };
+
+    use super::*;
+
+    struct InMemoryChartReader(Vec<ChartQueryData>);
+
+    #[async_trait::async_trait]
+    impl ChartReader for InMemoryChartReader {
+        async fn get(&self, id: ChartId) -> Result<Option<ChartQueryData>, chart_reader::Error> {
+            Ok(self.0.iter().find(|chart| chart.id == id).cloned())
+        }
+
+        async fn list(
+            &self,
+            _: ChartListQuery,
+        ) -> Result<Vec<ChartQueryData>, chart_reader::Error> {
+            unreachable!()
+        }
+    }
+
+    struct InMemoryDataPointReader(Vec<DataPointQueryData>);
+
+    #[async_trait::async_trait]
+    impl DataPointReader for InMemoryDataPointReader {
+        async fn get(
+            &self,
+            _: DataPointId,
+        ) -> Result<Option<DataPointQueryData>, data_point_reader::Error> {
+            unreachable!()
+        }
+
+        async fn list(
+            &self,
+            chart_id: ChartId,
+            series: SeriesFilter,
+            range: XValueRange,
+        ) -> Result<Vec<DataPointQueryData>, data_point_reader::Error> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|data_point| {
+                    data_point.chart_id == chart_id
+                        && series.matches(data_point.series_id)
+                        && range.contains(data_point.x_value)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    struct TestGetChartStats(Arc<InMemoryChartReader>, Arc<InMemoryDataPointReader>);
+
+    impl HasChartReader for TestGetChartStats {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync> {
+            self.0.clone()
+        }
+    }
+
+    impl HasDataPointReader for TestGetChartStats {
+        fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync> {
+            self.1.clone()
+        }
+    }
+
+    impl GetChartStats for TestGetChartStats {}
+
+    fn build_use_case(
+        chart_id: ChartId,
+        data_points: &[(&str, &str)],
+    ) -> anyhow::Result<TestGetChartStats> {
+        build_use_case_with_series(
+            chart_id,
+            &[],
+            &data_points
+                .iter()
+                .map(|(x_value, y_value)| (None, *x_value, *y_value))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn build_use_case_with_series(
+        chart_id: ChartId,
+        series: &[SeriesId],
+        data_points: &[(Option<SeriesId>, &str, &str)],
+    ) -> anyhow::Result<TestGetChartStats> {
+        let chart = ChartQueryData {
+            created_at: DateTime::now(),
+            id: chart_id,
+            series: series
+                .iter()
+                .map(|series_id| SeriesQueryData {
+                    id: *series_id,
+                    name: series_id.to_string(),
+                })
+                .collect(),
+            title: "title".to_string(),
+            updated_at: DateTime::now(),
+            version: Version::new(),
+        };
+        let data_points = data_points
+            .iter()
+            .map(|(series_id, x_value, y_value)| {
+                Ok(DataPointQueryData {
+                    chart_id,
+                    created_at: DateTime::now(),
+                    series_id: *series_id,
+                    version: Version::new(),
+                    x_value: XValue::from_str(x_value)?,
+                    y_value: YValue::from_str(y_value)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<DataPointQueryData>>>()?;
+        Ok(TestGetChartStats(
+            Arc::new(InMemoryChartReader(vec![chart])),
+            Arc::new(InMemoryDataPointReader(data_points)),
+        ))
+    }
+
+    fn build_input(chart_id: ChartId) -> Input {
+        Input {
+            chart_id: chart_id.to_string(),
+            from: None,
+            series_id: None,
+            to: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stats() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let use_case = build_use_case(
+            chart_id,
+            &[
+                ("2020-01-05", "4"),
+                ("2020-01-01", "2"),
+                ("2020-01-02", "4"),
+                ("2020-01-03", "4"),
+                ("2020-01-06", "5"),
+                ("2020-01-07", "7"),
+                ("2020-01-08", "9"),
+                ("2020-01-09", "2"),
+            ],
+        )?;
+        let output = use_case.execute(build_input(chart_id)).await?;
+        assert_eq!(output.count, 8);
+        assert_eq!(output.current_streak, 5);
+        assert_eq!(output.first_x_value, Some("2020-01-01".to_string()));
+        assert_eq!(
+            output.last,
+            Some(Last {
+                delta: Some("-7".to_string()),
+                x_value: "2020-01-09".to_string(),
+                y_value: "2".to_string(),
+            })
+        );
+        assert_eq!(output.last_x_value, Some("2020-01-09".to_string()));
+        assert_eq!(output.longest_streak, 5);
+        assert_eq!(
+            output.max,
+            Some(Extremum {
+                x_value: "2020-01-08".to_string(),
+                y_value: "9".to_string(),
+            })
+        );
+        assert_eq!(output.mean, Some("4.625".to_string()));
+        assert_eq!(output.median, Some("4".to_string()));
+        assert_eq!(
+            output.min,
+            Some(Extremum {
+                x_value: "2020-01-01".to_string(),
+                y_value: "2".to_string(),
+            })
+        );
+        let standard_deviation = output.standard_deviation.expect("some");
+        assert!((standard_deviation - 2.232_571).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_range() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let use_case = build_use_case(
+            chart_id,
+            &[
+                ("2020-01-01", "1"),
+                ("2020-01-02", "2"),
+                ("2020-01-03", "3"),
+                ("2020-01-04", "4"),
+            ],
+        )?;
+        let output = use_case
+            .execute(Input {
+                from: Some("2020-01-02".to_string()),
+                to: Some("2020-01-04".to_string()),
+                ..build_input(chart_id)
+            })
+            .await?;
+        assert_eq!(output.count, 2);
+        assert_eq!(output.first_x_value, Some("2020-01-02".to_string()));
+        assert_eq!(output.last_x_value, Some("2020-01-03".to_string()));
+        assert_eq!(output.median, Some("2.5".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let use_case = build_use_case(chart_id, &[])?;
+        let output = use_case.execute(build_input(chart_id)).await?;
+        assert_eq!(output.count, 0);
+        assert_eq!(output.current_streak, 0);
+        assert_eq!(output.last, None);
+        assert_eq!(output.standard_deviation, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let series_id = SeriesId::generate();
+        let use_case = build_use_case_with_series(
+            chart_id,
+            &[series_id],
+            &[
+                (None, "2020-01-01", "1"),
+                (Some(series_id), "2020-01-01", "5"),
+                (Some(series_id), "2020-01-02", "7"),
+            ],
+        )?;
+        // the default series and the series are not pooled
+        let output = use_case.execute(build_input(chart_id)).await?;
+        assert_eq!(output.count, 1);
+        assert_eq!(output.mean, Some("1".to_string()));
+        let output = use_case
+            .execute(Input {
+                series_id: Some(series_id.to_string()),
+                ..build_input(chart_id)
+            })
+            .await?;
+        assert_eq!(output.count, 2);
+        assert_eq!(output.current_streak, 2);
+        assert_eq!(
+            output.last,
+            Some(Last {
+                delta: Some("2".to_string()),
+                x_value: "2020-01-02".to_string(),
+                y_value: "7".to_string(),
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deleted_series() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        // the chart has no series, so the series of the data point has been deleted
+        let deleted_series_id = SeriesId::generate();
+        let use_case = build_use_case_with_series(
+            chart_id,
+            &[],
+            &[
+                (None, "2020-01-01", "1"),
+                (Some(deleted_series_id), "2020-01-02", "2"),
+            ],
+        )?;
+        let output = use_case.execute(build_input(chart_id)).await?;
+        assert_eq!(output.count, 1);
+        assert_eq!(output.last_x_value, Some("2020-01-01".to_string()));
+        assert!(matches!(
+            use_case
+                .execute(Input {
+                    series_id: Some(deleted_series_id.to_string()),
+                    ..build_input(chart_id)
+                })
+                .await,
+            Err(Error::SeriesNotFound(id)) if id == deleted_series_id
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chart_not_found() -> anyhow::Result<()> {
+        let use_case = build_use_case(ChartId::generate(), &[("2020-01-01", "1")])?;
+        let chart_id = ChartId::generate();
+        assert!(matches!(
+            use_case.execute(build_input(chart_id)).await,
+            Err(Error::ChartNotFound(id)) if id == chart_id
+        ));
+        Ok(())
+    }
+}
@@ -2,9 +2,9 @@ use std::{str::FromStr as _, sync::Arc};
 
 use write_model::value_object::DataPointId;
 
-use crate::port::{DataPointQueryData, HasDataPointReader};
+use crate::port::{DataPointQueryData, HasChartReader, HasDataPointReader};
 #[cfg(any(test, feature = "test-util"))]
-use crate::query_use_case::port::DataPointReader;
+use crate::query_use_case::port::{ChartReader, DataPointReader};
 
 pub struct Input {
     pub data_point_id: String,
@@ -15,8 +15,10 @@ pub struct Output(pub Option<OutputItem>);
 pub struct OutputItem {
     pub chart_id: String,
     pub created_at: String,
+    pub series_id: Option<String>,
+    pub version: u32,
     pub x_value: String,
-    pub y_value: u32,
+    pub y_value: String,
 }
 
 impl From<DataPointQueryData> for OutputItem {
@@ -24,6 +26,8 @@ impl From<DataPointQueryData> for OutputItem {
         DataPointQueryData {
             chart_id,
             created_at,
+            series_id,
+            version,
             x_value,
             y_value,
         }: DataPointQueryData,
@@ -31,14 +35,18 @@ impl From<DataPointQueryData> for OutputItem {
         Self {
             chart_id: chart_id.to_string(),
             created_at: created_at.to_string(),
+            series_id: series_id.map(|series_id| series_id.to_string()),
+            version: u32::from(version),
             x_value: x_value.to_string(),
-            y_value: u32::from(y_value),
+            y_value: y_value.to_string(),
         }
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("chart get")]
+    ChartGet(#[source] crate::query_use_case::port::chart_reader::Error),
     #[error("data point get")]
     DataPointGet(#[source] crate::query_use_case::port::data_point_reader::Error),
     #[error("data point id")]
@@ -46,16 +54,27 @@ pub enum Error {
 }
 
 #[async_trait::async_trait]
-pub trait GetDataPoint: HasDataPointReader {
+pub trait GetDataPoint: HasChartReader + HasDataPointReader {
     async fn execute(&self, Input { data_point_id }: Input) -> Result<Output, Error> {
+        let chart_reader = self.chart_reader();
         let data_point_reader = self.data_point_reader();
         let data_point_id = DataPointId::from_str(&data_point_id).map_err(Error::DataPointId)?;
+        let Some(data_point) = data_point_reader
+            .get(data_point_id)
+            .await
+            .map_err(Error::DataPointGet)?
+        else {
+            return Ok(Output(None));
+        };
+        // the data points of a deleted chart or of a deleted series are hidden
+        let chart = chart_reader
+            .get(data_point.chart_id)
+            .await
+            .map_err(Error::ChartGet)?;
         Ok(Output(
-            data_point_reader
-                .get(data_point_id)
-                .await
-                .map_err(Error::DataPointGet)?
-                .map(OutputItem::from),
+            chart
+                .filter(|chart| chart.shows_series(data_point.series_id))
+                .map(|_| OutputItem::from(data_point)),
         ))
     }
 }
@@ -64,6 +83,10 @@ pub trait GetDataPoint: HasDataPointReader {
 mockall::mock! {
     pub GetDataPoint {}
 
+    impl HasChartReader for GetDataPoint {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync>;
+    }
+
     impl HasDataPointReader for GetDataPoint {
         fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync>;
     }
@@ -89,8 +112,10 @@ mod tests {
             Ok(Output(Some(OutputItem {
                 chart_id: "chart_id".to_string(),
                 created_at: "2021-08-21T00:00:00Z".to_string(),
+                series_id: None,
+                version: 1,
                 x_value: "2020-01-02".to_string(),
-                y_value: 2,
+                y_value: "2".to_string(),
             })))
         });
     }
@@ -1,18 +1,40 @@
-use std::sync::Arc;
+use std::{str::FromStr as _, sync::Arc};
 
-use crate::port::{ChartQueryData, HasChartReader};
+use write_model::value_object::ChartId;
+
+use crate::port::{
+    ChartCursor, ChartListQuery, ChartQueryData, ChartSortKey, HasChartReader, SortOrder,
+};
 #[cfg(any(test, feature = "test-util"))]
 use crate::query_use_case::port::ChartReader;
 
-pub struct Input;
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
 
-pub struct Output(pub Vec<Chart>);
+#[derive(Debug, Default)]
+pub struct Input {
+    /// The `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// `1` to `1000`. Defaults to `100`
+    pub limit: Option<usize>,
+    /// `"asc"` (default) or `"desc"`
+    pub order: Option<String>,
+    /// `"created_at"` (default), `"title"` or `"updated_at"`
+    pub sort: Option<String>,
+}
+
+pub struct Output {
+    pub charts: Vec<Chart>,
+    /// `None` if there are no more charts
+    pub next_cursor: Option<String>,
+}
 
 #[derive(Clone)]
 pub struct Chart {
     pub created_at: String,
     pub id: String,
     pub title: String,
+    pub updated_at: String,
 }
 
 impl From<ChartQueryData> for Chart {
@@ -20,13 +42,17 @@ impl From<ChartQueryData> for Chart {
         ChartQueryData {
             created_at,
             id,
+            series: _,
             title,
+            updated_at,
+            version: _,
         }: ChartQueryData,
     ) -> Self {
         Self {
             created_at: created_at.to_string(),
             id: id.to_string(),
             title,
+            updated_at: updated_at.to_string(),
         }
     }
 }
@@ -35,20 +61,132 @@ impl From<ChartQueryData> for Chart {
 pub enum Error {
     #[error("chart list")]
     ChartList(#[source] crate::port::chart_reader::Error),
+    #[error("cursor")]
+    Cursor(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("limit (1..={MAX_LIMIT})")]
+    Limit,
+    #[error("order (asc or desc)")]
+    Order,
+    #[error("sort (created_at, title or updated_at)")]
+    Sort,
 }
 
 #[async_trait::async_trait]
 pub trait ListCharts: HasChartReader {
-    async fn execute(&self, _: Input) -> Result<Output, Error> {
+    async fn execute(
+        &self,
+        Input {
+            cursor,
+            limit,
+            order,
+            sort,
+        }: Input,
+    ) -> Result<Output, Error> {
         let chart_reader = self.chart_reader();
-        chart_reader
-            .list()
+        let limit = match limit {
+            None => DEFAULT_LIMIT,
+            Some(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            Some(_) => return Err(Error::Limit),
+        };
+        let order = match order.as_deref() {
+            None | Some("asc") => SortOrder::Ascending,
+            Some("desc") => SortOrder::Descending,
+            Some(_) => return Err(Error::Order),
+        };
+        let sort_key = match sort.as_deref() {
+            None | Some("created_at") => ChartSortKey::CreatedAt,
+            Some("title") => ChartSortKey::Title,
+            Some("updated_at") => ChartSortKey::UpdatedAt,
+            Some(_) => return Err(Error::Sort),
+        };
+        let after = cursor
+            .as_deref()
+            .map(|cursor| decode_cursor(cursor, order, sort_key))
+            .transpose()
+            .map_err(Error::Cursor)?;
+        // fetches one more chart to know whether the next page exists
+        let query = ChartListQuery {
+            after,
+            limit: limit + 1,
+            order,
+            sort_key,
+        };
+        let mut charts = chart_reader
+            .list(query.clone())
             .await
-            .map(|charts| Output(charts.into_iter().map(Chart::from).collect()))
-            .map_err(Error::ChartList)
+            .map_err(Error::ChartList)?;
+        let next_cursor = if charts.len() > limit {
+            charts.truncate(limit);
+            charts
+                .last()
+                .map(|chart| encode_cursor(&query.cursor(chart), order, sort_key))
+        } else {
+            None
+        };
+        Ok(Output {
+            charts: charts.into_iter().map(Chart::from).collect(),
+            next_cursor,
+        })
+    }
+}
+
+fn order_to_str(order: SortOrder) -> &'static str {
+    match order {
+        SortOrder::Ascending => "asc",
+        SortOrder::Descending => "desc",
+    }
+}
+
+fn sort_key_to_str(sort_key: ChartSortKey) -> &'static str {
+    match sort_key {
+        ChartSortKey::CreatedAt => "created_at",
+        ChartSortKey::Title => "title",
+        ChartSortKey::UpdatedAt => "updated_at",
     }
 }
 
+/// Encodes the cursor with the sort options as a hex string, so that a cursor cannot be reused with another sort
+fn encode_cursor(cursor: &ChartCursor, order: SortOrder, sort_key: ChartSortKey) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        sort_key_to_str(sort_key),
+        order_to_str(order),
+        cursor.id,
+        cursor.key
+    )
+    .bytes()
+    .map(|b| format!("{:02x}", b))
+    .collect()
+}
+
+fn decode_cursor(
+    s: &str,
+    order: SortOrder,
+    sort_key: ChartSortKey,
+) -> Result<ChartCursor, Box<dyn std::error::Error + Send + Sync>> {
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return Err("invalid cursor".into());
+    }
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()?;
+    let decoded = String::from_utf8(bytes)?;
+    let mut parts = decoded.splitn(4, '\n');
+    let (Some(sort), Some(o), Some(id), Some(key)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("invalid cursor".into());
+    };
+    if sort != sort_key_to_str(sort_key) || o != order_to_str(order) {
+        return Err("cursor for another sort".into());
+    }
+    Ok(ChartCursor {
+        id: ChartId::from_str(id)?,
+        key: key.to_string(),
+    })
+}
+
 #[cfg(any(test, feature = "test-util"))]
 mockall::mock! {
     pub ListCharts {}
@@ -75,11 +213,33 @@ mod tests {
     fn test_mock() {
         let mut mock = MockListCharts::new();
         mock.expect_execute().return_once(|_| {
-            Ok(Output(vec![Chart {
-                created_at: "created_at".to_string(),
-                id: "id".to_string(),
-                title: "title".to_string(),
-            }]))
+            Ok(Output {
+                charts: vec![Chart {
+                    created_at: "created_at".to_string(),
+                    id: "id".to_string(),
+                    title: "title".to_string(),
+                    updated_at: "updated_at".to_string(),
+                }],
+                next_cursor: None,
+            })
         });
     }
+
+    #[test]
+    fn test_cursor() -> anyhow::Result<()> {
+        let cursor = ChartCursor {
+            id: ChartId::generate(),
+            key: "title\n1".to_string(),
+        };
+        let encoded = encode_cursor(&cursor, SortOrder::Descending, ChartSortKey::Title);
+        assert_eq!(
+            decode_cursor(&encoded, SortOrder::Descending, ChartSortKey::Title)
+                .map_err(|e| anyhow::anyhow!(e))?,
+            cursor
+        );
+        assert!(decode_cursor(&encoded, SortOrder::Ascending, ChartSortKey::Title).is_err());
+        assert!(decode_cursor(&encoded, SortOrder::Descending, ChartSortKey::CreatedAt).is_err());
+        assert!(decode_cursor("zz", SortOrder::Ascending, ChartSortKey::CreatedAt).is_err());
+        Ok(())
+    }
 }
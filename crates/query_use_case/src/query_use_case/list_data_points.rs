@@ -1,13 +1,26 @@
 use std::{str::FromStr as _, sync::Arc};
 
-use write_model::value_object::ChartId;
+use write_model::value_object::{ChartId, DataPointId, SeriesId, XValue};
 
-use crate::port::{DataPointQueryData, HasDataPointReader};
+use crate::port::{
+    DataPointCursor, DataPointQueryData, HasChartReader, HasDataPointReader, SeriesFilter,
+    XValueRange,
+};
 #[cfg(any(test, feature = "test-util"))]
-use crate::query_use_case::port::DataPointReader;
+use crate::query_use_case::port::{ChartReader, DataPointReader};
 
 pub struct Input {
+    /// The id of the last data point of the previous page. Requires `limit`
+    pub after: Option<String>,
     pub chart_id: String,
+    /// The inclusive lower bound of `x_value`
+    pub from: Option<String>,
+    /// The maximum number of data points. `None` lists all. A page with fewer data points is the
+    /// last one. Pages are ordered by `x_value` and then by `series_id`
+    pub limit: Option<usize>,
+    pub series_id: Option<String>,
+    /// The exclusive upper bound of `x_value`
+    pub to: Option<String>,
 }
 
 pub struct Output(pub Vec<DataPoint>);
@@ -16,47 +29,134 @@ pub struct Output(pub Vec<DataPoint>);
 pub struct DataPoint {
     pub chart_id: String,
     pub created_at: String,
+    pub id: String,
+    pub series_id: Option<String>,
+    pub version: u32,
     pub x_value: String,
-    pub y_value: u32,
+    pub y_value: String,
 }
 
 impl From<DataPointQueryData> for DataPoint {
-    fn from(
-        DataPointQueryData {
+    fn from(data_point: DataPointQueryData) -> Self {
+        let id = data_point.id().to_string();
+        let DataPointQueryData {
             chart_id,
             created_at,
+            series_id,
+            version,
             x_value,
             y_value,
-        }: DataPointQueryData,
-    ) -> Self {
+        } = data_point;
         Self {
             chart_id: chart_id.to_string(),
             created_at: created_at.to_string(),
+            id,
+            series_id: series_id.map(|series_id| series_id.to_string()),
+            version: u32::from(version),
             x_value: x_value.to_string(),
-            y_value: u32::from(y_value),
+            y_value: y_value.to_string(),
         }
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("after")]
+    After(#[source] write_model::value_object::data_point_id::Error),
+    #[error("chart get")]
+    ChartGet(#[source] crate::query_use_case::port::chart_reader::Error),
     #[error("chart id")]
     ChartId(#[source] write_model::value_object::chart_id::Error),
     #[error("data point list")]
     DataPointList(#[source] crate::query_use_case::port::data_point_reader::Error),
+    #[error("from")]
+    From(#[source] write_model::value_object::x_value::Error),
+    #[error("series id")]
+    SeriesId(#[source] write_model::value_object::series_id::Error),
+    #[error("to")]
+    To(#[source] write_model::value_object::x_value::Error),
 }
 
 #[async_trait::async_trait]
-pub trait ListDataPoints: HasDataPointReader {
-    async fn execute(&self, Input { chart_id }: Input) -> Result<Output, Error> {
+pub trait ListDataPoints: HasChartReader + HasDataPointReader {
+    async fn execute(
+        &self,
+        Input {
+            after,
+            chart_id,
+            from,
+            limit,
+            series_id,
+            to,
+        }: Input,
+    ) -> Result<Output, Error> {
+        let chart_reader = self.chart_reader();
         let data_point_reader = self.data_point_reader();
         let chart_id = ChartId::from_str(&chart_id).map_err(Error::ChartId)?;
-        data_point_reader
-            .list(chart_id)
-            .await
-            .map(|data_points| data_points.into_iter().map(DataPoint::from).collect())
-            .map(Output)
-            .map_err(Error::DataPointList)
+        let series_id = series_id
+            .as_deref()
+            .map(SeriesId::from_str)
+            .transpose()
+            .map_err(Error::SeriesId)?;
+        let range = XValueRange {
+            from: from
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::From)?,
+            to: to
+                .as_deref()
+                .map(XValue::from_str)
+                .transpose()
+                .map_err(Error::To)?,
+        };
+        let mut after = after
+            .as_deref()
+            .map(DataPointId::from_str)
+            .transpose()
+            .map_err(Error::After)?
+            .map(DataPointCursor::from);
+        // the data points of a deleted chart or of a deleted series are hidden
+        let Some(chart) = chart_reader.get(chart_id).await.map_err(Error::ChartGet)? else {
+            return Ok(Output(vec![]));
+        };
+        let series = series_id.map_or(SeriesFilter::All, SeriesFilter::Series);
+        let Some(limit) = limit else {
+            return data_point_reader
+                .list(chart_id, series, range)
+                .await
+                .map(|data_points| {
+                    data_points
+                        .into_iter()
+                        .filter(|data_point| chart.shows_series(data_point.series_id))
+                        .map(DataPoint::from)
+                        .collect()
+                })
+                .map(Output)
+                .map_err(Error::DataPointList);
+        };
+        // the hidden data points do not count, so the pages are read until `limit` are shown
+        let mut data_points = vec![];
+        while data_points.len() < limit {
+            let page = data_point_reader
+                .list_page(chart_id, series, range, after, limit - data_points.len())
+                .await
+                .map_err(Error::DataPointList)?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            let last_page = page.len() < limit - data_points.len();
+            after = Some(DataPointCursor::from(last));
+            data_points.extend(
+                page.into_iter()
+                    .filter(|data_point| chart.shows_series(data_point.series_id))
+                    .map(DataPoint::from),
+            );
+            if last_page {
+                break;
+            }
+        }
+        Ok(Output(data_points))
     }
 }
 
@@ -64,6 +164,10 @@ pub trait ListDataPoints: HasDataPointReader {
 mockall::mock! {
     pub ListDataPoints {}
 
+    impl HasChartReader for ListDataPoints {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync>;
+    }
+
     impl HasDataPointReader for ListDataPoints {
         fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync>;
     }
@@ -80,8 +184,148 @@ pub trait HasListDataPoints {
 
 #[cfg(test)]
 mod tests {
+    use write_model::value_object::{DateTime, Version, YValue};
+
+    use crate::port::{
+        chart_reader, data_point_reader, ChartListQuery, ChartQueryData, SeriesQueryData,
+    };
+
     use super::*;
 
+    struct InMemoryChartReader(Vec<ChartQueryData>);
+
+    #[async_trait::async_trait]
+    impl ChartReader for InMemoryChartReader {
+        async fn get(&self, id: ChartId) -> Result<Option<ChartQueryData>, chart_reader::Error> {
+            Ok(self.0.iter().find(|chart| chart.id == id).cloned())
+        }
+
+        async fn list(
+            &self,
+            _: ChartListQuery,
+        ) -> Result<Vec<ChartQueryData>, chart_reader::Error> {
+            unreachable!()
+        }
+    }
+
+    /// Uses the default `list_page`
+    struct InMemoryDataPointReader(Vec<DataPointQueryData>);
+
+    #[async_trait::async_trait]
+    impl DataPointReader for InMemoryDataPointReader {
+        async fn get(
+            &self,
+            _: DataPointId,
+        ) -> Result<Option<DataPointQueryData>, data_point_reader::Error> {
+            unreachable!()
+        }
+
+        async fn list(
+            &self,
+            chart_id: ChartId,
+            series: SeriesFilter,
+            range: XValueRange,
+        ) -> Result<Vec<DataPointQueryData>, data_point_reader::Error> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|data_point| {
+                    data_point.chart_id == chart_id
+                        && series.matches(data_point.series_id)
+                        && range.contains(data_point.x_value)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    struct TestListDataPoints(Arc<InMemoryChartReader>, Arc<InMemoryDataPointReader>);
+
+    impl HasChartReader for TestListDataPoints {
+        fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync> {
+            self.0.clone()
+        }
+    }
+
+    impl HasDataPointReader for TestListDataPoints {
+        fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync> {
+            self.1.clone()
+        }
+    }
+
+    impl ListDataPoints for TestListDataPoints {}
+
+    #[tokio::test]
+    async fn test_pages() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let series_id = SeriesId::generate();
+        let deleted_series_id = SeriesId::generate();
+        let chart = ChartQueryData {
+            created_at: DateTime::now(),
+            id: chart_id,
+            series: vec![SeriesQueryData {
+                id: series_id,
+                name: "name".to_string(),
+            }],
+            title: "title".to_string(),
+            updated_at: DateTime::now(),
+            version: Version::new(),
+        };
+        let data_points = [
+            (Some(series_id), "2020-01-01"),
+            (None, "2020-01-01"),
+            (Some(deleted_series_id), "2020-01-02"),
+            (Some(deleted_series_id), "2020-01-03"),
+            (None, "2020-01-03"),
+        ]
+        .into_iter()
+        .map(|(series_id, x_value)| {
+            Ok(DataPointQueryData {
+                chart_id,
+                created_at: DateTime::now(),
+                series_id,
+                version: Version::new(),
+                x_value: XValue::from_str(x_value)?,
+                y_value: YValue::from_str("1")?,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+        let use_case = TestListDataPoints(
+            Arc::new(InMemoryChartReader(vec![chart])),
+            Arc::new(InMemoryDataPointReader(data_points)),
+        );
+
+        let mut ids = vec![];
+        let mut after = None;
+        loop {
+            let Output(page) = use_case
+                .execute(Input {
+                    after: after.clone(),
+                    chart_id: chart_id.to_string(),
+                    from: None,
+                    limit: Some(2),
+                    series_id: None,
+                    to: None,
+                })
+                .await?;
+            after = page.last().map(|data_point| data_point.id.clone());
+            let last_page = page.len() < 2;
+            ids.extend(page.into_iter().map(|data_point| data_point.id));
+            if last_page {
+                break;
+            }
+        }
+        assert_eq!(
+            ids,
+            vec![
+                format!("{}:2020-01-01", chart_id),
+                format!("{}:{}:2020-01-01", chart_id, series_id),
+                format!("{}:2020-01-03", chart_id),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_mock() {
         let mut mock = MockListDataPoints::new();
@@ -89,8 +333,11 @@ mod tests {
             Ok(Output(vec![DataPoint {
                 chart_id: "chart_id".to_string(),
                 created_at: "2021-08-21T00:00:00Z".to_string(),
+                id: "chart_id:2020-01-02".to_string(),
+                series_id: None,
+                version: 1,
                 x_value: "2020-01-02".to_string(),
-                y_value: 2,
+                y_value: "2".to_string(),
             }]))
         });
     }
@@ -1,5 +1,11 @@
 pub mod chart_reader;
 pub mod data_point_reader;
 
-pub use self::chart_reader::{ChartQueryData, ChartReader, HasChartReader};
-pub use self::data_point_reader::{DataPointQueryData, DataPointReader, HasDataPointReader};
+pub use self::chart_reader::{
+    ChartCursor, ChartListQuery, ChartQueryData, ChartReader, ChartSortKey, HasChartReader,
+    SeriesQueryData, SortOrder,
+};
+pub use self::data_point_reader::{
+    DataPointCursor, DataPointQueryData, DataPointReader, HasDataPointReader, SeriesFilter,
+    XValueRange,
+};
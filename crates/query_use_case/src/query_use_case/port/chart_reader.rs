@@ -1,24 +1,222 @@
 use std::sync::Arc;
 
-use write_model::value_object::{ChartId, DateTime};
+use write_model::value_object::{ChartId, DateTime, SeriesId, Version};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ChartQueryData {
     pub created_at: DateTime,
     pub id: ChartId,
+    pub series: Vec<SeriesQueryData>,
     pub title: String,
+    pub updated_at: DateTime,
+    pub version: Version,
+}
+
+impl ChartQueryData {
+    /// Whether the data points of the series are shown. `None` is the default series.
+    /// Deleting a series does not delete its data points; they are kept by the stores but hidden
+    pub fn shows_series(&self, series_id: Option<SeriesId>) -> bool {
+        series_id.map_or(true, |series_id| {
+            self.series.iter().any(|series| series.id == series_id)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeriesQueryData {
+    pub id: SeriesId,
+    pub name: String,
+}
+
+/// The field the charts are listed by. Ties are broken by `id`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ChartSortKey {
+    #[default]
+    CreatedAt,
+    Title,
+    UpdatedAt,
+}
+
+impl ChartSortKey {
+    /// Returns the value of the field as a string. Timestamps are ordered by their string form
+    pub fn value(&self, chart: &ChartQueryData) -> String {
+        match self {
+            ChartSortKey::CreatedAt => chart.created_at.to_string(),
+            ChartSortKey::Title => chart.title.clone(),
+            ChartSortKey::UpdatedAt => chart.updated_at.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// The position of the last chart of the previous page
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartCursor {
+    pub id: ChartId,
+    /// The value of the sort key (see `ChartSortKey::value`)
+    pub key: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChartListQuery {
+    /// Lists the charts after this position. `None` lists from the first chart
+    pub after: Option<ChartCursor>,
+    pub limit: usize,
+    pub order: SortOrder,
+    pub sort_key: ChartSortKey,
+}
+
+impl ChartListQuery {
+    pub fn cursor(&self, chart: &ChartQueryData) -> ChartCursor {
+        ChartCursor {
+            id: chart.id,
+            key: self.sort_key.value(chart),
+        }
+    }
+
+    /// Sorts the charts, skips the charts up to `after` and takes `limit` charts.
+    /// For stores that keep every chart in memory
+    pub fn apply<I>(&self, charts: I) -> Vec<ChartQueryData>
+    where
+        I: IntoIterator<Item = ChartQueryData>,
+    {
+        let compare = |a: &(String, String), b: &(String, String)| match self.order {
+            SortOrder::Ascending => a.cmp(b),
+            SortOrder::Descending => b.cmp(a),
+        };
+        let after = self
+            .after
+            .as_ref()
+            .map(|cursor| (cursor.key.clone(), cursor.id.to_string()));
+        let mut charts = charts
+            .into_iter()
+            .map(|chart| ((self.sort_key.value(&chart), chart.id.to_string()), chart))
+            .filter(|(key, _)| {
+                after
+                    .as_ref()
+                    .map_or(true, |after| compare(after, key).is_lt())
+            })
+            .collect::<Vec<((String, String), ChartQueryData)>>();
+        charts.sort_by(|(a, _), (b, _)| compare(a, b));
+        charts
+            .into_iter()
+            .take(self.limit)
+            .map(|(_, chart)| chart)
+            .collect()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error(transparent)]
-pub struct Error(#[from] Box<dyn std::error::Error + Send + Sync>);
+pub enum Error {
+    /// The stored data cannot be read back (e.g. an unparsable document)
+    #[error("corrupted")]
+    Corrupted(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The storage cannot be reached. Reads can be retried
+    #[error("unavailable")]
+    Unavailable(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    /// A conflict or a missing stream is not expected on reads. They are `Other`
+    pub fn new(
+        kind: store_error::ErrorKind,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Self {
+        match kind {
+            store_error::ErrorKind::Corrupted => Self::Corrupted(error),
+            store_error::ErrorKind::Unavailable => Self::Unavailable(error),
+            store_error::ErrorKind::Conflict
+            | store_error::ErrorKind::NotFound
+            | store_error::ErrorKind::Other => Self::Other(error),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 pub trait ChartReader {
     async fn get(&self, id: ChartId) -> Result<Option<ChartQueryData>, Error>;
-    async fn list(&self) -> Result<Vec<ChartQueryData>, Error>;
+    /// Lists the charts in the order of `query.sort_key` and `query.order`
+    async fn list(&self, query: ChartListQuery) -> Result<Vec<ChartQueryData>, Error>;
 }
 
 pub trait HasChartReader {
     fn chart_reader(&self) -> Arc<dyn ChartReader + Send + Sync>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_list_query_apply() -> anyhow::Result<()> {
+        let build_chart = |title: &str, updated_at: i64| -> anyhow::Result<ChartQueryData> {
+            Ok(ChartQueryData {
+                created_at: DateTime::from_unix_timestamp_millis(0)?,
+                id: ChartId::generate(),
+                series: vec![],
+                title: title.to_string(),
+                updated_at: DateTime::from_unix_timestamp_millis(updated_at)?,
+                version: Version::new(),
+            })
+        };
+        let charts = vec![
+            build_chart("b", 2)?,
+            build_chart("a", 3)?,
+            build_chart("c", 1)?,
+        ];
+        let titles = |charts: Vec<ChartQueryData>| {
+            charts
+                .into_iter()
+                .map(|chart| chart.title)
+                .collect::<Vec<String>>()
+        };
+
+        let query = ChartListQuery {
+            after: None,
+            limit: 2,
+            order: SortOrder::Ascending,
+            sort_key: ChartSortKey::Title,
+        };
+        let page = query.apply(charts.clone());
+        assert_eq!(titles(page.clone()), vec!["a", "b"]);
+        let query = ChartListQuery {
+            after: Some(query.cursor(&page[1])),
+            ..query
+        };
+        assert_eq!(titles(query.apply(charts.clone())), vec!["c"]);
+
+        let query = ChartListQuery {
+            after: None,
+            limit: 3,
+            order: SortOrder::Descending,
+            sort_key: ChartSortKey::UpdatedAt,
+        };
+        assert_eq!(titles(query.apply(charts.clone())), vec!["a", "b", "c"]);
+
+        // ties are broken by id
+        let query = ChartListQuery {
+            after: None,
+            limit: 1,
+            order: SortOrder::Ascending,
+            sort_key: ChartSortKey::CreatedAt,
+        };
+        let page = query.apply(charts.clone());
+        let query = ChartListQuery {
+            after: Some(query.cursor(&page[0])),
+            limit: 3,
+            ..query
+        };
+        let rest = query.apply(charts.clone());
+        assert_eq!(rest.len(), 2);
+        assert!(rest.iter().all(|chart| chart.id != page[0].id));
+        Ok(())
+    }
+}
@@ -1,25 +1,181 @@
 use std::sync::Arc;
 
-use write_model::value_object::{ChartId, DataPointId, DateTime, XValue, YValue};
+use write_model::value_object::{
+    ChartId, DataPointId, DateTime, SeriesId, Version, XValue, YValue,
+};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DataPointQueryData {
     pub chart_id: ChartId,
     pub created_at: DateTime,
+    pub series_id: Option<SeriesId>,
+    pub version: Version,
     pub x_value: XValue,
     pub y_value: YValue,
 }
 
+impl DataPointQueryData {
+    pub fn id(&self) -> DataPointId {
+        DataPointId::new(self.chart_id, self.series_id, self.x_value)
+    }
+}
+
+/// Bounds of `XValue`. `from` is inclusive and `to` is exclusive. `None` is unbounded
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct XValueRange {
+    pub from: Option<XValue>,
+    pub to: Option<XValue>,
+}
+
+impl XValueRange {
+    pub fn contains(&self, x_value: XValue) -> bool {
+        self.from.map_or(true, |from| from <= x_value) && self.to.map_or(true, |to| x_value < to)
+    }
+}
+
+/// The series whose data points are listed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SeriesFilter {
+    /// Every series, including the default series
+    All,
+    /// The default series (the data points without a series id)
+    Default,
+    Series(SeriesId),
+}
+
+impl SeriesFilter {
+    pub fn matches(&self, series_id: Option<SeriesId>) -> bool {
+        match self {
+            SeriesFilter::All => true,
+            SeriesFilter::Default => series_id.is_none(),
+            SeriesFilter::Series(id) => series_id == Some(*id),
+        }
+    }
+}
+
+/// The position of a data point in a page of `DataPointReader::list_page`. Data points are ordered
+/// by `x_value` and then by `series_id`, the default series first
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DataPointCursor {
+    pub x_value: XValue,
+    pub series_id: Option<SeriesId>,
+}
+
+impl From<&DataPointQueryData> for DataPointCursor {
+    fn from(data_point: &DataPointQueryData) -> Self {
+        Self {
+            x_value: data_point.x_value,
+            series_id: data_point.series_id,
+        }
+    }
+}
+
+impl From<DataPointId> for DataPointCursor {
+    fn from(id: DataPointId) -> Self {
+        Self {
+            x_value: id.x_value(),
+            series_id: id.series_id(),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
-#[error(transparent)]
-pub struct Error(#[from] Box<dyn std::error::Error + Send + Sync>);
+pub enum Error {
+    /// The stored data cannot be read back (e.g. an unparsable document)
+    #[error("corrupted")]
+    Corrupted(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The storage cannot be reached. Reads can be retried
+    #[error("unavailable")]
+    Unavailable(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    /// A conflict or a missing stream is not expected on reads. They are `Other`
+    pub fn new(
+        kind: store_error::ErrorKind,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Self {
+        match kind {
+            store_error::ErrorKind::Corrupted => Self::Corrupted(error),
+            store_error::ErrorKind::Unavailable => Self::Unavailable(error),
+            store_error::ErrorKind::Conflict
+            | store_error::ErrorKind::NotFound
+            | store_error::ErrorKind::Other => Self::Other(error),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 pub trait DataPointReader {
     async fn get(&self, id: DataPointId) -> Result<Option<DataPointQueryData>, Error>;
-    async fn list(&self, chart_id: ChartId) -> Result<Vec<DataPointQueryData>, Error>;
+    /// Lists the data points of the chart in `range`. The data points of deleted series are
+    /// listed too (see `ChartQueryData::shows_series`)
+    async fn list(
+        &self,
+        chart_id: ChartId,
+        series: SeriesFilter,
+        range: XValueRange,
+    ) -> Result<Vec<DataPointQueryData>, Error>;
+    /// Lists at most `limit` data points of `list` after `after`, ordered as `DataPointCursor`.
+    /// The default implementation reads the whole range. Stores that can seek override it
+    async fn list_page(
+        &self,
+        chart_id: ChartId,
+        series: SeriesFilter,
+        range: XValueRange,
+        after: Option<DataPointCursor>,
+        limit: usize,
+    ) -> Result<Vec<DataPointQueryData>, Error> {
+        let mut data_points = self
+            .list(chart_id, series, range)
+            .await?
+            .into_iter()
+            .filter(|data_point| {
+                after.map_or(true, |after| after < DataPointCursor::from(data_point))
+            })
+            .collect::<Vec<_>>();
+        data_points.sort_by_key(|data_point| DataPointCursor::from(data_point));
+        data_points.truncate(limit);
+        Ok(data_points)
+    }
 }
 
 pub trait HasDataPointReader {
     fn data_point_reader(&self) -> Arc<dyn DataPointReader + Send + Sync>;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_x_value_range_contains() -> anyhow::Result<()> {
+        let x = |s: &str| XValue::from_str(s);
+        let range = XValueRange {
+            from: Some(x("2020-01-02")?),
+            to: Some(x("2020-01-04")?),
+        };
+        assert!(!range.contains(x("2020-01-01")?));
+        assert!(range.contains(x("2020-01-02")?));
+        assert!(range.contains(x("2020-01-03")?));
+        assert!(!range.contains(x("2020-01-04")?));
+        assert!(XValueRange::default().contains(x("2020-01-01")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_series_filter_matches() {
+        let series_id = SeriesId::generate();
+        assert!(SeriesFilter::All.matches(None));
+        assert!(SeriesFilter::All.matches(Some(series_id)));
+        assert!(SeriesFilter::Default.matches(None));
+        assert!(!SeriesFilter::Default.matches(Some(series_id)));
+        assert!(!SeriesFilter::Series(series_id).matches(None));
+        assert!(SeriesFilter::Series(series_id).matches(Some(series_id)));
+        assert!(!SeriesFilter::Series(series_id).matches(Some(SeriesId::generate())));
+    }
+}
@@ -0,0 +1,280 @@
+//! Builds the query data from the events. Every store that keeps its own query data projects
+//! the events with these functions, so that the stores return the same query data
+use write_model::{
+    event::{
+        ChartCreated, ChartEvent, ChartEventData, ChartSeriesCreated, ChartSeriesDeleted,
+        ChartSeriesRenamed, ChartUpdated, DataPointCreated, DataPointEvent, DataPointEventData,
+        DataPointUpdated,
+    },
+    value_object::{ChartId, DataPointId, SeriesId},
+};
+
+use crate::port::{ChartQueryData, DataPointQueryData, SeriesQueryData};
+
+/// The event does not apply to the query data built from the previous events
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("chart not found ({0})")]
+    ChartNotFound(ChartId),
+    #[error("data point not found ({0})")]
+    DataPointNotFound(DataPointId),
+    #[error("series not found ({0})")]
+    SeriesNotFound(SeriesId),
+}
+
+/// Applies the event to the chart (`None` if the chart does not exist or is deleted)
+pub fn project_chart(
+    chart: Option<ChartQueryData>,
+    event: &ChartEvent,
+) -> Result<Option<ChartQueryData>, Error> {
+    if let ChartEventData::Created(ChartCreated { title }) = &event.data {
+        return Ok(Some(ChartQueryData {
+            created_at: event.at,
+            id: event.stream_id,
+            series: vec![],
+            title: title.clone(),
+            updated_at: event.at,
+            version: event.version,
+        }));
+    }
+    let mut chart = chart.ok_or(Error::ChartNotFound(event.stream_id))?;
+    match &event.data {
+        ChartEventData::Created(_) => unreachable!(),
+        ChartEventData::Deleted(_) => return Ok(None),
+        ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id }) => {
+            chart.series.push(SeriesQueryData {
+                id: *series_id,
+                name: name.clone(),
+            });
+        }
+        ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+            chart.series.retain(|series| series.id != *series_id);
+        }
+        ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+            let series = chart
+                .series
+                .iter_mut()
+                .find(|series| series.id == *series_id)
+                .ok_or(Error::SeriesNotFound(*series_id))?;
+            series.name.clone_from(name);
+        }
+        ChartEventData::Updated(ChartUpdated { title }) => {
+            chart.title.clone_from(title);
+        }
+    }
+    chart.updated_at = event.at;
+    chart.version = event.version;
+    Ok(Some(chart))
+}
+
+/// Applies the event to the data point (`None` if the data point does not exist or is deleted)
+pub fn project_data_point(
+    data_point: Option<DataPointQueryData>,
+    event: &DataPointEvent,
+) -> Result<Option<DataPointQueryData>, Error> {
+    Ok(match &event.data {
+        DataPointEventData::Created(DataPointCreated { value }) => Some(DataPointQueryData {
+            chart_id: event.stream_id.chart_id(),
+            created_at: event.at,
+            series_id: event.stream_id.series_id(),
+            version: event.version,
+            x_value: event.stream_id.x_value(),
+            y_value: *value,
+        }),
+        DataPointEventData::Deleted(_) => None,
+        DataPointEventData::Updated(DataPointUpdated { value }) => {
+            let mut data_point = data_point.ok_or(Error::DataPointNotFound(event.stream_id))?;
+            data_point.version = event.version;
+            data_point.y_value = *value;
+            Some(data_point)
+        }
+    })
+}
+
+/// Applies the event to the chart in `charts`. For stores that keep every chart in memory
+pub fn apply_chart_event(
+    charts: &mut Vec<ChartQueryData>,
+    event: &ChartEvent,
+) -> Result<(), Error> {
+    let index = charts.iter().position(|chart| chart.id == event.stream_id);
+    let chart = project_chart(index.map(|index| charts[index].clone()), event)?;
+    replace(charts, index, chart);
+    Ok(())
+}
+
+/// Applies the event to the data point in `data_points`. For stores that keep every data point in memory
+pub fn apply_data_point_event(
+    data_points: &mut Vec<DataPointQueryData>,
+    event: &DataPointEvent,
+) -> Result<(), Error> {
+    let index = data_points
+        .iter()
+        .position(|data_point| data_point.id() == event.stream_id);
+    let data_point = project_data_point(index.map(|index| data_points[index].clone()), event)?;
+    replace(data_points, index, data_point);
+    Ok(())
+}
+
+fn replace<T>(items: &mut Vec<T>, index: Option<usize>, item: Option<T>) {
+    match (index, item) {
+        (None, None) => {}
+        (None, Some(item)) => items.push(item),
+        (Some(index), None) => {
+            items.remove(index);
+        }
+        (Some(index), Some(item)) => items[index] = item,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use write_model::{
+        event::ChartDeleted,
+        value_object::{DateTime, EventId, Version, YValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_project_chart() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let series_id = SeriesId::generate();
+        let at = DateTime::from_unix_timestamp_millis(0)?;
+        let event = |version: u32, data: ChartEventData| ChartEvent {
+            at,
+            data,
+            id: EventId::generate(),
+            stream_id: chart_id,
+            version: Version::try_from(version).expect("version to be valid"),
+        };
+        let mut charts = vec![];
+        apply_chart_event(
+            &mut charts,
+            &event(
+                1,
+                ChartEventData::Created(ChartCreated {
+                    title: "title1".to_owned(),
+                }),
+            ),
+        )?;
+        apply_chart_event(
+            &mut charts,
+            &event(
+                2,
+                ChartEventData::SeriesCreated(ChartSeriesCreated {
+                    name: "series1".to_owned(),
+                    series_id,
+                }),
+            ),
+        )?;
+        apply_chart_event(
+            &mut charts,
+            &event(
+                3,
+                ChartEventData::SeriesRenamed(ChartSeriesRenamed {
+                    name: "series2".to_owned(),
+                    series_id,
+                }),
+            ),
+        )?;
+        assert_eq!(charts.len(), 1);
+        assert_eq!(charts[0].series[0].name, "series2");
+        assert_eq!(charts[0].version, Version::try_from(3_u32)?);
+
+        assert!(matches!(
+            apply_chart_event(
+                &mut charts,
+                &event(
+                    4,
+                    ChartEventData::SeriesDeleted(ChartSeriesDeleted {
+                        series_id: SeriesId::generate(),
+                    }),
+                ),
+            ),
+            Ok(())
+        ));
+        assert!(matches!(
+            apply_chart_event(
+                &mut charts,
+                &event(
+                    5,
+                    ChartEventData::SeriesRenamed(ChartSeriesRenamed {
+                        name: "series3".to_owned(),
+                        series_id: SeriesId::generate(),
+                    }),
+                ),
+            ),
+            Err(Error::SeriesNotFound(_))
+        ));
+
+        apply_chart_event(
+            &mut charts,
+            &event(5, ChartEventData::Deleted(ChartDeleted {})),
+        )?;
+        assert!(charts.is_empty());
+        assert!(matches!(
+            project_chart(
+                None,
+                &event(
+                    6,
+                    ChartEventData::Updated(ChartUpdated {
+                        title: "title2".to_owned(),
+                    }),
+                ),
+            ),
+            Err(Error::ChartNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_data_point() -> anyhow::Result<()> {
+        let id = DataPointId::new(ChartId::generate(), None, "2020-01-02".parse()?);
+        let at = DateTime::from_unix_timestamp_millis(0)?;
+        let event = |version: u32, data: DataPointEventData| DataPointEvent {
+            at,
+            data,
+            id: EventId::generate(),
+            stream_id: id,
+            version: Version::try_from(version).expect("version to be valid"),
+        };
+        let mut data_points = vec![];
+        apply_data_point_event(
+            &mut data_points,
+            &event(
+                1,
+                DataPointEventData::Created(DataPointCreated {
+                    value: YValue::from_str("1")?,
+                }),
+            ),
+        )?;
+        apply_data_point_event(
+            &mut data_points,
+            &event(
+                2,
+                DataPointEventData::Updated(DataPointUpdated {
+                    value: YValue::from_str("2.5")?,
+                }),
+            ),
+        )?;
+        assert_eq!(data_points.len(), 1);
+        assert_eq!(data_points[0].id(), id);
+        assert_eq!(data_points[0].y_value, YValue::from_str("2.5")?);
+        assert_eq!(data_points[0].version, Version::try_from(2_u32)?);
+        assert!(matches!(
+            project_data_point(
+                None,
+                &event(
+                    3,
+                    DataPointEventData::Updated(DataPointUpdated {
+                        value: YValue::from_str("3")?,
+                    }),
+                ),
+            ),
+            Err(Error::DataPointNotFound(_))
+        ));
+        Ok(())
+    }
+}
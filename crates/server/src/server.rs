@@ -5,11 +5,14 @@ use std::{env, net::Ipv4Addr};
 
 use command_use_case::{
     create_chart::HasCreateChart, create_data_point::HasCreateDataPoint,
-    delete_chart::HasDeleteChart, delete_data_point::HasDeleteDataPoint,
+    create_series::HasCreateSeries, delete_chart::HasDeleteChart,
+    delete_data_point::HasDeleteDataPoint, delete_series::HasDeleteSeries,
+    import_data_points::HasImportDataPoints, rename_series::HasRenameSeries,
     update_chart::HasUpdateChart, update_data_point::HasUpdateDataPoint,
 };
 use query_use_case::{
-    get_chart::HasGetChart, get_data_point::HasGetDataPoint, list_charts::HasListCharts,
+    aggregate_data_points::HasAggregateDataPoints, get_chart::HasGetChart,
+    get_chart_stats::HasGetChartStats, get_data_point::HasGetDataPoint, list_charts::HasListCharts,
     list_data_points::HasListDataPoints,
 };
 
@@ -27,14 +30,20 @@ pub enum Error {
 
 pub async fn run<
     T: Clone
+        + HasAggregateDataPoints
         + HasCreateChart
         + HasCreateDataPoint
+        + HasCreateSeries
         + HasDeleteChart
         + HasDeleteDataPoint
+        + HasDeleteSeries
         + HasGetChart
+        + HasGetChartStats
         + HasGetDataPoint
+        + HasImportDataPoints
         + HasListCharts
         + HasListDataPoints
+        + HasRenameSeries
         + HasUpdateChart
         + HasUpdateDataPoint
         + Send
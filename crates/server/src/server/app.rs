@@ -45,6 +45,16 @@ impl command_use_case::create_data_point::HasCreateDataPoint for App {
     }
 }
 
+impl command_use_case::create_series::CreateSeries for App {}
+
+impl command_use_case::create_series::HasCreateSeries for App {
+    fn create_series(
+        &self,
+    ) -> Arc<dyn command_use_case::create_series::CreateSeries + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
 impl command_use_case::delete_chart::DeleteChart for App {}
 
 impl command_use_case::delete_chart::HasDeleteChart for App {
@@ -63,18 +73,48 @@ impl command_use_case::delete_data_point::HasDeleteDataPoint for App {
     }
 }
 
+impl command_use_case::delete_series::DeleteSeries for App {}
+
+impl command_use_case::delete_series::HasDeleteSeries for App {
+    fn delete_series(
+        &self,
+    ) -> Arc<dyn command_use_case::delete_series::DeleteSeries + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
 impl command_use_case::port::HasChartRepository for App {
     fn chart_repository(&self) -> Arc<dyn ChartRepository + Send + Sync> {
         self.chart_repository.clone()
     }
 }
 
+impl command_use_case::import_data_points::HasImportDataPoints for App {
+    fn import_data_points(
+        &self,
+    ) -> Arc<dyn command_use_case::import_data_points::ImportDataPoints + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
+impl command_use_case::import_data_points::ImportDataPoints for App {}
+
 impl command_use_case::port::HasDataPointRepository for App {
     fn data_point_repository(&self) -> Arc<dyn DataPointRepository + Send + Sync> {
         self.data_point_repository.clone()
     }
 }
 
+impl command_use_case::rename_series::HasRenameSeries for App {
+    fn rename_series(
+        &self,
+    ) -> Arc<dyn command_use_case::rename_series::RenameSeries + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
+impl command_use_case::rename_series::RenameSeries for App {}
+
 impl command_use_case::update_chart::HasUpdateChart for App {
     fn update_chart(&self) -> Arc<dyn command_use_case::update_chart::UpdateChart + Send + Sync> {
         Arc::new(self.clone())
@@ -101,6 +141,16 @@ impl query_use_case::get_chart::HasGetChart for App {
     }
 }
 
+impl query_use_case::get_chart_stats::GetChartStats for App {}
+
+impl query_use_case::get_chart_stats::HasGetChartStats for App {
+    fn get_chart_stats(
+        &self,
+    ) -> Arc<dyn query_use_case::get_chart_stats::GetChartStats + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
 impl query_use_case::get_data_point::GetDataPoint for App {}
 
 impl query_use_case::get_data_point::HasGetDataPoint for App {
@@ -111,6 +161,16 @@ impl query_use_case::get_data_point::HasGetDataPoint for App {
     }
 }
 
+impl query_use_case::aggregate_data_points::AggregateDataPoints for App {}
+
+impl query_use_case::aggregate_data_points::HasAggregateDataPoints for App {
+    fn aggregate_data_points(
+        &self,
+    ) -> Arc<dyn query_use_case::aggregate_data_points::AggregateDataPoints + Send + Sync> {
+        Arc::new(self.clone())
+    }
+}
+
 impl query_use_case::list_charts::HasListCharts for App {
     fn list_charts(&self) -> Arc<dyn query_use_case::list_charts::ListCharts + Send + Sync> {
         Arc::new(self.clone())
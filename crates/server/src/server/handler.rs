@@ -1,12 +1,25 @@
+mod aggregate_data_points;
 mod create_chart;
 mod create_data_point;
+mod create_series;
+mod decimal;
 mod delete_chart;
 mod delete_data_point;
+mod delete_series;
+mod etag;
+mod export_chart;
+mod extract;
 mod get_chart;
+mod get_chart_image;
+mod get_chart_stats;
 mod get_data_point;
 mod get_root;
+mod import_data_points;
 mod list_charts;
 mod list_data_points;
+mod problem;
+mod rename_series;
+mod ui;
 mod update_chart;
 mod update_data_point;
 
@@ -14,24 +27,33 @@ use axum::Router;
 
 use command_use_case::{
     create_chart::HasCreateChart, create_data_point::HasCreateDataPoint,
-    delete_chart::HasDeleteChart, delete_data_point::HasDeleteDataPoint,
+    create_series::HasCreateSeries, delete_chart::HasDeleteChart,
+    delete_data_point::HasDeleteDataPoint, delete_series::HasDeleteSeries,
+    import_data_points::HasImportDataPoints, rename_series::HasRenameSeries,
     update_chart::HasUpdateChart, update_data_point::HasUpdateDataPoint,
 };
 use query_use_case::{
-    get_chart::HasGetChart, get_data_point::HasGetDataPoint, list_charts::HasListCharts,
+    aggregate_data_points::HasAggregateDataPoints, get_chart::HasGetChart,
+    get_chart_stats::HasGetChartStats, get_data_point::HasGetDataPoint, list_charts::HasListCharts,
     list_data_points::HasListDataPoints,
 };
 
 pub fn router<
     T: Clone
+        + HasAggregateDataPoints
         + HasCreateChart
         + HasCreateDataPoint
+        + HasCreateSeries
         + HasDeleteChart
         + HasDeleteDataPoint
+        + HasDeleteSeries
         + HasGetChart
+        + HasGetChartStats
         + HasGetDataPoint
+        + HasImportDataPoints
         + HasListCharts
         + HasListDataPoints
+        + HasRenameSeries
         + HasUpdateChart
         + HasUpdateDataPoint
         + Send
@@ -39,15 +61,24 @@ pub fn router<
         + 'static,
 >() -> Router<T> {
     Router::new()
+        .merge(aggregate_data_points::router())
         .merge(create_chart::router())
         .merge(create_data_point::router())
+        .merge(create_series::router())
         .merge(delete_chart::router())
         .merge(delete_data_point::router())
+        .merge(delete_series::router())
+        .merge(export_chart::router())
         .merge(get_chart::router())
+        .merge(get_chart_image::router())
+        .merge(get_chart_stats::router())
         .merge(get_data_point::router())
         .merge(get_root::router())
+        .merge(import_data_points::router())
         .merge(list_charts::router())
         .merge(list_data_points::router())
+        .merge(rename_series::router())
+        .merge(ui::router())
         .merge(update_chart::router())
         .merge(update_data_point::router())
 }
@@ -0,0 +1,320 @@
+use axum::{extract::State, Router};
+
+use query_use_case::{self, aggregate_data_points::HasAggregateDataPoints};
+
+use crate::server::handler::{
+    decimal::{Decimal, InvalidDecimal},
+    extract::{Json, Path, Query},
+    problem::Problem,
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct PathParameters {
+    chart_id: String,
+    #[serde(default)]
+    series_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    from: Option<String>,
+    /// Comma-separated aggregate functions (e.g. `sum,mean`)
+    #[serde(default)]
+    functions: Option<String>,
+    interval: String,
+    #[serde(default)]
+    to: Option<String>,
+}
+
+fn input_from_request(
+    PathParameters {
+        chart_id,
+        series_id,
+    }: PathParameters,
+    QueryParameters {
+        from,
+        functions,
+        interval,
+        to,
+    }: QueryParameters,
+) -> query_use_case::aggregate_data_points::Input {
+    query_use_case::aggregate_data_points::Input {
+        chart_id,
+        from,
+        functions: functions
+            .map(|functions| {
+                functions
+                    .split(',')
+                    .filter(|function| !function.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        interval,
+        series_id,
+        to,
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {
+    buckets: Vec<ResponseBodyBucket>,
+}
+
+impl TryFrom<query_use_case::aggregate_data_points::Output> for ResponseBody {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::aggregate_data_points::Output(buckets): query_use_case::aggregate_data_points::Output,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            buckets: buckets
+                .into_iter()
+                .map(ResponseBodyBucket::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBodyBucket {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mean: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sum: Option<Decimal>,
+}
+
+impl TryFrom<query_use_case::aggregate_data_points::Bucket> for ResponseBodyBucket {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::aggregate_data_points::Bucket {
+            count,
+            key,
+            max,
+            mean,
+            min,
+            sum,
+        }: query_use_case::aggregate_data_points::Bucket,
+    ) -> Result<Self, Self::Error> {
+        let decimal = |value: Option<String>| value.map(|value| value.parse()).transpose();
+        Ok(Self {
+            count,
+            key,
+            max: decimal(max)?,
+            mean: decimal(mean)?,
+            min: decimal(min)?,
+            sum: decimal(sum)?,
+        })
+    }
+}
+
+impl From<query_use_case::aggregate_data_points::Error> for Problem {
+    fn from(error: query_use_case::aggregate_data_points::Error) -> Self {
+        use query_use_case::aggregate_data_points::Error;
+        match &error {
+            Error::ChartGet(e) => Problem::from_chart_reader_error(&error, e),
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::DataPointList(e) => Problem::from_data_point_reader_error(&error, e),
+            Error::From(_) => Problem::invalid_parameter("from", &error),
+            Error::Function => Problem::invalid_parameter("functions", &error),
+            Error::Interval => Problem::invalid_parameter("interval", &error),
+            Error::Overflow => Problem::validation_failed(None, &error),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+            Error::SeriesNotFound(_) => Problem::not_found(error.to_string()),
+            Error::To(_) => Problem::invalid_parameter("to", &error),
+        }
+    }
+}
+
+async fn handler<T: HasAggregateDataPoints>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<Json<ResponseBody>, Problem> {
+    let use_case = state.aggregate_data_points();
+    let input = input_from_request(path_parameters, query_parameters);
+    let output = use_case.execute(input).await.map_err(Problem::from)?;
+    Ok(Json(ResponseBody::try_from(output)?))
+}
+
+pub fn router<T: Clone + HasAggregateDataPoints + Send + Sync + 'static>() -> Router<T> {
+    Router::new()
+        .route(
+            "/charts/:chart_id/rollups",
+            axum::routing::get(handler::<T>),
+        )
+        .route(
+            "/charts/:chart_id/series/:series_id/rollups",
+            axum::routing::get(handler::<T>),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use query_use_case::aggregate_data_points::{Bucket, MockAggregateDataPoints};
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let bucket = build_bucket();
+        let mut mocks = Mocks::with_happy_path_behavior(bucket.clone());
+        mocks.aggregate_data_points = {
+            let bucket = bucket.clone();
+            let mut mock = MockAggregateDataPoints::new();
+            mock.expect_execute().return_once(move |input| {
+                assert_eq!(input.chart_id, "chart_id1");
+                assert_eq!(input.from, Some("2020-01-01".to_string()));
+                assert_eq!(input.functions, vec!["sum", "mean"]);
+                assert_eq!(input.interval, "month");
+                assert_eq!(input.series_id, Some("series_id1".to_string()));
+                assert_eq!(input.to, None);
+                Ok(query_use_case::aggregate_data_points::Output(vec![bucket]))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            "/charts/chart_id1/series/series_id1/rollups?interval=month&functions=sum,mean&from=2020-01-01",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_string().await?,
+            r#"{"buckets":[{"key":"2020-01","mean":1.75,"sum":3.5}]}"#
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_interval() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior(build_bucket());
+        mocks.aggregate_data_points = {
+            let mut mock = MockAggregateDataPoints::new();
+            mock.expect_execute()
+                .return_once(|_| Err(query_use_case::aggregate_data_points::Error::Interval));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/rollups?interval=day")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("interval".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chart_not_found() -> anyhow::Result<()> {
+        let chart_id = write_model::value_object::ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior(build_bucket());
+        mocks.aggregate_data_points = {
+            let mut mock = MockAggregateDataPoints::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(query_use_case::aggregate_data_points::Error::ChartNotFound(
+                    chart_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&format!("/charts/{}/rollups?interval=day", chart_id))?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series_not_found() -> anyhow::Result<()> {
+        let chart_id = write_model::value_object::ChartId::generate();
+        let series_id = write_model::value_object::SeriesId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior(build_bucket());
+        mocks.aggregate_data_points = {
+            let mut mock = MockAggregateDataPoints::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(query_use_case::aggregate_data_points::Error::SeriesNotFound(series_id))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&format!(
+            "/charts/{}/series/{}/rollups?interval=day",
+            chart_id, series_id
+        ))?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        aggregate_data_points: Arc<MockAggregateDataPoints>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior(bucket: Bucket) -> Self {
+            let mut aggregate_data_points = MockAggregateDataPoints::new();
+            aggregate_data_points
+                .expect_execute()
+                .return_once(move |_| {
+                    Ok(query_use_case::aggregate_data_points::Output(vec![bucket]))
+                });
+            Self {
+                aggregate_data_points: Arc::new(aggregate_data_points),
+            }
+        }
+    }
+
+    impl query_use_case::aggregate_data_points::HasAggregateDataPoints for Mocks {
+        fn aggregate_data_points(
+            &self,
+        ) -> Arc<dyn query_use_case::aggregate_data_points::AggregateDataPoints + Send + Sync>
+        {
+            self.aggregate_data_points.clone()
+        }
+    }
+
+    fn build_bucket() -> Bucket {
+        Bucket {
+            count: None,
+            key: "2020-01".to_string(),
+            max: None,
+            mean: Some("1.75".to_string()),
+            min: None,
+            sum: Some("3.5".to_string()),
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?)
+    }
+}
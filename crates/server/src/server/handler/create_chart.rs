@@ -1,7 +1,9 @@
-use axum::{extract::State, http::StatusCode, Json, Router};
+use axum::{extract::State, Router};
 
 use command_use_case::{self, create_chart::HasCreateChart};
 
+use crate::server::handler::{extract::Json, problem::Problem};
+
 #[derive(serde::Deserialize, serde::Serialize)]
 struct RequestBody {
     title: String,
@@ -26,15 +28,25 @@ impl From<command_use_case::create_chart::Output> for ResponseBody {
     }
 }
 
+impl From<command_use_case::create_chart::Error> for Problem {
+    fn from(error: command_use_case::create_chart::Error) -> Self {
+        use command_use_case::create_chart::Error;
+        match &error {
+            Error::ChartCreate(e) => Problem::from_chart_error(&error, e),
+            Error::ChartStore(e) => Problem::from_chart_repository_error(&error, e),
+        }
+    }
+}
+
 async fn handler<T: HasCreateChart>(
     State(state): State<T>,
     Json(body): Json<RequestBody>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<Json<ResponseBody>, Problem> {
     let use_case = state.create_chart();
     let output = use_case
         .execute(command_use_case::create_chart::Input::from(body))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(Problem::from)?;
     Ok(Json(ResponseBody::from(output)))
 }
 
@@ -46,6 +58,7 @@ pub fn router<T: Clone + HasCreateChart + Send + Sync + 'static>() -> Router<T>
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use command_use_case::create_chart::MockCreateChart;
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -86,7 +99,41 @@ mod tests {
         let request = build_request(&RequestBody { title })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_title() -> anyhow::Result<()> {
+        let title = "".to_string();
+        let chart_id = "chart_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(title.clone(), chart_id);
+        mocks.create_chart = {
+            let mut mock = MockCreateChart::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::create_chart::Error::ChartCreate(
+                    write_model::aggregate::chart::Error::InvalidTitle,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&RequestBody { title })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("chart create: invalid title".to_string()),
+                field: Some("title".to_string()),
+                status: 422,
+                title: "Validation failed".to_string(),
+                r#type: "/problems/validation-failed".to_string(),
+            }
+        );
         Ok(())
     }
 
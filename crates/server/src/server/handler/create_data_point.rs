@@ -1,11 +1,13 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
-};
+use axum::{extract::State, Router};
 
 use command_use_case::{self, create_data_point::HasCreateDataPoint};
 
+use crate::server::handler::{
+    decimal::Decimal,
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     chart_id: String,
@@ -13,8 +15,10 @@ struct PathParameters {
 
 #[derive(serde::Deserialize, serde::Serialize)]
 struct RequestBody {
+    #[serde(default)]
+    series_id: Option<String>,
     x_value: String,
-    y_value: u32,
+    y_value: Decimal,
 }
 
 trait InputExt {
@@ -27,12 +31,17 @@ trait InputExt {
 impl InputExt for command_use_case::create_data_point::Input {
     fn from_request(
         PathParameters { chart_id }: PathParameters,
-        RequestBody { x_value, y_value }: RequestBody,
+        RequestBody {
+            series_id,
+            x_value,
+            y_value,
+        }: RequestBody,
     ) -> command_use_case::create_data_point::Input {
         command_use_case::create_data_point::Input {
             chart_id,
+            series_id,
             x_value,
-            y_value,
+            y_value: y_value.to_string(),
         }
     }
 }
@@ -50,11 +59,28 @@ impl From<command_use_case::create_data_point::Output> for ResponseBody {
     }
 }
 
+impl From<command_use_case::create_data_point::Error> for Problem {
+    fn from(error: command_use_case::create_data_point::Error) -> Self {
+        use command_use_case::create_data_point::Error;
+        match &error {
+            Error::ChartFind(e) => Problem::from_chart_repository_error(&error, e),
+            Error::DataPointStore(e) => Problem::from_data_point_repository_error(&error, e),
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::DataPointCreate(e) => Problem::from_data_point_error(&error, e),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+            Error::SeriesNotFound(_) => Problem::not_found(error.to_string()),
+            Error::XValue(_) => Problem::invalid_parameter("x_value", &error),
+            Error::YValue(_) => Problem::invalid_parameter("y_value", &error),
+        }
+    }
+}
+
 async fn handler<T: HasCreateDataPoint>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
     Json(body): Json<RequestBody>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<Json<ResponseBody>, Problem> {
     let use_case = state.create_data_point();
     let output = use_case
         .execute(command_use_case::create_data_point::Input::from_request(
@@ -62,7 +88,7 @@ async fn handler<T: HasCreateDataPoint>(
             body,
         ))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(Problem::from)?;
     Ok(Json(ResponseBody::from(output)))
 }
 
@@ -75,8 +101,9 @@ pub fn router<T: Clone + HasCreateDataPoint + Send + Sync + 'static>() -> Router
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{str::FromStr as _, sync::Arc};
 
+    use axum::http::StatusCode;
     use command_use_case::create_data_point::MockCreateDataPoint;
     use write_model::value_object::ChartId;
 
@@ -94,8 +121,9 @@ mod tests {
                 chart_id: chart_id.to_string(),
             },
             &RequestBody {
+                series_id: None,
                 x_value: "2020-01-02".to_string(),
-                y_value: 34,
+                y_value: Decimal::from_str("34")?,
             },
         )?;
         let response = send_request(app, request).await?;
@@ -109,6 +137,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_exact_y_value() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.create_data_point = {
+            let mut mock = MockCreateDataPoint::new();
+            mock.expect_execute()
+                .withf(|input| input.y_value == "-9223372036854.775807")
+                .return_once(move |input| {
+                    Ok(command_use_case::create_data_point::Output {
+                        data_point_id: format!("{}:{}", input.chart_id, input.x_value),
+                    })
+                });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &RequestBody {
+                series_id: None,
+                x_value: "2020-01-02".to_string(),
+                y_value: Decimal::from_str("-9223372036854.775807")?,
+            },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_error() -> anyhow::Result<()> {
         let chart_id = ChartId::generate();
@@ -128,13 +187,151 @@ mod tests {
                 chart_id: chart_id.to_string(),
             },
             &RequestBody {
+                series_id: None,
                 x_value: "2020-01-02".to_string(),
-                y_value: 34,
+                y_value: Decimal::from_str("34")?,
             },
         )?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_x_value() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.create_data_point = {
+            let mut mock = MockCreateDataPoint::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::create_data_point::Error::XValue(
+                    write_model::value_object::XValue::from_str("2020-13-01").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &RequestBody {
+                series_id: None,
+                x_value: "2020-13-01".to_string(),
+                y_value: Decimal::from_str("34")?,
+            },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = response.into_body_as_json::<Problem>().await?;
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("x_value".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chart_not_found() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.create_data_point = {
+            let mut mock = MockCreateDataPoint::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(command_use_case::create_data_point::Error::ChartNotFound(
+                    chart_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &RequestBody {
+                series_id: None,
+                x_value: "2020-01-02".to_string(),
+                y_value: Decimal::from_str("34")?,
+            },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some(format!("chart not found (id = {})", chart_id)),
+                field: None,
+                status: 404,
+                title: "Not found".to_string(),
+                r#type: "/problems/not-found".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series_not_found() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let series_id = write_model::value_object::SeriesId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.create_data_point = {
+            let mut mock = MockCreateDataPoint::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(command_use_case::create_data_point::Error::SeriesNotFound(
+                    series_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &RequestBody {
+                series_id: Some(series_id.to_string()),
+                x_value: "2020-01-02".to_string(),
+                y_value: Decimal::from_str("34")?,
+            },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request_body() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let app = router().with_state(Mocks::with_happy_path_behavior());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &serde_json::json!({ "x_value": "2020-01-02", "y_value": "34" }),
+        )?;
+        let response = send_request(app.clone(), request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = response.into_body_as_json::<Problem>().await?;
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("y_value".to_string()));
+
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &serde_json::json!({ "y_value": 34 }),
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = response.into_body_as_json::<Problem>().await?;
+        assert_eq!(problem.field, Some("x_value".to_string()));
         Ok(())
     }
 
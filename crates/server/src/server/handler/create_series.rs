@@ -0,0 +1,196 @@
+use axum::{extract::State, Router};
+
+use command_use_case::{self, create_series::HasCreateSeries};
+
+use crate::server::handler::{
+    extract::{Json, Path},
+    problem::Problem,
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RequestBody {
+    name: String,
+}
+
+fn input_from_request(
+    PathParameters { chart_id }: PathParameters,
+    RequestBody { name }: RequestBody,
+) -> command_use_case::create_series::Input {
+    command_use_case::create_series::Input { chart_id, name }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {
+    series_id: String,
+}
+
+impl From<command_use_case::create_series::Output> for ResponseBody {
+    fn from(
+        command_use_case::create_series::Output { series_id }: command_use_case::create_series::Output,
+    ) -> Self {
+        Self { series_id }
+    }
+}
+
+impl From<command_use_case::create_series::Error> for Problem {
+    fn from(error: command_use_case::create_series::Error) -> Self {
+        use command_use_case::create_series::Error;
+        match &error {
+            Error::ChartFind(e) | Error::ChartStore(e) => {
+                Problem::from_chart_repository_error(&error, e)
+            }
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::SeriesCreate(e) => Problem::from_chart_error(&error, e),
+        }
+    }
+}
+
+async fn handler<T: HasCreateSeries>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+    Json(body): Json<RequestBody>,
+) -> Result<Json<ResponseBody>, Problem> {
+    let use_case = state.create_series();
+    let output = use_case
+        .execute(input_from_request(path_parameters, body))
+        .await
+        .map_err(Problem::from)?;
+    Ok(Json(ResponseBody::from(output)))
+}
+
+pub fn router<T: Clone + HasCreateSeries + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/charts/:chart_id/series",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use command_use_case::create_series::MockCreateSeries;
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "name1".to_string();
+        let series_id = "series_id1".to_string();
+        let mocks =
+            Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id.clone());
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { chart_id }, &RequestBody { name })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?,
+            ResponseBody { series_id }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "name1".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id);
+        mocks.create_series = {
+            let mut mock = MockCreateSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::create_series::Error::ChartStore(
+                    command_use_case::port::chart_repository::Error::from(build_error()),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { chart_id }, &RequestBody { name })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_name() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id);
+        mocks.create_series = {
+            let mut mock = MockCreateSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::create_series::Error::SeriesCreate(
+                    write_model::aggregate::chart::Error::InvalidSeriesName,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { chart_id }, &RequestBody { name })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("name".to_string())
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        create_series: Arc<MockCreateSeries>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior(chart_id: String, name: String, series_id: String) -> Self {
+            let mut create_series = MockCreateSeries::new();
+            create_series.expect_execute().return_once(move |input| {
+                assert_eq!(input.chart_id, chart_id);
+                assert_eq!(input.name, name);
+                Ok(command_use_case::create_series::Output { series_id })
+            });
+            Self {
+                create_series: Arc::new(create_series),
+            }
+        }
+    }
+
+    impl command_use_case::create_series::HasCreateSeries for Mocks {
+        fn create_series(
+            &self,
+        ) -> Arc<dyn command_use_case::create_series::CreateSeries + Send + Sync> {
+            self.create_series.clone()
+        }
+    }
+
+    fn build_error() -> Box<dyn std::error::Error + Send + Sync> {
+        Box::new(std::io::Error::new(std::io::ErrorKind::Other, "error"))
+    }
+
+    fn build_request<T: serde::Serialize>(
+        path_parameters: &PathParameters,
+        request_body: &T,
+    ) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::POST)
+            .uri(format!("/charts/{}/series", path_parameters.chart_id))
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(serde_json::to_string(request_body)?))?)
+    }
+}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::server::handler::problem::Problem;
+
+/// A decimal number in a JSON body (e.g. `y_value`). It is written and read as a JSON number
+/// whose digits are kept as is, instead of being rounded through `f64` like `serde_json::Number`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Decimal(String);
+
+#[derive(Debug, thiserror::Error)]
+#[error("not a JSON number ({0})")]
+pub(crate) struct InvalidDecimal(String);
+
+impl FromStr for Decimal {
+    type Err = InvalidDecimal;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::Number::from_str(s).map_err(|_| InvalidDecimal(s.to_owned()))?;
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl serde::Serialize for Decimal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_json::value::RawValue::from_string(self.0.clone())
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Decimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Box::<serde_json::value::RawValue>::deserialize(deserializer)?;
+        Self::from_str(raw.get()).map_err(D::Error::custom)
+    }
+}
+
+impl From<InvalidDecimal> for Problem {
+    fn from(error: InvalidDecimal) -> Self {
+        Problem::internal_server_error(&error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        for s in ["0", "-12.5", "-9223372036854.775807", "0.000001"] {
+            let decimal = Decimal::from_str(s)?;
+            assert_eq!(serde_json::to_string(&decimal)?, s);
+            assert_eq!(serde_json::from_str::<Decimal>(s)?, decimal);
+        }
+        assert!(Decimal::from_str("x").is_err());
+        assert!(Decimal::from_str("\"1\"").is_err());
+        assert!(serde_json::from_str::<Decimal>("\"1\"").is_err());
+        Ok(())
+    }
+}
@@ -1,35 +1,65 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Router,
 };
 
 use command_use_case::{self, delete_chart::HasDeleteChart};
 
+use crate::server::handler::{
+    etag::{etag_from_version, expected_version_from_headers},
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     chart_id: String,
 }
 
-impl From<PathParameters> for command_use_case::delete_chart::Input {
-    fn from(PathParameters { chart_id }: PathParameters) -> Self {
-        Self { chart_id }
+fn input_from_request(
+    PathParameters { chart_id }: PathParameters,
+    expected_version: Option<u32>,
+) -> command_use_case::delete_chart::Input {
+    command_use_case::delete_chart::Input {
+        chart_id,
+        expected_version,
     }
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct ResponseBody {}
 
+impl From<command_use_case::delete_chart::Error> for Problem {
+    fn from(error: command_use_case::delete_chart::Error) -> Self {
+        use command_use_case::delete_chart::Error;
+        match &error {
+            Error::ChartDelete(e) => Problem::from_chart_error(&error, e),
+            Error::ChartFind(e) | Error::ChartStore(e) => {
+                Problem::from_chart_repository_error(&error, e)
+            }
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::VersionMismatch { .. } => Problem::precondition_failed(&error),
+        }
+    }
+}
+
 async fn handler<T: HasDeleteChart>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+    headers: HeaderMap,
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
+    let expected_version = expected_version_from_headers(&headers)?;
     let use_case = state.delete_chart();
-    let _output = use_case
-        .execute(command_use_case::delete_chart::Input::from(path_parameters))
+    let output = use_case
+        .execute(input_from_request(path_parameters, expected_version))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ResponseBody {}))
+        .map_err(Problem::from)?;
+    Ok((
+        [(header::ETAG, etag_from_version(output.version))],
+        Json(ResponseBody {}),
+    ))
 }
 
 pub fn router<T: Clone + HasDeleteChart + Send + Sync + 'static>() -> Router<T> {
@@ -40,6 +70,7 @@ pub fn router<T: Clone + HasDeleteChart + Send + Sync + 'static>() -> Router<T>
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use command_use_case::delete_chart::MockDeleteChart;
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -54,6 +85,10 @@ mod tests {
         let request = build_request(&PathParameters { chart_id })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {}
@@ -78,7 +113,40 @@ mod tests {
         let request = build_request(&PathParameters { chart_id })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_already_deleted() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(chart_id.clone());
+        mocks.delete_chart = {
+            let mut mock = MockDeleteChart::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::delete_chart::Error::ChartDelete(
+                    write_model::aggregate::chart::Error::AlreadyDeleted,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { chart_id })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("chart delete: already deleted".to_string()),
+                field: None,
+                status: 409,
+                title: "Conflict".to_string(),
+                r#type: "/problems/conflict".to_string(),
+            }
+        );
         Ok(())
     }
 
@@ -92,7 +160,7 @@ mod tests {
             let mut delete_chart = MockDeleteChart::new();
             delete_chart.expect_execute().return_once(move |input| {
                 assert_eq!(input.chart_id, chart_id);
-                Ok(command_use_case::delete_chart::Output)
+                Ok(command_use_case::delete_chart::Output { version: 2 })
             });
             Self {
                 delete_chart: Arc::new(delete_chart),
@@ -1,37 +1,65 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Router,
 };
 
 use command_use_case::{self, delete_data_point::HasDeleteDataPoint};
 
+use crate::server::handler::{
+    etag::{etag_from_version, expected_version_from_headers},
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     data_point_id: String,
 }
 
-impl From<PathParameters> for command_use_case::delete_data_point::Input {
-    fn from(PathParameters { data_point_id }: PathParameters) -> Self {
-        Self { data_point_id }
+fn input_from_request(
+    PathParameters { data_point_id }: PathParameters,
+    expected_version: Option<u32>,
+) -> command_use_case::delete_data_point::Input {
+    command_use_case::delete_data_point::Input {
+        data_point_id,
+        expected_version,
     }
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct ResponseBody {}
 
+impl From<command_use_case::delete_data_point::Error> for Problem {
+    fn from(error: command_use_case::delete_data_point::Error) -> Self {
+        use command_use_case::delete_data_point::Error;
+        match &error {
+            Error::DataPointDelete(e) => Problem::from_data_point_error(&error, e),
+            Error::DataPointFind(e) | Error::DataPointStore(e) => {
+                Problem::from_data_point_repository_error(&error, e)
+            }
+            Error::DataPointId(_) => Problem::invalid_parameter("data_point_id", &error),
+            Error::DataPointNotFound(_) => Problem::not_found(error.to_string()),
+            Error::VersionMismatch { .. } => Problem::precondition_failed(&error),
+        }
+    }
+}
+
 async fn handler<T: HasDeleteDataPoint>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+    headers: HeaderMap,
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
+    let expected_version = expected_version_from_headers(&headers)?;
     let use_case = state.delete_data_point();
-    let _output = use_case
-        .execute(command_use_case::delete_data_point::Input::from(
-            path_parameters,
-        ))
+    let output = use_case
+        .execute(input_from_request(path_parameters, expected_version))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ResponseBody {}))
+        .map_err(Problem::from)?;
+    Ok((
+        [(header::ETAG, etag_from_version(output.version))],
+        Json(ResponseBody {}),
+    ))
 }
 
 pub fn router<T: Clone + HasDeleteDataPoint + Send + Sync + 'static>() -> Router<T> {
@@ -43,8 +71,9 @@ pub fn router<T: Clone + HasDeleteDataPoint + Send + Sync + 'static>() -> Router
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{str::FromStr as _, sync::Arc};
 
+    use axum::http::StatusCode;
     use command_use_case::delete_data_point::MockDeleteDataPoint;
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -59,6 +88,10 @@ mod tests {
         let request = build_request(&PathParameters { data_point_id })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {}
@@ -83,7 +116,54 @@ mod tests {
         let request = build_request(&PathParameters { data_point_id })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_data_point_id() -> anyhow::Result<()> {
+        let data_point_id = "data_point_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point_id.clone());
+        mocks.delete_data_point = {
+            let mut mock = MockDeleteDataPoint::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::delete_data_point::Error::DataPointId(
+                    write_model::value_object::DataPointId::from_str("data_point_id1").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { data_point_id })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("data_point_id".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_if_match() -> anyhow::Result<()> {
+        let data_point_id = "data_point_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point_id.clone());
+        mocks.delete_data_point = Arc::new(MockDeleteDataPoint::new());
+        let app = router().with_state(mocks.clone());
+        let mut request = build_request(&PathParameters { data_point_id })?;
+        request.headers_mut().insert(
+            axum::http::header::IF_MATCH,
+            axum::http::HeaderValue::from_static("invalid"),
+        );
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("If-Match".to_string())
+        );
         Ok(())
     }
 
@@ -99,7 +179,7 @@ mod tests {
                 .expect_execute()
                 .return_once(move |input| {
                     assert_eq!(input.data_point_id, data_point_id);
-                    Ok(command_use_case::delete_data_point::Output)
+                    Ok(command_use_case::delete_data_point::Output { version: 2 })
                 });
             Self {
                 delete_data_point: Arc::new(delete_data_point),
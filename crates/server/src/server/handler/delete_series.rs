@@ -0,0 +1,198 @@
+use axum::{extract::State, Router};
+
+use command_use_case::{self, delete_series::HasDeleteSeries};
+
+use crate::server::handler::{
+    extract::{Json, Path},
+    problem::Problem,
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+    series_id: String,
+}
+
+impl From<PathParameters> for command_use_case::delete_series::Input {
+    fn from(
+        PathParameters {
+            chart_id,
+            series_id,
+        }: PathParameters,
+    ) -> Self {
+        Self {
+            chart_id,
+            series_id,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {}
+
+impl From<command_use_case::delete_series::Error> for Problem {
+    fn from(error: command_use_case::delete_series::Error) -> Self {
+        use command_use_case::delete_series::Error;
+        match &error {
+            Error::ChartFind(e) | Error::ChartStore(e) => {
+                Problem::from_chart_repository_error(&error, e)
+            }
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::SeriesDelete(e) => Problem::from_chart_error(&error, e),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+        }
+    }
+}
+
+async fn handler<T: HasDeleteSeries>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+) -> Result<Json<ResponseBody>, Problem> {
+    let use_case = state.delete_series();
+    let _output = use_case
+        .execute(command_use_case::delete_series::Input::from(
+            path_parameters,
+        ))
+        .await
+        .map_err(Problem::from)?;
+    Ok(Json(ResponseBody {}))
+}
+
+pub fn router<T: Clone + HasDeleteSeries + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/charts/:chart_id/series/:series_id",
+        axum::routing::delete(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use command_use_case::delete_series::MockDeleteSeries;
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let series_id = "series_id1".to_string();
+        let mocks = Mocks::with_happy_path_behavior(chart_id.clone(), series_id.clone());
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            chart_id,
+            series_id,
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?,
+            ResponseBody {}
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(chart_id.clone(), series_id.clone());
+        mocks.delete_series = {
+            let mut mock = MockDeleteSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::delete_series::Error::ChartStore(
+                    command_use_case::port::chart_repository::Error::from(build_error()),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            chart_id,
+            series_id,
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series_not_found() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(chart_id.clone(), series_id.clone());
+        mocks.delete_series = {
+            let mut mock = MockDeleteSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::delete_series::Error::SeriesDelete(
+                    write_model::aggregate::chart::Error::SeriesNotFound,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            chart_id,
+            series_id,
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        delete_series: Arc<MockDeleteSeries>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior(chart_id: String, series_id: String) -> Self {
+            let mut delete_series = MockDeleteSeries::new();
+            delete_series.expect_execute().return_once(move |input| {
+                assert_eq!(input.chart_id, chart_id);
+                assert_eq!(input.series_id, series_id);
+                Ok(command_use_case::delete_series::Output)
+            });
+            Self {
+                delete_series: Arc::new(delete_series),
+            }
+        }
+    }
+
+    impl command_use_case::delete_series::HasDeleteSeries for Mocks {
+        fn delete_series(
+            &self,
+        ) -> Arc<dyn command_use_case::delete_series::DeleteSeries + Send + Sync> {
+            self.delete_series.clone()
+        }
+    }
+
+    fn build_error() -> Box<dyn std::error::Error + Send + Sync> {
+        Box::new(std::io::Error::new(std::io::ErrorKind::Other, "error"))
+    }
+
+    fn build_request(
+        path_parameters: &PathParameters,
+    ) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::DELETE)
+            .uri(format!(
+                "/charts/{}/series/{}",
+                path_parameters.chart_id, path_parameters.series_id
+            ))
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?)
+    }
+}
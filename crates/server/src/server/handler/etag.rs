@@ -0,0 +1,58 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+
+use crate::server::handler::problem::Problem;
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid If-Match header")]
+struct InvalidIfMatch;
+
+/// Formats the aggregate version as a strong entity tag (e.g. `"3"`)
+pub(crate) fn etag_from_version(version: u32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("etag to be a valid header value")
+}
+
+/// Reads the expected version from the `If-Match` header. A missing header or `*` skips the check.
+pub(crate) fn expected_version_from_headers(headers: &HeaderMap) -> Result<Option<u32>, Problem> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| Problem::invalid_parameter("If-Match", &InvalidIfMatch))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .and_then(|s| s.parse::<u32>().ok())
+        .map(Some)
+        .ok_or_else(|| Problem::invalid_parameter("If-Match", &InvalidIfMatch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_from_version() {
+        assert_eq!(etag_from_version(3), HeaderValue::from_static("\"3\""));
+    }
+
+    #[test]
+    fn test_expected_version_from_headers() {
+        let expected_version = |value: Option<&'static str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(value) = value {
+                headers.insert(header::IF_MATCH, HeaderValue::from_static(value));
+            }
+            expected_version_from_headers(&headers).map_err(|problem| problem.status)
+        };
+        assert_eq!(expected_version(None), Ok(None));
+        assert_eq!(expected_version(Some("*")), Ok(None));
+        assert_eq!(expected_version(Some("\"3\"")), Ok(Some(3)));
+        assert_eq!(expected_version(Some("3")), Err(400));
+        assert_eq!(expected_version(Some("W/\"3\"")), Err(400));
+    }
+}
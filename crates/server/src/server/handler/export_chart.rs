@@ -0,0 +1,538 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderName, HeaderValue},
+    Router,
+};
+use futures_util::{Stream, StreamExt as _, TryStreamExt as _};
+
+use query_use_case::{self, get_chart::HasGetChart, list_data_points::HasListDataPoints};
+
+use crate::server::handler::{
+    decimal::{Decimal, InvalidDecimal},
+    extract::{Path, Query},
+    problem::Problem,
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    series_id: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ExportChart {
+    created_at: String,
+    id: String,
+    series: Vec<ExportSeries>,
+    title: String,
+    version: u32,
+}
+
+impl From<query_use_case::get_chart::OutputItem> for ExportChart {
+    fn from(
+        query_use_case::get_chart::OutputItem {
+            created_at,
+            id,
+            series,
+            title,
+            version,
+        }: query_use_case::get_chart::OutputItem,
+    ) -> Self {
+        Self {
+            created_at,
+            id,
+            series: series
+                .into_iter()
+                .map(
+                    |query_use_case::get_chart::OutputItemSeries { id, name }| ExportSeries {
+                        id,
+                        name,
+                    },
+                )
+                .collect(),
+            title,
+            version,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ExportDataPoint {
+    created_at: String,
+    series_id: Option<String>,
+    x_value: String,
+    y_value: Decimal,
+}
+
+impl TryFrom<query_use_case::list_data_points::DataPoint> for ExportDataPoint {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::list_data_points::DataPoint {
+            chart_id: _,
+            created_at,
+            id: _,
+            series_id,
+            version: _,
+            x_value,
+            y_value,
+        }: query_use_case::list_data_points::DataPoint,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            created_at,
+            series_id,
+            x_value,
+            y_value: y_value.parse()?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ExportSeries {
+    id: String,
+    name: String,
+}
+
+/// The number of data points read from the store at a time
+const PAGE_SIZE: usize = 1000;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Reads the chart and the first page of its data points, so that an invalid request is answered
+/// with a problem before the body is written
+async fn chart_and_first_page<T: HasGetChart + HasListDataPoints>(
+    state: &T,
+    chart_id: String,
+    query_parameters: &QueryParameters,
+) -> Result<
+    (
+        query_use_case::get_chart::OutputItem,
+        Vec<query_use_case::list_data_points::DataPoint>,
+    ),
+    Problem,
+> {
+    let query_use_case::get_chart::Output(chart) = state
+        .get_chart()
+        .execute(query_use_case::get_chart::Input {
+            chart_id: chart_id.clone(),
+        })
+        .await
+        .map_err(Problem::from)?;
+    let chart = chart.ok_or_else(|| Problem::not_found("chart not found"))?;
+    let query_use_case::list_data_points::Output(data_points) = state
+        .list_data_points()
+        .execute(list_data_points_input(chart_id, query_parameters, None))
+        .await
+        .map_err(Problem::from)?;
+    Ok((chart, data_points))
+}
+
+fn list_data_points_input(
+    chart_id: String,
+    QueryParameters {
+        from,
+        series_id,
+        to,
+    }: &QueryParameters,
+    after: Option<String>,
+) -> query_use_case::list_data_points::Input {
+    query_use_case::list_data_points::Input {
+        after,
+        chart_id,
+        from: from.clone(),
+        limit: Some(PAGE_SIZE),
+        series_id: series_id.clone(),
+        to: to.clone(),
+    }
+}
+
+/// The id to read the page after `page` from. `None` if `page` is the last page
+fn next_after(page: &[query_use_case::list_data_points::DataPoint]) -> Option<String> {
+    if page.len() < PAGE_SIZE {
+        None
+    } else {
+        page.last().map(|data_point| data_point.id.clone())
+    }
+}
+
+/// Yields `first_page` and then reads the following pages one at a time while the body is written
+fn data_point_pages<T: Clone + HasListDataPoints + Send + Sync + 'static>(
+    state: T,
+    chart_id: String,
+    query_parameters: QueryParameters,
+    first_page: Vec<query_use_case::list_data_points::DataPoint>,
+) -> impl Stream<Item = Result<Vec<query_use_case::list_data_points::DataPoint>, BoxError>> + Send {
+    let after = next_after(&first_page);
+    let next_pages = futures_util::stream::try_unfold(after, move |after| {
+        let state = state.clone();
+        let input = after
+            .map(|after| list_data_points_input(chart_id.clone(), &query_parameters, Some(after)));
+        async move {
+            let Some(input) = input else {
+                return Ok(None);
+            };
+            let query_use_case::list_data_points::Output(page) =
+                state.list_data_points().execute(input).await?;
+            let after = next_after(&page);
+            Ok(Some((page, after)))
+        }
+    });
+    futures_util::stream::once(async { Ok(first_page) }).chain(next_pages)
+}
+
+/// Writes `head`, the chunks and `tail`. The status has been sent when a chunk fails, so the error
+/// is logged and the body is cut short
+fn streaming_body<S>(head: String, chunks: S, tail: String) -> Body
+where
+    S: Stream<Item = Result<String, BoxError>> + Send + 'static,
+{
+    let chunks = chunks.inspect_err(|e| tracing::error!("export aborted: {}", e));
+    Body::from_stream(
+        futures_util::stream::once(async { Ok(head) })
+            .chain(chunks)
+            .chain(futures_util::stream::once(async { Ok(tail) })),
+    )
+}
+
+fn content_disposition(chart_id: &str, extension: &str) -> Result<HeaderValue, Problem> {
+    HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}.{}\"",
+        chart_id, extension
+    ))
+    .map_err(|e| Problem::internal_server_error(&e))
+}
+
+async fn csv_handler<T: Clone + HasGetChart + HasListDataPoints + Send + Sync + 'static>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<([(HeaderName, HeaderValue); 2], Body), Problem> {
+    let (chart, first_page) =
+        chart_and_first_page(&state, chart_id.clone(), &query_parameters).await?;
+    let content_disposition = content_disposition(&chart.id, "csv")?;
+    let chunks = data_point_pages(state, chart_id, query_parameters, first_page).map_ok(|page| {
+        page.into_iter()
+            .map(|data_point| {
+                format!(
+                    "{},{},{}\n",
+                    data_point.x_value,
+                    data_point.series_id.unwrap_or_default(),
+                    data_point.y_value
+                )
+            })
+            .collect::<String>()
+    });
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/csv; charset=utf-8"),
+            ),
+            (header::CONTENT_DISPOSITION, content_disposition),
+        ],
+        streaming_body("date,series_id,value\n".to_owned(), chunks, String::new()),
+    ))
+}
+
+async fn json_handler<T: Clone + HasGetChart + HasListDataPoints + Send + Sync + 'static>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<([(HeaderName, HeaderValue); 2], Body), Problem> {
+    let (chart, first_page) =
+        chart_and_first_page(&state, chart_id.clone(), &query_parameters).await?;
+    let content_disposition = content_disposition(&chart.id, "json")?;
+    let chart = serde_json::to_string(&ExportChart::from(chart))
+        .map_err(|e| Problem::internal_server_error(&e))?;
+    let mut written = 0_usize;
+    let chunks =
+        data_point_pages(state, chart_id, query_parameters, first_page).and_then(move |page| {
+            let chunk = page
+                .into_iter()
+                .map(|data_point| {
+                    let data_point =
+                        serde_json::to_string(&ExportDataPoint::try_from(data_point)?)?;
+                    let separator = if written == 0 { "\n" } else { ",\n" };
+                    written += 1;
+                    Ok(format!("{}{}", separator, data_point))
+                })
+                .collect::<Result<String, BoxError>>();
+            async { chunk }
+        });
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            ),
+            (header::CONTENT_DISPOSITION, content_disposition),
+        ],
+        streaming_body(
+            format!("{{\"chart\":{},\"data_points\":[", chart),
+            chunks,
+            "\n]}\n".to_owned(),
+        ),
+    ))
+}
+
+pub fn router<T: Clone + HasGetChart + HasListDataPoints + Send + Sync + 'static>() -> Router<T> {
+    Router::new()
+        .route(
+            "/charts/:chart_id/data_points.csv",
+            axum::routing::get(csv_handler::<T>),
+        )
+        .route(
+            "/charts/:chart_id/export.json",
+            axum::routing::get(json_handler::<T>),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr as _, sync::Arc};
+
+    use axum::http::StatusCode;
+    use query_use_case::{
+        get_chart::{MockGetChart, OutputItem, OutputItemSeries},
+        list_data_points::{DataPoint, MockListDataPoints},
+    };
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Export {
+        chart: ExportChart,
+        data_points: Vec<ExportDataPoint>,
+    }
+
+    #[tokio::test]
+    async fn test_csv() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request =
+            build_request("/charts/chart_id1/data_points.csv?from=2020-01-01&to=2020-02-01")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("text/csv; charset=utf-8"))
+        );
+        assert_eq!(
+            response.headers().get(header::CONTENT_DISPOSITION),
+            Some(&HeaderValue::from_static(
+                "attachment; filename=\"chart_id1.csv\""
+            ))
+        );
+        assert_eq!(
+            response.into_body_string().await?,
+            "date,series_id,value\n2020-01-01,,1\n2020-01-02,series_id1,-1.5\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/export.json?from=2020-01-01&to=2020-02-01")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<Export>().await?,
+            Export {
+                chart: ExportChart {
+                    created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                    id: "chart_id1".to_string(),
+                    series: vec![ExportSeries {
+                        id: "series_id1".to_string(),
+                        name: "name1".to_string(),
+                    }],
+                    title: "title1".to_string(),
+                    version: 3,
+                },
+                data_points: vec![
+                    ExportDataPoint {
+                        created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                        series_id: None,
+                        x_value: "2020-01-01".to_string(),
+                        y_value: Decimal::from_str("1")?,
+                    },
+                    ExportDataPoint {
+                        created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                        series_id: Some("series_id1".to_string()),
+                        x_value: "2020-01-02".to_string(),
+                        y_value: Decimal::from_str("-1.5")?,
+                    },
+                ],
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json_empty() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.list_data_points = {
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute()
+                .return_once(|_| Ok(query_use_case::list_data_points::Output(vec![])));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/export.json")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .into_body_as_json::<Export>()
+            .await?
+            .data_points
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pages() -> anyhow::Result<()> {
+        let data_point = |index: usize| DataPoint {
+            chart_id: "chart_id1".to_string(),
+            created_at: "2020-01-01T00:00:00.000Z".to_string(),
+            id: format!("chart_id1:{}", index),
+            series_id: None,
+            version: 1,
+            x_value: format!("x{}", index),
+            y_value: index.to_string(),
+        };
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.list_data_points = {
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute().times(2).returning(move |input| {
+                Ok(query_use_case::list_data_points::Output(
+                    match input.after {
+                        None => (0..PAGE_SIZE).map(data_point).collect(),
+                        Some(after) => {
+                            assert_eq!(after, format!("chart_id1:{}", PAGE_SIZE - 1));
+                            vec![data_point(PAGE_SIZE)]
+                        }
+                    },
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/data_points.csv")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert_eq!(body.lines().count(), 1 + PAGE_SIZE + 1);
+        assert_eq!(
+            body.lines().last(),
+            Some(format!("x{},,{}", PAGE_SIZE, PAGE_SIZE).as_str())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_found() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute()
+                .return_once(|_| Ok(query_use_case::get_chart::Output(None)));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/data_points.csv")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        get_chart: Arc<MockGetChart>,
+        list_data_points: Arc<MockListDataPoints>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior() -> Self {
+            let mut get_chart = MockGetChart::new();
+            get_chart.expect_execute().return_once(|_| {
+                Ok(query_use_case::get_chart::Output(Some(OutputItem {
+                    created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                    id: "chart_id1".to_string(),
+                    series: vec![OutputItemSeries {
+                        id: "series_id1".to_string(),
+                        name: "name1".to_string(),
+                    }],
+                    title: "title1".to_string(),
+                    version: 3,
+                })))
+            });
+            let mut list_data_points = MockListDataPoints::new();
+            list_data_points
+                .expect_execute()
+                .withf(|input| {
+                    input.after.is_none()
+                        && input.chart_id == "chart_id1"
+                        && input.from == Some("2020-01-01".to_string())
+                        && input.limit == Some(PAGE_SIZE)
+                        && input.to == Some("2020-02-01".to_string())
+                })
+                .return_once(|_| {
+                    let data_point =
+                        |series_id: Option<&str>, x_value: &str, y_value: &str| DataPoint {
+                            chart_id: "chart_id1".to_string(),
+                            created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                            id: match series_id {
+                                None => format!("chart_id1:{}", x_value),
+                                Some(series_id) => format!("chart_id1:{}:{}", series_id, x_value),
+                            },
+                            series_id: series_id.map(str::to_owned),
+                            version: 1,
+                            x_value: x_value.to_string(),
+                            y_value: y_value.to_string(),
+                        };
+                    Ok(query_use_case::list_data_points::Output(vec![
+                        data_point(None, "2020-01-01", "1"),
+                        data_point(Some("series_id1"), "2020-01-02", "-1.5"),
+                    ]))
+                });
+            Self {
+                get_chart: Arc::new(get_chart),
+                list_data_points: Arc::new(list_data_points),
+            }
+        }
+    }
+
+    impl query_use_case::get_chart::HasGetChart for Mocks {
+        fn get_chart(&self) -> Arc<dyn query_use_case::get_chart::GetChart + Send + Sync> {
+            self.get_chart.clone()
+        }
+    }
+
+    impl query_use_case::list_data_points::HasListDataPoints for Mocks {
+        fn list_data_points(
+            &self,
+        ) -> Arc<dyn query_use_case::list_data_points::ListDataPoints + Send + Sync> {
+            self.list_data_points.clone()
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .body(axum::body::Body::empty())?)
+    }
+}
@@ -0,0 +1,220 @@
+use axum::{
+    extract::{FromRequest, FromRequestParts},
+    http::{request::Parts, Request},
+    response::{IntoResponse, Response},
+};
+
+use crate::server::handler::problem::Problem;
+
+/// `axum::Json` rejecting with a `Problem` that names the offending field
+#[derive(Debug)]
+pub(crate) struct Json<T>(pub(crate) T);
+
+#[axum::async_trait]
+impl<S, T> FromRequest<S> for Json<T>
+where
+    S: Send + Sync,
+    T: serde::de::DeserializeOwned,
+{
+    type Rejection = Problem;
+
+    async fn from_request(request: Request<axum::body::Body>, state: &S) -> Result<Self, Problem> {
+        axum::Json::<T>::from_request(request, state)
+            .await
+            .map(|axum::Json(value)| Self(value))
+            .map_err(|rejection| Problem::from_json_rejection(&rejection))
+    }
+}
+
+impl<T: serde::Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// `axum::extract::Path` rejecting with a `Problem` that names the offending parameter
+#[derive(Debug)]
+pub(crate) struct Path<T>(pub(crate) T);
+
+#[axum::async_trait]
+impl<S, T> FromRequestParts<S> for Path<T>
+where
+    S: Send + Sync,
+    T: serde::de::DeserializeOwned + Send,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Problem> {
+        axum::extract::Path::<T>::from_request_parts(parts, state)
+            .await
+            .map(|axum::extract::Path(value)| Self(value))
+            .map_err(|rejection| Problem::from_path_rejection(&rejection))
+    }
+}
+
+/// `axum::extract::Query` rejecting with a `Problem` that names the offending parameter
+#[derive(Debug)]
+pub(crate) struct Query<T>(pub(crate) T);
+
+#[axum::async_trait]
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    S: Send + Sync,
+    T: serde::de::DeserializeOwned,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Problem> {
+        let query = parts.uri.query().unwrap_or_default();
+        // the same deserializer as `axum::extract::Query` but tracking the path to the error
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+        serde_path_to_error::deserialize(deserializer)
+            .map(Self)
+            .map_err(|error| {
+                let field = error_field(&error.path().to_string(), &error.inner().to_string());
+                Problem::invalid_request(field.as_deref(), &error)
+            })
+    }
+}
+
+/// The JSON body or the query string has been deserialized with `serde_path_to_error`.
+/// The path of a missing field is its parent, so the field is read from the serde message
+pub(crate) fn error_field(path: &str, message: &str) -> Option<String> {
+    message
+        .strip_prefix("missing field `")
+        .and_then(|s| s.split('`').next())
+        .map(|field| match path {
+            "." => field.to_owned(),
+            _ => format!("{}.{}", path, field),
+        })
+        .or_else(|| (path != ".").then(|| path.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    struct Parameters {
+        limit: u32,
+        name: String,
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route(
+                "/json",
+                axum::routing::post(|Json(body): Json<Parameters>| async move { Json(body) }),
+            )
+            .route(
+                "/path/:limit/:name",
+                get(|Path(path): Path<Parameters>| async move { Json(path) }),
+            )
+            .route(
+                "/query",
+                get(|Query(query): Query<Parameters>| async move { Json(query) }),
+            )
+    }
+
+    async fn send(request: Request<Body>) -> anyhow::Result<(StatusCode, Problem)> {
+        let response = send_request(router(), request).await?;
+        let status = response.status();
+        Ok((status, response.into_body_as_json::<Problem>().await?))
+    }
+
+    fn build_json_request(body: &'static str) -> anyhow::Result<Request<Body>> {
+        Ok(Request::builder()
+            .method(axum::http::Method::POST)
+            .uri("/json")
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))?)
+    }
+
+    fn build_get_request(uri: &str) -> anyhow::Result<Request<Body>> {
+        Ok(Request::builder().uri(uri).body(Body::empty())?)
+    }
+
+    #[tokio::test]
+    async fn test_json() -> anyhow::Result<()> {
+        let response =
+            send_request(router(), build_json_request(r#"{"limit":1,"name":"a"}"#)?).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (status, problem) = send(build_json_request(r#"{"limit":"a","name":"a"}"#)?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("limit".to_string()));
+
+        let (status, problem) = send(build_json_request(r#"{"limit":1}"#)?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.field, Some("name".to_string()));
+
+        let (status, problem) = send(build_json_request("{")?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.r#type, "/problems/invalid-request");
+        assert_eq!(problem.field, None);
+
+        let request = Request::builder()
+            .method(axum::http::Method::POST)
+            .uri("/json")
+            .body(Body::from(r#"{"limit":1,"name":"a"}"#))?;
+        let (status, problem) = send(request).await?;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(problem.r#type, "/problems/unsupported-media-type");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_path() -> anyhow::Result<()> {
+        let response = send_request(router(), build_get_request("/path/1/a")?).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (status, problem) = send(build_get_request("/path/a/a")?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("limit".to_string()));
+
+        let (status, problem) = send(build_get_request("/path/1/%FF")?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.field, Some("name".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query() -> anyhow::Result<()> {
+        let response = send_request(router(), build_get_request("/query?limit=1&name=a")?).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (status, problem) = send(build_get_request("/query?limit=a&name=a")?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("limit".to_string()));
+
+        let (status, problem) = send(build_get_request("/query?limit=1")?).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.field, Some("name".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_field() {
+        assert_eq!(error_field(".", "invalid type"), None);
+        assert_eq!(
+            error_field("limit", "invalid digit"),
+            Some("limit".to_string())
+        );
+        assert_eq!(
+            error_field(".", "missing field `name` at line 1 column 10"),
+            Some("name".to_string())
+        );
+        assert_eq!(
+            error_field("rows[0]", "missing field `x_value`"),
+            Some("rows[0].x_value".to_string())
+        );
+    }
+}
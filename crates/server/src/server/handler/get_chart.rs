@@ -1,11 +1,17 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderName, HeaderValue},
+    Router,
 };
 
 use query_use_case::{self, get_chart::HasGetChart};
 
+use crate::server::handler::{
+    etag::etag_from_version,
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     chart_id: String,
@@ -21,37 +27,65 @@ impl From<PathParameters> for query_use_case::get_chart::Input {
 struct ResponseBody {
     created_at: String,
     id: String,
+    series: Vec<ResponseBodySeries>,
     title: String,
 }
 
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBodySeries {
+    id: String,
+    name: String,
+}
+
 impl From<query_use_case::get_chart::OutputItem> for ResponseBody {
     fn from(
         query_use_case::get_chart::OutputItem {
             created_at,
             id,
+            series,
             title,
+            version: _,
         }: query_use_case::get_chart::OutputItem,
     ) -> Self {
         Self {
             created_at,
             id,
+            series: series
+                .into_iter()
+                .map(|query_use_case::get_chart::OutputItemSeries { id, name }| {
+                    ResponseBodySeries { id, name }
+                })
+                .collect(),
             title,
         }
     }
 }
 
+impl From<query_use_case::get_chart::Error> for Problem {
+    fn from(error: query_use_case::get_chart::Error) -> Self {
+        use query_use_case::get_chart::Error;
+        match &error {
+            Error::ChartGet(e) => Problem::from_chart_reader_error(&error, e),
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+        }
+    }
+}
+
 async fn handler<T: HasGetChart>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
     let use_case = state.get_chart();
     let query_use_case::get_chart::Output(output_item) = use_case
         .execute(query_use_case::get_chart::Input::from(path_parameters))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(Problem::from)?;
     match output_item {
-        Some(output) => Ok(Json(ResponseBody::from(output))),
-        None => Err(StatusCode::NOT_FOUND),
+        Some(output) => Ok((
+            [(header::ETAG, etag_from_version(output.version))],
+            Json(ResponseBody::from(output)),
+        )),
+        None => Err(Problem::not_found("chart not found")),
     }
 }
 
@@ -63,6 +97,7 @@ pub fn router<T: Clone + HasGetChart + Send + Sync + 'static>() -> Router<T> {
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use query_use_case::{get_chart::MockGetChart, list_charts::Chart};
     use write_model::value_object::DateTime;
 
@@ -80,11 +115,19 @@ mod tests {
         })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {
                 created_at: chart.created_at,
                 id: chart.id,
+                series: vec![ResponseBodySeries {
+                    id: "series_id1".to_string(),
+                    name: "name1".to_string(),
+                }],
                 title: chart.title
             }
         );
@@ -110,7 +153,71 @@ mod tests {
         })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_found() -> anyhow::Result<()> {
+        let chart = build_chart();
+        let mut mocks = Mocks::with_happy_path_behavior(chart.clone());
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute()
+                .return_once(|_| Ok(query_use_case::get_chart::Output(None)));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            chart_id: chart.id.clone(),
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("chart not found".to_string()),
+                field: None,
+                status: 404,
+                title: "Not found".to_string(),
+                r#type: "/problems/not-found".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unavailable() -> anyhow::Result<()> {
+        let chart = build_chart();
+        let mut mocks = Mocks::with_happy_path_behavior(chart.clone());
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute().return_once(|_| {
+                Err(query_use_case::get_chart::Error::ChartGet(
+                    query_use_case::port::chart_reader::Error::Unavailable(build_error()),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            chart_id: chart.id.clone(),
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: None,
+                field: None,
+                status: 503,
+                title: "Service unavailable".to_string(),
+                r#type: "/problems/service-unavailable".to_string(),
+            }
+        );
         Ok(())
     }
 
@@ -128,7 +235,12 @@ mod tests {
                     query_use_case::get_chart::OutputItem {
                         created_at: chart.created_at,
                         id: chart.id,
+                        series: vec![query_use_case::get_chart::OutputItemSeries {
+                            id: "series_id1".to_string(),
+                            name: "name1".to_string(),
+                        }],
                         title: chart.title,
+                        version: 2,
                     },
                 )))
             });
@@ -149,6 +261,7 @@ mod tests {
             created_at: DateTime::now().to_string(),
             id: "chart_id1".to_string(),
             title: "title1".to_string(),
+            updated_at: DateTime::now().to_string(),
         }
     }
 
@@ -0,0 +1,343 @@
+use std::str::FromStr as _;
+
+use axum::{
+    extract::State,
+    http::{header, HeaderName},
+    Router,
+};
+
+use query_use_case::{self, get_chart::HasGetChart, list_data_points::HasListDataPoints};
+use write_model::value_object::XValue;
+
+use crate::server::handler::{
+    extract::{Path, Query},
+    problem::Problem,
+};
+
+const MAX_SIZE: u32 = 4000;
+const MIN_SIZE: u32 = 100;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0} must be between {MIN_SIZE} and {MAX_SIZE}")]
+struct InvalidSize(&'static str);
+
+#[derive(Debug, thiserror::Error)]
+#[error("theme must be light or dark")]
+struct InvalidTheme;
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    theme: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+}
+
+fn chart_from_outputs(
+    chart: query_use_case::get_chart::OutputItem,
+    query_use_case::list_data_points::Output(data_points): query_use_case::list_data_points::Output,
+) -> chart_renderer::Chart {
+    let mut series_ids = vec![None];
+    series_ids.extend(chart.series.iter().map(|series| Some(series.id.clone())));
+    let lines = series_ids
+        .into_iter()
+        .map(|series_id| chart_renderer::Line {
+            name: series_id.as_ref().map(|series_id| {
+                chart
+                    .series
+                    .iter()
+                    .find(|series| &series.id == series_id)
+                    .map(|series| series.name.clone())
+                    .unwrap_or_else(|| series_id.clone())
+            }),
+            points: data_points
+                .iter()
+                .filter(|data_point| data_point.series_id == series_id)
+                .filter_map(|data_point| {
+                    Some(chart_renderer::Point {
+                        x_value: XValue::from_str(&data_point.x_value).ok()?,
+                        y_value: data_point.y_value.parse::<f64>().ok()?,
+                    })
+                })
+                .collect(),
+        })
+        .filter(|line| !line.points.is_empty())
+        .collect();
+    chart_renderer::Chart {
+        lines,
+        title: chart.title,
+    }
+}
+
+fn options_from_query_parameters(
+    query_parameters: &QueryParameters,
+) -> Result<chart_renderer::Options, Problem> {
+    let size = |field: &'static str, value: Option<u32>, default: u32| match value {
+        None => Ok(default),
+        Some(value) if (MIN_SIZE..=MAX_SIZE).contains(&value) => Ok(value),
+        Some(_) => Err(Problem::invalid_parameter(field, &InvalidSize(field))),
+    };
+    let x_value = |field: &'static str, value: Option<&str>| {
+        value
+            .map(XValue::from_str)
+            .transpose()
+            .map_err(|e| Problem::invalid_parameter(field, &e))
+    };
+    let default = chart_renderer::Options::default();
+    Ok(chart_renderer::Options {
+        from: x_value("from", query_parameters.from.as_deref())?,
+        height: size("height", query_parameters.height, default.height)?,
+        theme: query_parameters
+            .theme
+            .as_deref()
+            .map(chart_renderer::Theme::from_str)
+            .transpose()
+            .map_err(|_| Problem::invalid_parameter("theme", &InvalidTheme))?
+            .unwrap_or(default.theme),
+        to: x_value("to", query_parameters.to.as_deref())?,
+        width: size("width", query_parameters.width, default.width)?,
+    })
+}
+
+async fn chart_and_options<T: HasGetChart + HasListDataPoints>(
+    state: &T,
+    chart_id: String,
+    query_parameters: QueryParameters,
+) -> Result<(chart_renderer::Chart, chart_renderer::Options), Problem> {
+    let options = options_from_query_parameters(&query_parameters)?;
+    let query_use_case::get_chart::Output(chart) = state
+        .get_chart()
+        .execute(query_use_case::get_chart::Input {
+            chart_id: chart_id.clone(),
+        })
+        .await
+        .map_err(Problem::from)?;
+    let chart = chart.ok_or_else(|| Problem::not_found("chart not found"))?;
+    let data_points = state
+        .list_data_points()
+        .execute(query_use_case::list_data_points::Input {
+            after: None,
+            chart_id,
+            from: query_parameters.from,
+            limit: None,
+            series_id: None,
+            to: query_parameters.to,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok((chart_from_outputs(chart, data_points), options))
+}
+
+async fn png_handler<T: HasGetChart + HasListDataPoints>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<([(HeaderName, &'static str); 1], Vec<u8>), Problem> {
+    let (chart, options) = chart_and_options(&state, chart_id, query_parameters).await?;
+    let png = chart_renderer::render_png(&chart, &options)
+        .map_err(|e| Problem::internal_server_error(&e))?;
+    Ok(([(header::CONTENT_TYPE, "image/png")], png))
+}
+
+async fn svg_handler<T: HasGetChart + HasListDataPoints>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<([(HeaderName, &'static str); 1], String), Problem> {
+    let (chart, options) = chart_and_options(&state, chart_id, query_parameters).await?;
+    Ok((
+        [(header::CONTENT_TYPE, "image/svg+xml")],
+        chart_renderer::render_svg(&chart, &options),
+    ))
+}
+
+pub fn router<T: Clone + HasGetChart + HasListDataPoints + Send + Sync + 'static>() -> Router<T> {
+    Router::new()
+        .route(
+            "/charts/:chart_id/image.png",
+            axum::routing::get(png_handler::<T>),
+        )
+        .route(
+            "/charts/:chart_id/image.svg",
+            axum::routing::get(svg_handler::<T>),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use query_use_case::{
+        get_chart::{MockGetChart, OutputItem, OutputItemSeries},
+        list_data_points::{DataPoint, MockListDataPoints},
+    };
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_png() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/image.png?width=300&height=200&theme=dark")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&axum::http::HeaderValue::from_static("image/png"))
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        assert!(body.starts_with(b"\x89PNG\r\n\x1a\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_theme() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/image.png?theme=blue")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("theme".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_svg() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/image.svg?width=300&height=200")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&axum::http::HeaderValue::from_static("image/svg+xml"))
+        );
+        let body = response.into_body_string().await?;
+        assert!(
+            body.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="200""#)
+        );
+        assert!(body.contains("<title>title1</title>"));
+        // the default line and the named series line
+        assert_eq!(body.matches("<polyline ").count(), 2);
+        assert!(body.contains(">name1</text>"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_found() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute()
+                .return_once(|_| Ok(query_use_case::get_chart::Output(None)));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/image.svg")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_width() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/image.svg?width=10")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("width".to_string())
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        get_chart: Arc<MockGetChart>,
+        list_data_points: Arc<MockListDataPoints>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior() -> Self {
+            let mut get_chart = MockGetChart::new();
+            get_chart.expect_execute().return_once(|_| {
+                Ok(query_use_case::get_chart::Output(Some(OutputItem {
+                    created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                    id: "chart_id1".to_string(),
+                    series: vec![OutputItemSeries {
+                        id: "series_id1".to_string(),
+                        name: "name1".to_string(),
+                    }],
+                    title: "title1".to_string(),
+                    version: 1,
+                })))
+            });
+            let mut list_data_points = MockListDataPoints::new();
+            list_data_points.expect_execute().return_once(|_| {
+                let data_point =
+                    |series_id: Option<&str>, x_value: &str, y_value: &str| DataPoint {
+                        chart_id: "chart_id1".to_string(),
+                        created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                        id: match series_id {
+                            None => format!("chart_id1:{}", x_value),
+                            Some(series_id) => format!("chart_id1:{}:{}", series_id, x_value),
+                        },
+                        series_id: series_id.map(str::to_owned),
+                        version: 1,
+                        x_value: x_value.to_string(),
+                        y_value: y_value.to_string(),
+                    };
+                Ok(query_use_case::list_data_points::Output(vec![
+                    data_point(None, "2020-01-01", "1"),
+                    data_point(None, "2020-01-02", "2"),
+                    data_point(Some("series_id1"), "2020-01-01", "3"),
+                    data_point(Some("series_id1"), "2020-01-03", "-1.5"),
+                ]))
+            });
+            Self {
+                get_chart: Arc::new(get_chart),
+                list_data_points: Arc::new(list_data_points),
+            }
+        }
+    }
+
+    impl query_use_case::get_chart::HasGetChart for Mocks {
+        fn get_chart(&self) -> Arc<dyn query_use_case::get_chart::GetChart + Send + Sync> {
+            self.get_chart.clone()
+        }
+    }
+
+    impl query_use_case::list_data_points::HasListDataPoints for Mocks {
+        fn list_data_points(
+            &self,
+        ) -> Arc<dyn query_use_case::list_data_points::ListDataPoints + Send + Sync> {
+            self.list_data_points.clone()
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .body(axum::body::Body::empty())?)
+    }
+}
@@ -0,0 +1,380 @@
+use axum::{extract::State, Router};
+
+use query_use_case::{self, get_chart_stats::HasGetChartStats};
+
+use crate::server::handler::{
+    decimal::{Decimal, InvalidDecimal},
+    extract::{Json, Path, Query},
+    problem::Problem,
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct PathParameters {
+    chart_id: String,
+    #[serde(default)]
+    series_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+}
+
+fn input_from_request(
+    PathParameters {
+        chart_id,
+        series_id,
+    }: PathParameters,
+    QueryParameters { from, to }: QueryParameters,
+) -> query_use_case::get_chart_stats::Input {
+    query_use_case::get_chart_stats::Input {
+        chart_id,
+        from,
+        series_id,
+        to,
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {
+    count: usize,
+    current_streak: u32,
+    first_x_value: Option<String>,
+    last: Option<ResponseBodyLast>,
+    last_x_value: Option<String>,
+    longest_streak: u32,
+    max: Option<ResponseBodyExtremum>,
+    mean: Option<Decimal>,
+    median: Option<Decimal>,
+    min: Option<ResponseBodyExtremum>,
+    standard_deviation: Option<f64>,
+}
+
+impl TryFrom<query_use_case::get_chart_stats::Output> for ResponseBody {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::get_chart_stats::Output {
+            count,
+            current_streak,
+            first_x_value,
+            last,
+            last_x_value,
+            longest_streak,
+            max,
+            mean,
+            median,
+            min,
+            standard_deviation,
+        }: query_use_case::get_chart_stats::Output,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            count,
+            current_streak,
+            first_x_value,
+            last: last.map(ResponseBodyLast::try_from).transpose()?,
+            last_x_value,
+            longest_streak,
+            max: max.map(ResponseBodyExtremum::try_from).transpose()?,
+            mean: mean.map(|mean| mean.parse()).transpose()?,
+            median: median.map(|median| median.parse()).transpose()?,
+            min: min.map(ResponseBodyExtremum::try_from).transpose()?,
+            standard_deviation,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBodyExtremum {
+    x_value: String,
+    y_value: Decimal,
+}
+
+impl TryFrom<query_use_case::get_chart_stats::Extremum> for ResponseBodyExtremum {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::get_chart_stats::Extremum { x_value, y_value }: query_use_case::get_chart_stats::Extremum,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            x_value,
+            y_value: y_value.parse()?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBodyLast {
+    delta: Option<Decimal>,
+    x_value: String,
+    y_value: Decimal,
+}
+
+impl TryFrom<query_use_case::get_chart_stats::Last> for ResponseBodyLast {
+    type Error = InvalidDecimal;
+
+    fn try_from(
+        query_use_case::get_chart_stats::Last {
+            delta,
+            x_value,
+            y_value,
+        }: query_use_case::get_chart_stats::Last,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            delta: delta.map(|delta| delta.parse()).transpose()?,
+            x_value,
+            y_value: y_value.parse()?,
+        })
+    }
+}
+
+impl From<query_use_case::get_chart_stats::Error> for Problem {
+    fn from(error: query_use_case::get_chart_stats::Error) -> Self {
+        use query_use_case::get_chart_stats::Error;
+        match &error {
+            Error::ChartGet(e) => Problem::from_chart_reader_error(&error, e),
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::DataPointList(e) => Problem::from_data_point_reader_error(&error, e),
+            Error::From(_) => Problem::invalid_parameter("from", &error),
+            Error::Overflow => Problem::validation_failed(None, &error),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+            Error::SeriesNotFound(_) => Problem::not_found(error.to_string()),
+            Error::To(_) => Problem::invalid_parameter("to", &error),
+        }
+    }
+}
+
+async fn handler<T: HasGetChartStats>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<Json<ResponseBody>, Problem> {
+    let use_case = state.get_chart_stats();
+    let input = input_from_request(path_parameters, query_parameters);
+    let output = use_case.execute(input).await.map_err(Problem::from)?;
+    Ok(Json(ResponseBody::try_from(output)?))
+}
+
+pub fn router<T: Clone + HasGetChartStats + Send + Sync + 'static>() -> Router<T> {
+    Router::new()
+        .route("/charts/:chart_id/stats", axum::routing::get(handler::<T>))
+        .route(
+            "/charts/:chart_id/series/:series_id/stats",
+            axum::routing::get(handler::<T>),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr as _, sync::Arc};
+
+    use axum::http::StatusCode;
+    use query_use_case::get_chart_stats::{Extremum, Last, MockGetChartStats, Output};
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart_stats = {
+            let mut mock = MockGetChartStats::new();
+            mock.expect_execute().return_once(|input| {
+                assert_eq!(input.chart_id, "chart_id1");
+                assert_eq!(input.from, Some("2020-01-01".to_string()));
+                assert_eq!(input.series_id, None);
+                assert_eq!(input.to, None);
+                Ok(build_output())
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/stats?from=2020-01-01")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?,
+            ResponseBody {
+                count: 2,
+                current_streak: 2,
+                first_x_value: Some("2020-01-01".to_string()),
+                last: Some(ResponseBodyLast {
+                    delta: Some(Decimal::from_str("-1.5")?),
+                    x_value: "2020-01-02".to_string(),
+                    y_value: Decimal::from_str("1")?,
+                }),
+                last_x_value: Some("2020-01-02".to_string()),
+                longest_streak: 2,
+                max: Some(ResponseBodyExtremum {
+                    x_value: "2020-01-01".to_string(),
+                    y_value: Decimal::from_str("2.5")?,
+                }),
+                mean: Some(Decimal::from_str("1.75")?),
+                median: Some(Decimal::from_str("1.75")?),
+                min: Some(ResponseBodyExtremum {
+                    x_value: "2020-01-02".to_string(),
+                    y_value: Decimal::from_str("1")?,
+                }),
+                standard_deviation: Some(0.75),
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart_stats = {
+            let mut mock = MockGetChartStats::new();
+            mock.expect_execute().return_once(|input| {
+                assert_eq!(input.series_id, Some("series_id1".to_string()));
+                Ok(build_output())
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/series/series_id1/stats")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_chart_id() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart_stats = {
+            let mut mock = MockGetChartStats::new();
+            mock.expect_execute().return_once(|_| {
+                Err(query_use_case::get_chart_stats::Error::ChartId(
+                    <write_model::value_object::ChartId as std::str::FromStr>::from_str(
+                        "chart_id1",
+                    )
+                    .unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/charts/chart_id1/stats")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("chart_id".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chart_not_found() -> anyhow::Result<()> {
+        let chart_id = write_model::value_object::ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart_stats = {
+            let mut mock = MockGetChartStats::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(query_use_case::get_chart_stats::Error::ChartNotFound(
+                    chart_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&format!("/charts/{}/stats", chart_id))?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_series_not_found() -> anyhow::Result<()> {
+        let chart_id = write_model::value_object::ChartId::generate();
+        let series_id = write_model::value_object::SeriesId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart_stats = {
+            let mut mock = MockGetChartStats::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(query_use_case::get_chart_stats::Error::SeriesNotFound(
+                    series_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&format!("/charts/{}/series/{}/stats", chart_id, series_id))?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        get_chart_stats: Arc<MockGetChartStats>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior() -> Self {
+            let mut get_chart_stats = MockGetChartStats::new();
+            get_chart_stats
+                .expect_execute()
+                .return_once(|_| Ok(build_output()));
+            Self {
+                get_chart_stats: Arc::new(get_chart_stats),
+            }
+        }
+    }
+
+    impl query_use_case::get_chart_stats::HasGetChartStats for Mocks {
+        fn get_chart_stats(
+            &self,
+        ) -> Arc<dyn query_use_case::get_chart_stats::GetChartStats + Send + Sync> {
+            self.get_chart_stats.clone()
+        }
+    }
+
+    fn build_output() -> Output {
+        Output {
+            count: 2,
+            current_streak: 2,
+            first_x_value: Some("2020-01-01".to_string()),
+            last: Some(Last {
+                delta: Some("-1.5".to_string()),
+                x_value: "2020-01-02".to_string(),
+                y_value: "1".to_string(),
+            }),
+            last_x_value: Some("2020-01-02".to_string()),
+            longest_streak: 2,
+            max: Some(Extremum {
+                x_value: "2020-01-01".to_string(),
+                y_value: "2.5".to_string(),
+            }),
+            mean: Some("1.75".to_string()),
+            median: Some("1.75".to_string()),
+            min: Some(Extremum {
+                x_value: "2020-01-02".to_string(),
+                y_value: "1".to_string(),
+            }),
+            standard_deviation: Some(0.75),
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?)
+    }
+}
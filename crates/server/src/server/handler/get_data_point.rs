@@ -1,11 +1,18 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderName, HeaderValue},
+    Router,
 };
 
 use query_use_case::{self, get_data_point::HasGetDataPoint};
 
+use crate::server::handler::{
+    decimal::{Decimal, InvalidDecimal},
+    etag::etag_from_version,
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     data_point_id: String,
@@ -22,26 +29,46 @@ struct ResponseBody {
     chart_id: String,
     created_at: String,
     id: String,
+    series_id: Option<String>,
     x_value: String,
-    y_value: u32,
+    y_value: Decimal,
 }
 
-impl From<query_use_case::get_data_point::OutputItem> for ResponseBody {
-    fn from(
+impl TryFrom<query_use_case::get_data_point::OutputItem> for ResponseBody {
+    type Error = InvalidDecimal;
+
+    fn try_from(
         query_use_case::get_data_point::OutputItem {
             chart_id,
             created_at,
+            series_id,
+            version: _,
             x_value,
             y_value,
         }: query_use_case::get_data_point::OutputItem,
-    ) -> Self {
-        let id = format!("{}:{}", chart_id, x_value);
-        Self {
+    ) -> Result<Self, Self::Error> {
+        let id = match series_id.as_ref() {
+            None => format!("{}:{}", chart_id, x_value),
+            Some(series_id) => format!("{}:{}:{}", chart_id, series_id, x_value),
+        };
+        Ok(Self {
             chart_id,
             created_at,
             id,
+            series_id,
             x_value,
-            y_value,
+            y_value: y_value.parse()?,
+        })
+    }
+}
+
+impl From<query_use_case::get_data_point::Error> for Problem {
+    fn from(error: query_use_case::get_data_point::Error) -> Self {
+        use query_use_case::get_data_point::Error;
+        match &error {
+            Error::ChartGet(e) => Problem::from_chart_reader_error(&error, e),
+            Error::DataPointGet(e) => Problem::from_data_point_reader_error(&error, e),
+            Error::DataPointId(_) => Problem::invalid_parameter("data_point_id", &error),
         }
     }
 }
@@ -49,15 +76,18 @@ impl From<query_use_case::get_data_point::OutputItem> for ResponseBody {
 async fn handler<T: HasGetDataPoint>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
     let use_case = state.get_data_point();
     let query_use_case::get_data_point::Output(output_item) = use_case
         .execute(query_use_case::get_data_point::Input::from(path_parameters))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(Problem::from)?;
     match output_item {
-        Some(output) => Ok(Json(ResponseBody::from(output))),
-        None => Err(StatusCode::NOT_FOUND),
+        Some(output) => Ok((
+            [(header::ETAG, etag_from_version(output.version))],
+            Json(ResponseBody::try_from(output)?),
+        )),
+        None => Err(Problem::not_found("data point not found")),
     }
 }
 
@@ -70,8 +100,9 @@ pub fn router<T: Clone + HasGetDataPoint + Send + Sync + 'static>() -> Router<T>
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{str::FromStr as _, sync::Arc};
 
+    use axum::http::StatusCode;
     use query_use_case::{get_data_point::MockGetDataPoint, list_data_points::DataPoint};
     use write_model::value_object::DateTime;
 
@@ -89,6 +120,10 @@ mod tests {
         })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         let id = format!("{}:{}", data_point.chart_id, data_point.x_value);
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
@@ -96,8 +131,9 @@ mod tests {
                 chart_id: data_point.chart_id,
                 created_at: data_point.created_at,
                 id,
+                series_id: data_point.series_id,
                 x_value: data_point.x_value,
-                y_value: data_point.y_value,
+                y_value: Decimal::from_str(&data_point.y_value)?,
             }
         );
         Ok(())
@@ -122,7 +158,42 @@ mod tests {
         })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_data_point_id() -> anyhow::Result<()> {
+        let data_point = build_data_point();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point.clone());
+        mocks.get_data_point = {
+            let mut mock = MockGetDataPoint::new();
+            mock.expect_execute().return_once(|_| {
+                Err(query_use_case::get_data_point::Error::DataPointId(
+                    write_model::value_object::DataPointId::from_str("invalid").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters {
+            data_point_id: "invalid".to_string(),
+        })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("data point id: invalid format".to_string()),
+                field: Some("data_point_id".to_string()),
+                status: 400,
+                title: "Invalid parameter".to_string(),
+                r#type: "/problems/invalid-parameter".to_string(),
+            }
+        );
         Ok(())
     }
 
@@ -143,6 +214,8 @@ mod tests {
                     query_use_case::get_data_point::OutputItem {
                         chart_id: data_point.chart_id,
                         created_at: data_point.created_at,
+                        series_id: data_point.series_id,
+                        version: 2,
                         x_value: data_point.x_value,
                         y_value: data_point.y_value,
                     },
@@ -166,8 +239,11 @@ mod tests {
         DataPoint {
             chart_id: "chart_id1".to_string(),
             created_at: DateTime::now().to_string(),
+            id: "chart_id1:2020-01-02".to_string(),
+            series_id: None,
+            version: 2,
             x_value: "2020-01-02".to_string(),
-            y_value: 123_u32,
+            y_value: "-12.5".to_string(),
         }
     }
 
@@ -1,9 +1,34 @@
-use axum::Router;
+use axum::{response::Redirect, Router};
 
-async fn handler() -> &'static str {
-    "OK"
+async fn handler() -> Redirect {
+    Redirect::to("/ui/charts")
 }
 
 pub fn router<T: Clone + Send + Sync + 'static>() -> Router<T> {
     Router::new().route("/", axum::routing::get(handler))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderValue, StatusCode};
+
+    use crate::server::handler::tests::send_request;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_redirect_to_ui() -> anyhow::Result<()> {
+        let app = router();
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri("/")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&HeaderValue::from_static("/ui/charts"))
+        );
+        Ok(())
+    }
+}
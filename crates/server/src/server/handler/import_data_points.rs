@@ -0,0 +1,284 @@
+use std::str::FromStr as _;
+
+use axum::{extract::State, http::StatusCode, Router};
+
+use command_use_case::{self, import_data_points::HasImportDataPoints};
+
+use crate::server::handler::{
+    extract::{Json, Path, Query},
+    problem::Problem,
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    date_column: Option<String>,
+    #[serde(default)]
+    date_format: Option<String>,
+    #[serde(default)]
+    header: Option<bool>,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    series_id: Option<String>,
+    #[serde(default)]
+    value_column: Option<String>,
+}
+
+fn input_from_request(
+    PathParameters { chart_id }: PathParameters,
+    QueryParameters {
+        date_column,
+        date_format,
+        header,
+        mode,
+        series_id,
+        value_column,
+    }: QueryParameters,
+    csv: String,
+) -> Result<command_use_case::import_data_points::Input, Problem> {
+    let mode = mode
+        .as_deref()
+        .map(command_use_case::import_data_points::Mode::from_str)
+        .transpose()
+        .map_err(|e| Problem::invalid_parameter("mode", &e))?
+        .unwrap_or_default();
+    Ok(command_use_case::import_data_points::Input {
+        chart_id,
+        csv,
+        date_column,
+        date_format,
+        header,
+        mode,
+        series_id,
+        value_column,
+    })
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {
+    created: usize,
+    errors: Vec<ResponseBodyError>,
+    overwritten: usize,
+    skipped: usize,
+}
+
+impl From<command_use_case::import_data_points::Output> for ResponseBody {
+    fn from(
+        command_use_case::import_data_points::Output {
+            created,
+            errors,
+            overwritten,
+            skipped,
+        }: command_use_case::import_data_points::Output,
+    ) -> Self {
+        Self {
+            created,
+            errors: errors.into_iter().map(ResponseBodyError::from).collect(),
+            overwritten,
+            skipped,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBodyError {
+    line: u64,
+    message: String,
+}
+
+impl From<command_use_case::import_data_points::RowError> for ResponseBodyError {
+    fn from(
+        command_use_case::import_data_points::RowError { line, message }: command_use_case::import_data_points::RowError,
+    ) -> Self {
+        Self { line, message }
+    }
+}
+
+impl From<command_use_case::import_data_points::Error> for Problem {
+    fn from(error: command_use_case::import_data_points::Error) -> Self {
+        use command_use_case::import_data_points::Error;
+        match error {
+            Error::DataPointCreate { source, .. } => Problem::from(source),
+            Error::DataPointUpdate { source, .. } => Problem::from(source),
+            error => match &error {
+                Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+                Error::ColumnNotFound(_) => Problem::validation_failed(None, &error),
+                Error::DataPointFind(e) => Problem::from_data_point_repository_error(&error, e),
+                Error::DateFormat => Problem::invalid_parameter("date_format", &error),
+                Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+                Error::DataPointCreate { .. } | Error::DataPointUpdate { .. } => unreachable!(),
+            },
+        }
+    }
+}
+
+/// Imports the CSV request body. Responds 422 with the row errors if any row cannot be imported.
+/// If a write fails, the rows before it stay imported. `mode=skip` resumes the import
+async fn handler<T: HasImportDataPoints>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+    Query(query_parameters): Query<QueryParameters>,
+    body: String,
+) -> Result<(StatusCode, Json<ResponseBody>), Problem> {
+    let input = input_from_request(path_parameters, query_parameters, body)?;
+    let use_case = state.import_data_points();
+    let output = use_case.execute(input).await.map_err(Problem::from)?;
+    let status = if output.errors.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    Ok((status, Json(ResponseBody::from(output))))
+}
+
+pub fn router<T: Clone + HasImportDataPoints + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/charts/:chart_id/data_points/import",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use command_use_case::import_data_points::{MockImportDataPoints, Mode, RowError};
+    use write_model::value_object::ChartId;
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut import_data_points = MockImportDataPoints::new();
+        import_data_points
+            .expect_execute()
+            .withf(move |input| {
+                input.chart_id == chart_id.to_string()
+                    && input.csv == "date,value\n2020-01-02,3\n"
+                    && input.date_format == Some("YYYY-MM-DD".to_string())
+                    && input.header == Some(true)
+                    && input.mode == Mode::Skip
+                    && input.date_column.is_none()
+                    && input.series_id.is_none()
+                    && input.value_column.is_none()
+            })
+            .return_once(|_| {
+                Ok(command_use_case::import_data_points::Output {
+                    created: 1,
+                    errors: vec![],
+                    overwritten: 0,
+                    skipped: 0,
+                })
+            });
+        let mocks = Mocks {
+            import_data_points: Arc::new(import_data_points),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &format!(
+                "/charts/{}/data_points/import?date_format=YYYY-MM-DD&header=true&mode=skip",
+                chart_id
+            ),
+            "date,value\n2020-01-02,3\n",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?,
+            ResponseBody {
+                created: 1,
+                errors: vec![],
+                overwritten: 0,
+                skipped: 0,
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_row_errors() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mut import_data_points = MockImportDataPoints::new();
+        import_data_points.expect_execute().return_once(|_| {
+            Ok(command_use_case::import_data_points::Output {
+                created: 0,
+                errors: vec![RowError {
+                    line: 2,
+                    message: "invalid value (value = x)".to_string(),
+                }],
+                overwritten: 0,
+                skipped: 0,
+            })
+        });
+        let mocks = Mocks {
+            import_data_points: Arc::new(import_data_points),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &format!("/charts/{}/data_points/import", chart_id),
+            "date,value\n2020-01-02,x\n",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?.errors,
+            vec![ResponseBodyError {
+                line: 2,
+                message: "invalid value (value = x)".to_string(),
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_mode() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let mocks = Mocks {
+            import_data_points: Arc::new(MockImportDataPoints::new()),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &format!("/charts/{}/data_points/import?mode=replace", chart_id),
+            "",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = response.into_body_as_json::<Problem>().await?;
+        assert_eq!(problem.r#type, "/problems/invalid-parameter");
+        assert_eq!(problem.field, Some("mode".to_string()));
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        import_data_points: Arc<MockImportDataPoints>,
+    }
+
+    impl command_use_case::import_data_points::HasImportDataPoints for Mocks {
+        fn import_data_points(
+            &self,
+        ) -> Arc<dyn command_use_case::import_data_points::ImportDataPoints + Send + Sync> {
+            self.import_data_points.clone()
+        }
+    }
+
+    fn build_request(
+        uri: &str,
+        body: &str,
+    ) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::POST)
+            .uri(uri)
+            .header(axum::http::header::CONTENT_TYPE, "text/csv")
+            .body(axum::body::Body::from(body.to_owned()))?)
+    }
+}
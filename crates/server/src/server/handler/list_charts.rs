@@ -1,18 +1,58 @@
-use axum::{extract::State, http::StatusCode, Json, Router};
+use axum::{extract::State, Router};
 
 use query_use_case::{self, list_charts::HasListCharts};
 
+use crate::server::handler::{
+    extract::{Json, Query},
+    problem::Problem,
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    cursor: Option<String>,
+    #[serde(default)]
+    limit: Option<usize>,
+    #[serde(default)]
+    order: Option<String>,
+    #[serde(default)]
+    sort: Option<String>,
+}
+
+impl From<QueryParameters> for query_use_case::list_charts::Input {
+    fn from(
+        QueryParameters {
+            cursor,
+            limit,
+            order,
+            sort,
+        }: QueryParameters,
+    ) -> Self {
+        Self {
+            cursor,
+            limit,
+            order,
+            sort,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct ResponseBody {
     charts: Vec<ResponseBodyChart>,
+    next_cursor: Option<String>,
 }
 
 impl From<query_use_case::list_charts::Output> for ResponseBody {
     fn from(
-        query_use_case::list_charts::Output(charts): query_use_case::list_charts::Output,
+        query_use_case::list_charts::Output {
+            charts,
+            next_cursor,
+        }: query_use_case::list_charts::Output,
     ) -> Self {
         Self {
             charts: charts.into_iter().map(ResponseBodyChart::from).collect(),
+            next_cursor,
         }
     }
 }
@@ -22,6 +62,7 @@ struct ResponseBodyChart {
     created_at: String,
     id: String,
     title: String,
+    updated_at: String,
 }
 
 impl From<query_use_case::list_charts::Chart> for ResponseBodyChart {
@@ -30,24 +71,38 @@ impl From<query_use_case::list_charts::Chart> for ResponseBodyChart {
             created_at,
             id,
             title,
+            updated_at,
         }: query_use_case::list_charts::Chart,
     ) -> Self {
         Self {
             created_at,
             id,
             title,
+            updated_at,
+        }
+    }
+}
+
+impl From<query_use_case::list_charts::Error> for Problem {
+    fn from(error: query_use_case::list_charts::Error) -> Self {
+        use query_use_case::list_charts::Error;
+        match &error {
+            Error::ChartList(e) => Problem::from_chart_reader_error(&error, e),
+            Error::Cursor(_) => Problem::invalid_parameter("cursor", &error),
+            Error::Limit => Problem::invalid_parameter("limit", &error),
+            Error::Order => Problem::invalid_parameter("order", &error),
+            Error::Sort => Problem::invalid_parameter("sort", &error),
         }
     }
 }
 
 async fn handler<T: HasListCharts>(
     State(state): State<T>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<Json<ResponseBody>, Problem> {
     let use_case = state.list_charts();
-    let output = use_case
-        .execute(query_use_case::list_charts::Input)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let input = query_use_case::list_charts::Input::from(query_parameters);
+    let output = use_case.execute(input).await.map_err(Problem::from)?;
     Ok(Json(ResponseBody::from(output)))
 }
 
@@ -59,6 +114,7 @@ pub fn router<T: Clone + HasListCharts + Send + Sync + 'static>() -> Router<T> {
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use query_use_case::{list_charts::Chart, list_charts::MockListCharts};
     use write_model::value_object::DateTime;
 
@@ -80,13 +136,72 @@ mod tests {
                 charts: vec![ResponseBodyChart {
                     created_at: chart.created_at,
                     id: chart.id,
-                    title: chart.title
-                }]
+                    title: chart.title,
+                    updated_at: chart.updated_at,
+                }],
+                next_cursor: Some("cursor1".to_string()),
             }
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_query_parameters() -> anyhow::Result<()> {
+        let chart = build_chart();
+        let mut mocks = Mocks::with_happy_path_behavior(chart.clone());
+        mocks.list_charts = {
+            let mut mock = MockListCharts::new();
+            mock.expect_execute().return_once(move |input| {
+                assert_eq!(input.cursor, Some("cursor1".to_string()));
+                assert_eq!(input.limit, Some(10));
+                assert_eq!(input.order, Some("desc".to_string()));
+                assert_eq!(input.sort, Some("updated_at".to_string()));
+                Ok(query_use_case::list_charts::Output {
+                    charts: vec![chart],
+                    next_cursor: None,
+                })
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri("/charts?cursor=cursor1&limit=10&order=desc&sort=updated_at")
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .into_body_as_json::<ResponseBody>()
+                .await?
+                .next_cursor,
+            None
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_sort() -> anyhow::Result<()> {
+        let chart = build_chart();
+        let mut mocks = Mocks::with_happy_path_behavior(chart.clone());
+        mocks.list_charts = {
+            let mut mock = MockListCharts::new();
+            mock.expect_execute()
+                .return_once(|_| Err(query_use_case::list_charts::Error::Sort));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request()?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("sort".to_string())
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_error() -> anyhow::Result<()> {
         let chart = build_chart();
@@ -104,7 +219,10 @@ mod tests {
         let request = build_request()?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
         Ok(())
     }
 
@@ -116,9 +234,12 @@ mod tests {
     impl Mocks {
         fn with_happy_path_behavior(chart: Chart) -> Self {
             let mut list_charts = MockListCharts::new();
-            list_charts
-                .expect_execute()
-                .return_once(move |_| Ok(query_use_case::list_charts::Output(vec![chart.clone()])));
+            list_charts.expect_execute().return_once(move |_| {
+                Ok(query_use_case::list_charts::Output {
+                    charts: vec![chart.clone()],
+                    next_cursor: Some("cursor1".to_string()),
+                })
+            });
             Self {
                 list_charts: Arc::new(list_charts),
             }
@@ -136,6 +257,7 @@ mod tests {
             created_at: DateTime::now().to_string(),
             id: "chart_id1".to_string(),
             title: "title1".to_string(),
+            updated_at: DateTime::now().to_string(),
         }
     }
 
@@ -1,19 +1,42 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
-};
+use axum::{extract::State, Router};
 
 use query_use_case::{self, list_data_points::HasListDataPoints};
 
+use crate::server::handler::{
+    decimal::{Decimal, InvalidDecimal},
+    extract::{Json, Path, Query},
+    problem::Problem,
+};
+
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct PathParameters {
     chart_id: String,
+    #[serde(default)]
+    series_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
 }
 
-impl From<PathParameters> for query_use_case::list_data_points::Input {
-    fn from(PathParameters { chart_id }: PathParameters) -> Self {
-        Self { chart_id }
+fn input_from_request(
+    PathParameters {
+        chart_id,
+        series_id,
+    }: PathParameters,
+    QueryParameters { from, to }: QueryParameters,
+) -> query_use_case::list_data_points::Input {
+    query_use_case::list_data_points::Input {
+        after: None,
+        chart_id,
+        from,
+        limit: None,
+        series_id,
+        to,
     }
 }
 
@@ -22,16 +45,18 @@ struct ResponseBody {
     data_points: Vec<ResponseBodyDataPoint>,
 }
 
-impl From<query_use_case::list_data_points::Output> for ResponseBody {
-    fn from(
+impl TryFrom<query_use_case::list_data_points::Output> for ResponseBody {
+    type Error = InvalidDecimal;
+
+    fn try_from(
         query_use_case::list_data_points::Output(data_points): query_use_case::list_data_points::Output,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             data_points: data_points
                 .into_iter()
-                .map(ResponseBodyDataPoint::from)
-                .collect(),
-        }
+                .map(ResponseBodyDataPoint::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 }
 
@@ -40,26 +65,47 @@ struct ResponseBodyDataPoint {
     chart_id: String,
     created_at: String,
     id: String,
+    series_id: Option<String>,
     x_value: String,
-    y_value: u32,
+    y_value: Decimal,
 }
 
-impl From<query_use_case::list_data_points::DataPoint> for ResponseBodyDataPoint {
-    fn from(
+impl TryFrom<query_use_case::list_data_points::DataPoint> for ResponseBodyDataPoint {
+    type Error = InvalidDecimal;
+
+    fn try_from(
         query_use_case::list_data_points::DataPoint {
             chart_id,
             created_at,
+            id,
+            series_id,
+            version: _,
             x_value,
             y_value,
         }: query_use_case::list_data_points::DataPoint,
-    ) -> Self {
-        let id = format!("{}:{}", chart_id, x_value);
-        Self {
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             chart_id,
             created_at,
             id,
+            series_id,
             x_value,
-            y_value,
+            y_value: y_value.parse()?,
+        })
+    }
+}
+
+impl From<query_use_case::list_data_points::Error> for Problem {
+    fn from(error: query_use_case::list_data_points::Error) -> Self {
+        use query_use_case::list_data_points::Error;
+        match &error {
+            Error::After(_) => Problem::invalid_parameter("after", &error),
+            Error::ChartGet(e) => Problem::from_chart_reader_error(&error, e),
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::DataPointList(e) => Problem::from_data_point_reader_error(&error, e),
+            Error::From(_) => Problem::invalid_parameter("from", &error),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+            Error::To(_) => Problem::invalid_parameter("to", &error),
         }
     }
 }
@@ -67,27 +113,31 @@ impl From<query_use_case::list_data_points::DataPoint> for ResponseBodyDataPoint
 async fn handler<T: HasListDataPoints>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+    Query(query_parameters): Query<QueryParameters>,
+) -> Result<Json<ResponseBody>, Problem> {
     let use_case = state.list_data_points();
-    let input = query_use_case::list_data_points::Input::from(path_parameters);
-    let output = use_case
-        .execute(input)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ResponseBody::from(output)))
+    let input = input_from_request(path_parameters, query_parameters);
+    let output = use_case.execute(input).await.map_err(Problem::from)?;
+    Ok(Json(ResponseBody::try_from(output)?))
 }
 
 pub fn router<T: Clone + HasListDataPoints + Send + Sync + 'static>() -> Router<T> {
-    Router::new().route(
-        "/charts/:chart_id/data_points",
-        axum::routing::get(handler::<T>),
-    )
+    Router::new()
+        .route(
+            "/charts/:chart_id/data_points",
+            axum::routing::get(handler::<T>),
+        )
+        .route(
+            "/charts/:chart_id/series/:series_id/data_points",
+            axum::routing::get(handler::<T>),
+        )
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{str::FromStr as _, sync::Arc};
 
+    use axum::http::StatusCode;
     use query_use_case::{list_data_points::DataPoint, list_data_points::MockListDataPoints};
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -102,22 +152,107 @@ mod tests {
         let request = build_request(&data_point.chart_id)?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
-        let id = format!("{}:{}", data_point.chart_id, data_point.x_value);
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {
                 data_points: vec![ResponseBodyDataPoint {
                     chart_id: data_point.chart_id,
                     created_at: data_point.created_at,
-                    id,
+                    id: data_point.id,
+                    series_id: data_point.series_id,
                     x_value: data_point.x_value,
-                    y_value: data_point.y_value,
+                    y_value: Decimal::from_str(&data_point.y_value)?,
                 }]
             }
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_series() -> anyhow::Result<()> {
+        let mut data_point = build_data_point();
+        data_point.id = "chart_id1:series_id1:2020-01-02".to_string();
+        data_point.series_id = Some("series_id1".to_string());
+        let mut mocks = Mocks::with_happy_path_behavior(data_point.clone());
+        mocks.list_data_points = {
+            let data_point = data_point.clone();
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute().return_once(move |input| {
+                assert_eq!(input.chart_id, data_point.chart_id);
+                assert_eq!(input.series_id, data_point.series_id);
+                Ok(query_use_case::list_data_points::Output(vec![data_point]))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/charts/{}/series/series_id1/data_points",
+                data_point.chart_id
+            ))
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body_as_json::<ResponseBody>().await?;
+        assert_eq!(body.data_points[0].id, data_point.id);
+        assert_eq!(body.data_points[0].series_id, data_point.series_id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_range() -> anyhow::Result<()> {
+        let data_point = build_data_point();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point.clone());
+        mocks.list_data_points = {
+            let data_point = data_point.clone();
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute().return_once(move |input| {
+                assert_eq!(input.from, Some("2020-01-01".to_string()));
+                assert_eq!(input.to, Some("2020-02-01".to_string()));
+                Ok(query_use_case::list_data_points::Output(vec![data_point]))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/charts/{}/data_points?from=2020-01-01&to=2020-02-01",
+                data_point.chart_id
+            ))
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_from() -> anyhow::Result<()> {
+        let data_point = build_data_point();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point.clone());
+        mocks.list_data_points = {
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute().return_once(|_| {
+                Err(query_use_case::list_data_points::Error::From(
+                    write_model::value_object::XValue::from_str("2020-13-01").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&data_point.chart_id)?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("from".to_string())
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_error() -> anyhow::Result<()> {
         let data_point = build_data_point();
@@ -135,7 +270,34 @@ mod tests {
         let request = build_request(&data_point.chart_id)?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_chart_id() -> anyhow::Result<()> {
+        let data_point = build_data_point();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point.clone());
+        mocks.list_data_points = {
+            let mut mock = MockListDataPoints::new();
+            mock.expect_execute().return_once(|_| {
+                Err(query_use_case::list_data_points::Error::ChartId(
+                    write_model::value_object::ChartId::from_str("chart_id1").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&data_point.chart_id)?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("chart_id".to_string())
+        );
         Ok(())
     }
 
@@ -170,8 +332,11 @@ mod tests {
         DataPoint {
             chart_id: "chart_id1".to_string(),
             created_at: "created_at1".to_string(),
+            id: "chart_id1:2020-01-02".to_string(),
+            series_id: None,
+            version: 1,
             x_value: "2020-01-02".to_string(),
-            y_value: 123_u32,
+            y_value: "-12.5".to_string(),
         }
     }
 
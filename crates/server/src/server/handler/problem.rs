@@ -0,0 +1,335 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::server::handler::extract::error_field;
+
+/// Problem details (RFC 9457) returned as an error response body
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub(crate) struct Problem {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) field: Option<String>,
+    pub(crate) status: u16,
+    pub(crate) title: String,
+    #[serde(rename = "type")]
+    pub(crate) r#type: String,
+}
+
+impl Problem {
+    /// 400 Bad Request: a path parameter or a request body field is malformed
+    pub(crate) fn invalid_parameter(field: &str, error: &dyn std::error::Error) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid-parameter",
+            "Invalid parameter",
+            Some(error_chain(error)),
+            Some(field),
+        )
+    }
+
+    /// 400 Bad Request: the request cannot be deserialized. Names the offending field if it is known
+    pub(crate) fn invalid_request(field: Option<&str>, error: &dyn std::error::Error) -> Self {
+        match field {
+            Some(field) => Self::invalid_parameter(field, error),
+            None => Self::new(
+                StatusCode::BAD_REQUEST,
+                "invalid-request",
+                "Invalid request",
+                Some(error_chain(error)),
+                None,
+            ),
+        }
+    }
+
+    /// 404 Not Found: the requested resource or a resource referenced by the request does not exist
+    pub(crate) fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "not-found",
+            "Not found",
+            Some(detail.into()),
+            None,
+        )
+    }
+
+    /// 409 Conflict: the request conflicts with the current state of the resource
+    pub(crate) fn conflict(error: &dyn std::error::Error) -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            "conflict",
+            "Conflict",
+            Some(error_chain(error)),
+            None,
+        )
+    }
+
+    /// 412 Precondition Failed: the `If-Match` header does not match the current version
+    pub(crate) fn precondition_failed(error: &dyn std::error::Error) -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_FAILED,
+            "precondition-failed",
+            "Precondition failed",
+            Some(error_chain(error)),
+            None,
+        )
+    }
+
+    /// 415 Unsupported Media Type: the request body is not `application/json`
+    pub(crate) fn unsupported_media_type(error: &dyn std::error::Error) -> Self {
+        Self::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported-media-type",
+            "Unsupported media type",
+            Some(error_chain(error)),
+            None,
+        )
+    }
+
+    /// 422 Unprocessable Content: the request is well-formed but violates a domain rule
+    pub(crate) fn validation_failed(field: Option<&str>, error: &dyn std::error::Error) -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation-failed",
+            "Validation failed",
+            Some(error_chain(error)),
+            field,
+        )
+    }
+
+    /// 500 Internal Server Error: the error is logged and its details are not exposed
+    pub(crate) fn internal_server_error(error: &dyn std::error::Error) -> Self {
+        tracing::error!("{}", error_chain(error));
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal-server-error",
+            "Internal server error",
+            None,
+            None,
+        )
+    }
+
+    /// 503 Service Unavailable: the error is logged and its details are not exposed
+    pub(crate) fn service_unavailable(error: &dyn std::error::Error) -> Self {
+        tracing::warn!("{}", error_chain(error));
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "service-unavailable",
+            "Service unavailable",
+            None,
+            None,
+        )
+    }
+
+    /// Maps a rejection of `axum::Json`
+    pub(crate) fn from_json_rejection(rejection: &JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(e) => {
+                let field =
+                    std::iter::successors(Some(e as &(dyn std::error::Error + 'static)), |e| {
+                        e.source()
+                    })
+                    .find_map(|e| e.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>())
+                    .and_then(|e| error_field(&e.path().to_string(), &e.inner().to_string()));
+                Self::invalid_request(field.as_deref(), e)
+            }
+            JsonRejection::MissingJsonContentType(e) => Self::unsupported_media_type(e),
+            JsonRejection::JsonSyntaxError(_) | JsonRejection::BytesRejection(_) => Self::new(
+                rejection.status(),
+                "invalid-request",
+                "Invalid request",
+                Some(error_chain(rejection)),
+                None,
+            ),
+            _ => Self::internal_server_error(rejection),
+        }
+    }
+
+    /// Maps a rejection of `axum::extract::Path`
+    pub(crate) fn from_path_rejection(rejection: &PathRejection) -> Self {
+        use axum::extract::path::ErrorKind;
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(e) => match e.kind() {
+                ErrorKind::InvalidUtf8InPathParam { key }
+                | ErrorKind::ParseErrorAtKey { key, .. } => Self::invalid_parameter(key, e),
+                _ if e.status().is_client_error() => Self::invalid_request(None, e),
+                _ => Self::internal_server_error(e),
+            },
+            _ => Self::internal_server_error(rejection),
+        }
+    }
+
+    /// Maps an error of the `Chart` aggregate wrapped in the use case error `outer`
+    pub(crate) fn from_chart_error(
+        outer: &dyn std::error::Error,
+        error: &write_model::aggregate::chart::Error,
+    ) -> Self {
+        use write_model::aggregate::chart::Error;
+        match error {
+            Error::AlreadyDeleted => Self::conflict(outer),
+            Error::InvalidSeriesName => Self::validation_failed(Some("name"), outer),
+            Error::InvalidTitle => Self::validation_failed(Some("title"), outer),
+            Error::SeriesNotFound => Self::not_found(error_chain(outer)),
+            Error::MultipleCreatedEvent | Error::NoCreatedEvent | Error::VersionOverflow => {
+                Self::internal_server_error(outer)
+            }
+        }
+    }
+
+    /// Maps an error of the `DataPoint` aggregate wrapped in the use case error `outer`
+    pub(crate) fn from_data_point_error(
+        outer: &dyn std::error::Error,
+        error: &write_model::aggregate::data_point::Error,
+    ) -> Self {
+        use write_model::aggregate::data_point::Error;
+        match error {
+            Error::AlreadyDeleted => Self::conflict(outer),
+            Error::MultipleCreatedEvent | Error::NoCreatedEvent | Error::VersionOverflow => {
+                Self::internal_server_error(outer)
+            }
+        }
+    }
+
+    /// Maps an error of the `ChartRepository` port wrapped in the use case error `outer`
+    pub(crate) fn from_chart_repository_error(
+        outer: &dyn std::error::Error,
+        error: &command_use_case::port::chart_repository::Error,
+    ) -> Self {
+        use command_use_case::port::chart_repository::Error;
+        match error {
+            Error::Conflict(_) => Self::conflict(outer),
+            Error::NotFound(_) => Self::not_found(error_chain(outer)),
+            Error::Unavailable(_) => Self::service_unavailable(outer),
+            Error::Corrupted(_) | Error::Other(_) => Self::internal_server_error(outer),
+        }
+    }
+
+    /// Maps an error of the `DataPointRepository` port wrapped in the use case error `outer`
+    pub(crate) fn from_data_point_repository_error(
+        outer: &dyn std::error::Error,
+        error: &command_use_case::port::data_point_repository::Error,
+    ) -> Self {
+        use command_use_case::port::data_point_repository::Error;
+        match error {
+            Error::Conflict(_) => Self::conflict(outer),
+            Error::NotFound(_) => Self::not_found(error_chain(outer)),
+            Error::Unavailable(_) => Self::service_unavailable(outer),
+            Error::Corrupted(_) | Error::Other(_) => Self::internal_server_error(outer),
+        }
+    }
+
+    /// Maps an error of the `ChartReader` port wrapped in the use case error `outer`
+    pub(crate) fn from_chart_reader_error(
+        outer: &dyn std::error::Error,
+        error: &query_use_case::port::chart_reader::Error,
+    ) -> Self {
+        use query_use_case::port::chart_reader::Error;
+        match error {
+            Error::Unavailable(_) => Self::service_unavailable(outer),
+            Error::Corrupted(_) | Error::Other(_) => Self::internal_server_error(outer),
+        }
+    }
+
+    /// Maps an error of the `DataPointReader` port wrapped in the use case error `outer`
+    pub(crate) fn from_data_point_reader_error(
+        outer: &dyn std::error::Error,
+        error: &query_use_case::port::data_point_reader::Error,
+    ) -> Self {
+        use query_use_case::port::data_point_reader::Error;
+        match error {
+            Error::Unavailable(_) => Self::service_unavailable(outer),
+            Error::Corrupted(_) | Error::Other(_) => Self::internal_server_error(outer),
+        }
+    }
+
+    fn new(
+        status: StatusCode,
+        r#type: &str,
+        title: &str,
+        detail: Option<String>,
+        field: Option<&str>,
+    ) -> Self {
+        Self {
+            detail,
+            field: field.map(ToOwned::to_owned),
+            status: status.as_u16(),
+            title: title.to_owned(),
+            r#type: format!("/problems/{}", r#type),
+        }
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, Json(self)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+        response
+    }
+}
+
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut s = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        s.push_str(": ");
+        s.push_str(&e.to_string());
+        source = e.source();
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::handler::tests::ResponseExt as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_into_response() -> anyhow::Result<()> {
+        let error = std::io::Error::other("error");
+        let response = Problem::invalid_parameter("x_value", &error).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("application/problem+json"))
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&response.into_body_string().await?)?,
+            serde_json::json!({
+                "detail": "error",
+                "field": "x_value",
+                "status": 400,
+                "title": "Invalid parameter",
+                "type": "/problems/invalid-parameter",
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_internal_server_error_hides_detail() {
+        let error = std::io::Error::other("secret");
+        let problem = Problem::internal_server_error(&error);
+        assert_eq!(problem.status, 500);
+        assert_eq!(problem.detail, None);
+    }
+
+    #[test]
+    fn test_error_chain() {
+        #[derive(Debug, thiserror::Error)]
+        #[error("outer")]
+        struct Outer(#[source] std::io::Error);
+        assert_eq!(
+            error_chain(&Outer(std::io::Error::other("inner"))),
+            "outer: inner"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use axum::{extract::State, Router};
+
+use command_use_case::{self, rename_series::HasRenameSeries};
+
+use crate::server::handler::{
+    extract::{Json, Path},
+    problem::Problem,
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+    series_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RequestBody {
+    name: String,
+}
+
+fn input_from_request(
+    PathParameters {
+        chart_id,
+        series_id,
+    }: PathParameters,
+    RequestBody { name }: RequestBody,
+) -> command_use_case::rename_series::Input {
+    command_use_case::rename_series::Input {
+        chart_id,
+        name,
+        series_id,
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ResponseBody {}
+
+impl From<command_use_case::rename_series::Error> for Problem {
+    fn from(error: command_use_case::rename_series::Error) -> Self {
+        use command_use_case::rename_series::Error;
+        match &error {
+            Error::ChartFind(e) | Error::ChartStore(e) => {
+                Problem::from_chart_repository_error(&error, e)
+            }
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::SeriesId(_) => Problem::invalid_parameter("series_id", &error),
+            Error::SeriesRename(e) => Problem::from_chart_error(&error, e),
+        }
+    }
+}
+
+async fn handler<T: HasRenameSeries>(
+    State(state): State<T>,
+    Path(path_parameters): Path<PathParameters>,
+    Json(body): Json<RequestBody>,
+) -> Result<Json<ResponseBody>, Problem> {
+    let use_case = state.rename_series();
+    let _output = use_case
+        .execute(input_from_request(path_parameters, body))
+        .await
+        .map_err(Problem::from)?;
+    Ok(Json(ResponseBody {}))
+}
+
+pub fn router<T: Clone + HasRenameSeries + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/charts/:chart_id/series/:series_id",
+        axum::routing::patch(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr as _, sync::Arc};
+
+    use axum::http::StatusCode;
+    use command_use_case::rename_series::MockRenameSeries;
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "name1".to_string();
+        let series_id = "series_id1".to_string();
+        let mocks =
+            Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id.clone());
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id,
+                series_id,
+            },
+            &RequestBody { name },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.into_body_as_json::<ResponseBody>().await?,
+            ResponseBody {}
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "name1".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks =
+            Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id.clone());
+        mocks.rename_series = {
+            let mut mock = MockRenameSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::rename_series::Error::ChartStore(
+                    command_use_case::port::chart_repository::Error::from(build_error()),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id,
+                series_id,
+            },
+            &RequestBody { name },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_series_id() -> anyhow::Result<()> {
+        let chart_id = "chart_id1".to_string();
+        let name = "name1".to_string();
+        let series_id = "series_id1".to_string();
+        let mut mocks =
+            Mocks::with_happy_path_behavior(chart_id.clone(), name.clone(), series_id.clone());
+        mocks.rename_series = {
+            let mut mock = MockRenameSeries::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::rename_series::Error::SeriesId(
+                    write_model::value_object::SeriesId::from_str("series_id1").unwrap_err(),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id,
+                series_id,
+            },
+            &RequestBody { name },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.field,
+            Some("series_id".to_string())
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        rename_series: Arc<MockRenameSeries>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior(chart_id: String, name: String, series_id: String) -> Self {
+            let mut rename_series = MockRenameSeries::new();
+            rename_series.expect_execute().return_once(move |input| {
+                assert_eq!(input.chart_id, chart_id);
+                assert_eq!(input.name, name);
+                assert_eq!(input.series_id, series_id);
+                Ok(command_use_case::rename_series::Output)
+            });
+            Self {
+                rename_series: Arc::new(rename_series),
+            }
+        }
+    }
+
+    impl command_use_case::rename_series::HasRenameSeries for Mocks {
+        fn rename_series(
+            &self,
+        ) -> Arc<dyn command_use_case::rename_series::RenameSeries + Send + Sync> {
+            self.rename_series.clone()
+        }
+    }
+
+    fn build_error() -> Box<dyn std::error::Error + Send + Sync> {
+        Box::new(std::io::Error::new(std::io::ErrorKind::Other, "error"))
+    }
+
+    fn build_request<T: serde::Serialize>(
+        path_parameters: &PathParameters,
+        request_body: &T,
+    ) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::PATCH)
+            .uri(format!(
+                "/charts/{}/series/{}",
+                path_parameters.chart_id, path_parameters.series_id
+            ))
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(serde_json::to_string(request_body)?))?)
+    }
+}
@@ -0,0 +1,166 @@
+mod create_chart;
+mod create_data_point;
+mod delete_chart;
+mod delete_data_point;
+mod get_chart;
+mod list_charts;
+mod update_chart;
+mod update_data_point;
+
+use axum::{
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    Router,
+};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use chart_renderer::escape;
+use command_use_case::{
+    create_chart::HasCreateChart, create_data_point::HasCreateDataPoint,
+    delete_chart::HasDeleteChart, delete_data_point::HasDeleteDataPoint,
+    update_chart::HasUpdateChart, update_data_point::HasUpdateDataPoint,
+};
+use query_use_case::{
+    get_chart::HasGetChart, list_charts::HasListCharts, list_data_points::HasListDataPoints,
+};
+
+use crate::server::handler::problem::Problem;
+
+/// HTML pages for browsers. Forms are submitted as `POST` requests and answered with a redirect,
+/// so the pages work without JavaScript.
+pub fn router<
+    T: Clone
+        + HasCreateChart
+        + HasCreateDataPoint
+        + HasDeleteChart
+        + HasDeleteDataPoint
+        + HasGetChart
+        + HasListCharts
+        + HasListDataPoints
+        + HasUpdateChart
+        + HasUpdateDataPoint
+        + Send
+        + Sync
+        + 'static,
+>() -> Router<T> {
+    Router::new()
+        .merge(create_chart::router())
+        .merge(create_data_point::router())
+        .merge(delete_chart::router())
+        .merge(delete_data_point::router())
+        .merge(get_chart::router())
+        .merge(list_charts::router())
+        .merge(update_chart::router())
+        .merge(update_data_point::router())
+}
+
+/// An error shown as an HTML page with the status code of the problem
+#[derive(Debug)]
+pub(crate) struct ErrorPage(Problem);
+
+impl From<Problem> for ErrorPage {
+    fn from(problem: Problem) -> Self {
+        Self(problem)
+    }
+}
+
+impl IntoResponse for ErrorPage {
+    fn into_response(self) -> Response {
+        let Self(problem) = self;
+        let status =
+            StatusCode::from_u16(problem.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut body = String::new();
+        if let Some(detail) = problem.detail.as_deref() {
+            body.push_str(&format!("<p>{}</p>", escape(detail)));
+        }
+        body.push_str(r#"<p><a href="/ui/charts">Back to the charts</a></p>"#);
+        (status, page(&problem.title, &body)).into_response()
+    }
+}
+
+/// Characters kept as they are in a path segment. `:` separates the parts of a data point id
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b':')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encodes the id for a path segment of a URL
+pub(crate) fn path_segment(s: &str) -> String {
+    utf8_percent_encode(s, PATH_SEGMENT).to_string()
+}
+
+/// Wraps the body in the HTML document shared by all pages
+pub(crate) fn page(title: &str, body: &str) -> Html<String> {
+    Html(format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} - date-line-charts</title>
+<style>
+body {{ font-family: sans-serif; margin: 0 auto; max-width: 960px; padding: 0 16px; }}
+form.inline {{ display: inline; }}
+img {{ max-width: 100%; height: auto; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border-bottom: 1px solid #ddd; padding: 4px 8px; text-align: left; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}
+</body>
+</html>
+"#,
+        title = escape(title),
+        body = body,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    pub(crate) fn build_form_request(
+        uri: &str,
+        body: &str,
+    ) -> anyhow::Result<axum::http::Request<Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::POST)
+            .uri(uri)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(Body::from(body.to_owned()))?)
+    }
+
+    #[test]
+    fn test_path_segment() {
+        assert_eq!(path_segment("chart_id1:2020-01-02"), "chart_id1:2020-01-02");
+        assert_eq!(path_segment("a/b?c#d e"), "a%2Fb%3Fc%23d%20e");
+    }
+
+    #[tokio::test]
+    async fn test_error_page() -> anyhow::Result<()> {
+        async fn handler() -> Result<Html<String>, ErrorPage> {
+            Err(Problem::not_found("chart <1> not found"))?
+        }
+        let app = Router::new().route("/", axum::routing::get(handler));
+        let request = axum::http::Request::builder()
+            .uri("/")
+            .body(Body::empty())?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = response.into_body_string().await?;
+        assert!(body.contains("<h1>Not found</h1>"));
+        assert!(body.contains("<p>chart &lt;1&gt; not found</p>"));
+        Ok(())
+    }
+}
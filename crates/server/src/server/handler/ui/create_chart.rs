@@ -0,0 +1,78 @@
+use axum::{extract::State, response::Redirect, Form, Router};
+
+use command_use_case::{self, create_chart::HasCreateChart};
+
+use crate::server::handler::{problem::Problem, ui::ErrorPage};
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    title: String,
+}
+
+async fn handler<T: HasCreateChart>(
+    State(state): State<T>,
+    Form(FormData { title }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let use_case = state.create_chart();
+    let command_use_case::create_chart::Output { chart_id } = use_case
+        .execute(command_use_case::create_chart::Input { title })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to(&format!("/ui/charts/{}", chart_id)))
+}
+
+pub fn router<T: Clone + HasCreateChart + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route("/ui/charts", axum::routing::post(handler::<T>))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::{header, StatusCode};
+    use command_use_case::create_chart::MockCreateChart;
+
+    use crate::server::handler::{tests::send_request, ui::tests::build_form_request};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mut create_chart = MockCreateChart::new();
+        create_chart
+            .expect_execute()
+            .withf(|input| input.title == "title 1")
+            .return_once(|_| {
+                Ok(command_use_case::create_chart::Output {
+                    chart_id: "chart_id1".to_string(),
+                })
+            });
+        let mocks = Mocks {
+            create_chart: Arc::new(create_chart),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request("/ui/charts", "title=title+1")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&axum::http::HeaderValue::from_static(
+                "/ui/charts/chart_id1"
+            ))
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        create_chart: Arc<MockCreateChart>,
+    }
+
+    impl command_use_case::create_chart::HasCreateChart for Mocks {
+        fn create_chart(
+            &self,
+        ) -> Arc<dyn command_use_case::create_chart::CreateChart + Send + Sync> {
+            self.create_chart.clone()
+        }
+    }
+}
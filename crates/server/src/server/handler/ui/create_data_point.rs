@@ -0,0 +1,146 @@
+use axum::{
+    extract::{Path, State},
+    response::Redirect,
+    Form, Router,
+};
+
+use command_use_case::{self, create_data_point::HasCreateDataPoint};
+
+use crate::server::handler::{
+    problem::Problem,
+    ui::{path_segment, ErrorPage},
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    /// An empty string selects the default series
+    #[serde(default)]
+    series_id: Option<String>,
+    x_value: String,
+    y_value: String,
+}
+
+async fn handler<T: HasCreateDataPoint>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Form(FormData {
+        series_id,
+        x_value,
+        y_value,
+    }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let use_case = state.create_data_point();
+    use_case
+        .execute(command_use_case::create_data_point::Input {
+            chart_id: chart_id.clone(),
+            series_id: series_id.filter(|series_id| !series_id.is_empty()),
+            x_value,
+            y_value: y_value.trim().to_owned(),
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to(&format!(
+        "/ui/charts/{}",
+        path_segment(&chart_id)
+    )))
+}
+
+pub fn router<T: Clone + HasCreateDataPoint + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/ui/charts/:chart_id/data_points",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr as _, sync::Arc};
+
+    use axum::http::{header, StatusCode};
+    use command_use_case::create_data_point::MockCreateDataPoint;
+
+    use crate::server::handler::{
+        tests::{send_request, ResponseExt as _},
+        ui::tests::build_form_request,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mut create_data_point = MockCreateDataPoint::new();
+        create_data_point
+            .expect_execute()
+            .withf(|input| {
+                input.chart_id == "chart_id1"
+                    && input.series_id.is_none()
+                    && input.x_value == "2020-01-02"
+                    && input.y_value == "-1.5"
+            })
+            .return_once(|_| {
+                Ok(command_use_case::create_data_point::Output {
+                    data_point_id: "chart_id1:2020-01-02".to_string(),
+                })
+            });
+        let mocks = Mocks {
+            create_data_point: Arc::new(create_data_point),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request(
+            "/ui/charts/chart_id1/data_points",
+            "series_id=&x_value=2020-01-02&y_value=-1.5",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&axum::http::HeaderValue::from_static(
+                "/ui/charts/chart_id1"
+            ))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_y_value() -> anyhow::Result<()> {
+        let mut create_data_point = MockCreateDataPoint::new();
+        create_data_point.expect_execute().return_once(|_| {
+            Err(command_use_case::create_data_point::Error::YValue(
+                write_model::value_object::YValue::from_str("x").unwrap_err(),
+            ))
+        });
+        let mocks = Mocks {
+            create_data_point: Arc::new(create_data_point),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request(
+            "/ui/charts/chart_id1/data_points",
+            "series_id=series_id1&x_value=2020-01-02&y_value=x",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response
+            .into_body_string()
+            .await?
+            .contains("<h1>Invalid parameter</h1>"));
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        create_data_point: Arc<MockCreateDataPoint>,
+    }
+
+    impl command_use_case::create_data_point::HasCreateDataPoint for Mocks {
+        fn create_data_point(
+            &self,
+        ) -> Arc<dyn command_use_case::create_data_point::CreateDataPoint + Send + Sync> {
+            self.create_data_point.clone()
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    response::Redirect,
+    Form, Router,
+};
+
+use command_use_case::{self, delete_chart::HasDeleteChart};
+
+use crate::server::handler::{problem::Problem, ui::ErrorPage};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    #[serde(default)]
+    expected_version: Option<u32>,
+}
+
+async fn handler<T: HasDeleteChart>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Form(FormData { expected_version }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let use_case = state.delete_chart();
+    use_case
+        .execute(command_use_case::delete_chart::Input {
+            chart_id,
+            expected_version,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to("/ui/charts"))
+}
+
+pub fn router<T: Clone + HasDeleteChart + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/ui/charts/:chart_id/delete",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::{header, StatusCode};
+    use command_use_case::delete_chart::MockDeleteChart;
+
+    use crate::server::handler::{
+        tests::{send_request, ResponseExt as _},
+        ui::tests::build_form_request,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mut delete_chart = MockDeleteChart::new();
+        delete_chart
+            .expect_execute()
+            .withf(|input| input.chart_id == "chart_id1" && input.expected_version == Some(2))
+            .return_once(|_| Ok(command_use_case::delete_chart::Output { version: 2 }));
+        let mocks = Mocks {
+            delete_chart: Arc::new(delete_chart),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request("/ui/charts/chart_id1/delete", "expected_version=2")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&axum::http::HeaderValue::from_static("/ui/charts"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch() -> anyhow::Result<()> {
+        let mut delete_chart = MockDeleteChart::new();
+        delete_chart.expect_execute().return_once(|_| {
+            Err(command_use_case::delete_chart::Error::VersionMismatch {
+                actual: 3,
+                expected: 2,
+            })
+        });
+        let mocks = Mocks {
+            delete_chart: Arc::new(delete_chart),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request("/ui/charts/chart_id1/delete", "expected_version=2")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert!(response
+            .into_body_string()
+            .await?
+            .contains("<h1>Precondition failed</h1>"));
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        delete_chart: Arc<MockDeleteChart>,
+    }
+
+    impl command_use_case::delete_chart::HasDeleteChart for Mocks {
+        fn delete_chart(
+            &self,
+        ) -> Arc<dyn command_use_case::delete_chart::DeleteChart + Send + Sync> {
+            self.delete_chart.clone()
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr as _;
+
+use axum::{
+    extract::{Path, State},
+    response::Redirect,
+    Form, Router,
+};
+
+use command_use_case::{self, delete_data_point::HasDeleteDataPoint};
+use write_model::value_object::DataPointId;
+
+use crate::server::handler::{problem::Problem, ui::ErrorPage};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    data_point_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    #[serde(default)]
+    expected_version: Option<u32>,
+}
+
+async fn handler<T: HasDeleteDataPoint>(
+    State(state): State<T>,
+    Path(PathParameters { data_point_id }): Path<PathParameters>,
+    Form(FormData { expected_version }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let chart_id = DataPointId::from_str(&data_point_id)
+        .map_err(|e| Problem::invalid_parameter("data_point_id", &e))?
+        .chart_id();
+    let use_case = state.delete_data_point();
+    use_case
+        .execute(command_use_case::delete_data_point::Input {
+            data_point_id,
+            expected_version,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to(&format!("/ui/charts/{}", chart_id)))
+}
+
+pub fn router<T: Clone + HasDeleteDataPoint + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/ui/data_points/:data_point_id/delete",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::{header, HeaderValue, StatusCode};
+    use command_use_case::delete_data_point::MockDeleteDataPoint;
+    use write_model::value_object::ChartId;
+
+    use crate::server::handler::{tests::send_request, ui::tests::build_form_request};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let data_point_id = format!("{}:2020-01-02", chart_id);
+        let mut delete_data_point = MockDeleteDataPoint::new();
+        delete_data_point
+            .expect_execute()
+            .withf({
+                let data_point_id = data_point_id.clone();
+                move |input| {
+                    input.data_point_id == data_point_id && input.expected_version == Some(1)
+                }
+            })
+            .return_once(|_| Ok(command_use_case::delete_data_point::Output { version: 2 }));
+        let mocks = Mocks {
+            delete_data_point: Arc::new(delete_data_point),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request(
+            &format!("/ui/data_points/{}/delete", data_point_id),
+            "expected_version=1",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&HeaderValue::from_str(&format!("/ui/charts/{}", chart_id))?)
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        delete_data_point: Arc<MockDeleteDataPoint>,
+    }
+
+    impl command_use_case::delete_data_point::HasDeleteDataPoint for Mocks {
+        fn delete_data_point(
+            &self,
+        ) -> Arc<dyn command_use_case::delete_data_point::DeleteDataPoint + Send + Sync> {
+            self.delete_data_point.clone()
+        }
+    }
+}
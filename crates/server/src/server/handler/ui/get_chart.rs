@@ -0,0 +1,286 @@
+use axum::{
+    extract::{Path, State},
+    response::Html,
+    Router,
+};
+
+use chart_renderer::escape;
+use query_use_case::{self, get_chart::HasGetChart, list_data_points::HasListDataPoints};
+
+use crate::server::handler::{
+    problem::Problem,
+    ui::{page, path_segment, ErrorPage},
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+fn html_from_outputs(
+    chart: query_use_case::get_chart::OutputItem,
+    query_use_case::list_data_points::Output(data_points): query_use_case::list_data_points::Output,
+) -> Html<String> {
+    let chart_id = escape(&path_segment(&chart.id));
+    let series_name = |series_id: Option<&String>| match series_id {
+        None => "(default)".to_owned(),
+        Some(series_id) => chart
+            .series
+            .iter()
+            .find(|series| &series.id == series_id)
+            .map(|series| series.name.clone())
+            .unwrap_or_else(|| series_id.clone()),
+    };
+
+    let mut body = String::new();
+    body.push_str(&format!(
+        r#"<p><a href="/ui/charts">Charts</a></p>
+<p><img src="/charts/{chart_id}/image.svg" alt="{title}" width="640" height="480"></p>
+<form class="inline" method="post" action="/ui/charts/{chart_id}/update">
+<input type="hidden" name="expected_version" value="{version}">
+<input name="title" required value="{title}">
+<button type="submit">Rename</button>
+</form>
+<form class="inline" method="post" action="/ui/charts/{chart_id}/delete">
+<input type="hidden" name="expected_version" value="{version}">
+<button type="submit">Delete chart</button>
+</form>
+<h2>Data points</h2>
+<form method="post" action="/ui/charts/{chart_id}/data_points">
+"#,
+        chart_id = chart_id,
+        title = escape(&chart.title),
+        version = chart.version,
+    ));
+    if !chart.series.is_empty() {
+        body.push_str("<select name=\"series_id\">\n<option value=\"\">(default)</option>\n");
+        for series in &chart.series {
+            body.push_str(&format!(
+                "<option value=\"{}\">{}</option>\n",
+                escape(&series.id),
+                escape(&series.name)
+            ));
+        }
+        body.push_str("</select>\n");
+    }
+    body.push_str(
+        r#"<input type="date" name="x_value" required>
+<input name="y_value" required inputmode="decimal" placeholder="Value">
+<button type="submit">Add data point</button>
+</form>
+"#,
+    );
+    if data_points.is_empty() {
+        body.push_str("<p>No data points.</p>\n");
+    } else {
+        body.push_str("<table>\n<tr><th>Series</th><th>Date</th><th>Value</th><th></th></tr>\n");
+        for data_point in &data_points {
+            body.push_str(&format!(
+                r#"<tr><td>{series}</td><td>{x_value}</td><td>
+<form class="inline" method="post" action="/ui/data_points/{data_point_id}/update">
+<input type="hidden" name="expected_version" value="{version}">
+<input name="y_value" required inputmode="decimal" value="{y_value}">
+<button type="submit">Save</button>
+</form>
+</td><td>
+<form class="inline" method="post" action="/ui/data_points/{data_point_id}/delete">
+<input type="hidden" name="expected_version" value="{version}">
+<button type="submit">Delete</button>
+</form>
+</td></tr>
+"#,
+                data_point_id = escape(&path_segment(&data_point.id)),
+                series = escape(&series_name(data_point.series_id.as_ref())),
+                version = data_point.version,
+                x_value = escape(&data_point.x_value),
+                y_value = escape(&data_point.y_value),
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+    page(&chart.title, &body)
+}
+
+async fn handler<T: HasGetChart + HasListDataPoints>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+) -> Result<Html<String>, ErrorPage> {
+    let query_use_case::get_chart::Output(chart) = state
+        .get_chart()
+        .execute(query_use_case::get_chart::Input {
+            chart_id: chart_id.clone(),
+        })
+        .await
+        .map_err(Problem::from)?;
+    let chart = chart.ok_or_else(|| Problem::not_found("chart not found"))?;
+    let data_points = state
+        .list_data_points()
+        .execute(query_use_case::list_data_points::Input {
+            after: None,
+            chart_id,
+            from: None,
+            limit: None,
+            series_id: None,
+            to: None,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(html_from_outputs(chart, data_points))
+}
+
+pub fn router<T: Clone + HasGetChart + HasListDataPoints + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route("/ui/charts/:chart_id", axum::routing::get(handler::<T>))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use query_use_case::{
+        get_chart::{MockGetChart, OutputItem, OutputItemSeries},
+        list_data_points::{DataPoint, MockListDataPoints},
+    };
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/ui/charts/chart_id1")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert!(body.contains("<h1>title &lt;1&gt;</h1>"));
+        assert!(body.contains(r#"<img src="/charts/chart_id1/image.svg""#));
+        assert!(body.contains(r#"<input type="hidden" name="expected_version" value="3">"#));
+        assert!(body.contains(r#"<option value="series_id1">name1</option>"#));
+        assert!(body.contains(r#"action="/ui/data_points/chart_id1:2020-01-01/update""#));
+        assert!(body.contains(r#"action="/ui/data_points/chart_id1:series_id1:2020-01-02/delete""#));
+        assert!(body.contains(r#"<td>name1</td><td>2020-01-02</td>"#));
+        assert!(body.contains(r#"value="-1.5""#));
+        assert!(body.contains(
+            r#"<input type="hidden" name="expected_version" value="1">
+<input name="y_value""#
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_percent_encoded_id() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute().return_once(|_| {
+                Ok(query_use_case::get_chart::Output(Some(OutputItem {
+                    created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                    id: "chart/1?".to_string(),
+                    series: vec![],
+                    title: "title1".to_string(),
+                    version: 1,
+                })))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/ui/charts/chart_id1")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert!(body.contains(r#"<img src="/charts/chart%2F1%3F/image.svg""#));
+        assert!(body.contains(r#"action="/ui/charts/chart%2F1%3F/update""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_found() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.get_chart = {
+            let mut mock = MockGetChart::new();
+            mock.expect_execute()
+                .return_once(|_| Ok(query_use_case::get_chart::Output(None)));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/ui/charts/chart_id1")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response
+            .into_body_string()
+            .await?
+            .contains("<p>chart not found</p>"));
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        get_chart: Arc<MockGetChart>,
+        list_data_points: Arc<MockListDataPoints>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior() -> Self {
+            let mut get_chart = MockGetChart::new();
+            get_chart.expect_execute().return_once(|_| {
+                Ok(query_use_case::get_chart::Output(Some(OutputItem {
+                    created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                    id: "chart_id1".to_string(),
+                    series: vec![OutputItemSeries {
+                        id: "series_id1".to_string(),
+                        name: "name1".to_string(),
+                    }],
+                    title: "title <1>".to_string(),
+                    version: 3,
+                })))
+            });
+            let mut list_data_points = MockListDataPoints::new();
+            list_data_points.expect_execute().return_once(|_| {
+                let data_point =
+                    |series_id: Option<&str>, x_value: &str, y_value: &str| DataPoint {
+                        chart_id: "chart_id1".to_string(),
+                        created_at: "2020-01-01T00:00:00.000Z".to_string(),
+                        id: match series_id {
+                            None => format!("chart_id1:{}", x_value),
+                            Some(series_id) => format!("chart_id1:{}:{}", series_id, x_value),
+                        },
+                        series_id: series_id.map(str::to_owned),
+                        version: 1,
+                        x_value: x_value.to_string(),
+                        y_value: y_value.to_string(),
+                    };
+                Ok(query_use_case::list_data_points::Output(vec![
+                    data_point(None, "2020-01-01", "1"),
+                    data_point(Some("series_id1"), "2020-01-02", "-1.5"),
+                ]))
+            });
+            Self {
+                get_chart: Arc::new(get_chart),
+                list_data_points: Arc::new(list_data_points),
+            }
+        }
+    }
+
+    impl query_use_case::get_chart::HasGetChart for Mocks {
+        fn get_chart(&self) -> Arc<dyn query_use_case::get_chart::GetChart + Send + Sync> {
+            self.get_chart.clone()
+        }
+    }
+
+    impl query_use_case::list_data_points::HasListDataPoints for Mocks {
+        fn list_data_points(
+            &self,
+        ) -> Arc<dyn query_use_case::list_data_points::ListDataPoints + Send + Sync> {
+            self.list_data_points.clone()
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .body(axum::body::Body::empty())?)
+    }
+}
@@ -0,0 +1,161 @@
+use axum::{
+    extract::{Query, State},
+    response::Html,
+    Router,
+};
+
+use chart_renderer::escape;
+use query_use_case::{self, list_charts::HasListCharts};
+
+use crate::server::handler::{
+    problem::Problem,
+    ui::{page, path_segment, ErrorPage},
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct QueryParameters {
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+fn html_from_output(
+    query_use_case::list_charts::Output {
+        charts,
+        next_cursor,
+    }: query_use_case::list_charts::Output,
+) -> Html<String> {
+    let mut body = String::new();
+    body.push_str(
+        r#"<form method="post" action="/ui/charts">
+<input name="title" required placeholder="Title">
+<button type="submit">Create chart</button>
+</form>
+"#,
+    );
+    if charts.is_empty() {
+        body.push_str("<p>No charts.</p>\n");
+    } else {
+        body.push_str("<table>\n<tr><th>Title</th><th>Updated at</th></tr>\n");
+        for chart in charts {
+            body.push_str(&format!(
+                "<tr><td><a href=\"/ui/charts/{}\">{}</a></td><td>{}</td></tr>\n",
+                escape(&path_segment(&chart.id)),
+                escape(&chart.title),
+                escape(&chart.updated_at),
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+    if let Some(next_cursor) = next_cursor {
+        body.push_str(&format!(
+            "<p><a href=\"/ui/charts?cursor={}\">Next</a></p>\n",
+            escape(&form_urlencoded::byte_serialize(next_cursor.as_bytes()).collect::<String>())
+        ));
+    }
+    page("Charts", &body)
+}
+
+async fn handler<T: HasListCharts>(
+    State(state): State<T>,
+    Query(QueryParameters { cursor }): Query<QueryParameters>,
+) -> Result<Html<String>, ErrorPage> {
+    let use_case = state.list_charts();
+    let output = use_case
+        .execute(query_use_case::list_charts::Input {
+            cursor,
+            limit: None,
+            order: None,
+            sort: None,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(html_from_output(output))
+}
+
+pub fn router<T: Clone + HasListCharts + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route("/ui/charts", axum::routing::get(handler::<T>))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use query_use_case::list_charts::{Chart, MockListCharts};
+
+    use crate::server::handler::tests::{send_request, ResponseExt as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mocks = Mocks::with_happy_path_behavior();
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/ui/charts")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert!(body.contains(r#"<a href="/ui/charts/chart_id1">title &amp; 1</a>"#));
+        assert!(body.contains(r#"<a href="/ui/charts?cursor=cursor1">Next</a>"#));
+        assert!(body.contains(r#"<form method="post" action="/ui/charts">"#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_cursor() -> anyhow::Result<()> {
+        let mut mocks = Mocks::with_happy_path_behavior();
+        mocks.list_charts = {
+            let mut mock = MockListCharts::new();
+            mock.expect_execute()
+                .return_once(|_| Err(query_use_case::list_charts::Error::Limit));
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request("/ui/charts?cursor=x")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response
+            .into_body_string()
+            .await?
+            .contains("<h1>Invalid parameter</h1>"));
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        list_charts: Arc<MockListCharts>,
+    }
+
+    impl Mocks {
+        fn with_happy_path_behavior() -> Self {
+            let mut list_charts = MockListCharts::new();
+            list_charts.expect_execute().return_once(|_| {
+                Ok(query_use_case::list_charts::Output {
+                    charts: vec![Chart {
+                        created_at: "2020-01-02T03:04:05.678Z".to_string(),
+                        id: "chart_id1".to_string(),
+                        title: "title & 1".to_string(),
+                        updated_at: "2020-01-02T03:04:05.678Z".to_string(),
+                    }],
+                    next_cursor: Some("cursor1".to_string()),
+                })
+            });
+            Self {
+                list_charts: Arc::new(list_charts),
+            }
+        }
+    }
+
+    impl query_use_case::list_charts::HasListCharts for Mocks {
+        fn list_charts(&self) -> Arc<dyn query_use_case::list_charts::ListCharts + Send + Sync> {
+            self.list_charts.clone()
+        }
+    }
+
+    fn build_request(uri: &str) -> anyhow::Result<axum::http::Request<axum::body::Body>> {
+        Ok(axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(uri)
+            .body(axum::body::Body::empty())?)
+    }
+}
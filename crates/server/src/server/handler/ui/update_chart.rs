@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Path, State},
+    response::Redirect,
+    Form, Router,
+};
+
+use command_use_case::{self, update_chart::HasUpdateChart};
+
+use crate::server::handler::{
+    problem::Problem,
+    ui::{path_segment, ErrorPage},
+};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    chart_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    #[serde(default)]
+    expected_version: Option<u32>,
+    title: String,
+}
+
+async fn handler<T: HasUpdateChart>(
+    State(state): State<T>,
+    Path(PathParameters { chart_id }): Path<PathParameters>,
+    Form(FormData {
+        expected_version,
+        title,
+    }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let use_case = state.update_chart();
+    use_case
+        .execute(command_use_case::update_chart::Input {
+            chart_id: chart_id.clone(),
+            expected_version,
+            title,
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to(&format!(
+        "/ui/charts/{}",
+        path_segment(&chart_id)
+    )))
+}
+
+pub fn router<T: Clone + HasUpdateChart + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/ui/charts/:chart_id/update",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::{header, StatusCode};
+    use command_use_case::update_chart::MockUpdateChart;
+
+    use crate::server::handler::{tests::send_request, ui::tests::build_form_request};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let mut update_chart = MockUpdateChart::new();
+        update_chart
+            .expect_execute()
+            .withf(|input| {
+                input.chart_id == "chart_id1"
+                    && input.expected_version == Some(2)
+                    && input.title == "title2"
+            })
+            .return_once(|_| Ok(command_use_case::update_chart::Output { version: 2 }));
+        let mocks = Mocks {
+            update_chart: Arc::new(update_chart),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request(
+            "/ui/charts/chart_id1/update",
+            "expected_version=2&title=title2",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&axum::http::HeaderValue::from_static(
+                "/ui/charts/chart_id1"
+            ))
+        );
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        update_chart: Arc<MockUpdateChart>,
+    }
+
+    impl command_use_case::update_chart::HasUpdateChart for Mocks {
+        fn update_chart(
+            &self,
+        ) -> Arc<dyn command_use_case::update_chart::UpdateChart + Send + Sync> {
+            self.update_chart.clone()
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::str::FromStr as _;
+
+use axum::{
+    extract::{Path, State},
+    response::Redirect,
+    Form, Router,
+};
+
+use command_use_case::{self, update_data_point::HasUpdateDataPoint};
+use write_model::value_object::DataPointId;
+
+use crate::server::handler::{problem::Problem, ui::ErrorPage};
+
+#[derive(serde::Deserialize)]
+struct PathParameters {
+    data_point_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FormData {
+    #[serde(default)]
+    expected_version: Option<u32>,
+    y_value: String,
+}
+
+async fn handler<T: HasUpdateDataPoint>(
+    State(state): State<T>,
+    Path(PathParameters { data_point_id }): Path<PathParameters>,
+    Form(FormData {
+        expected_version,
+        y_value,
+    }): Form<FormData>,
+) -> Result<Redirect, ErrorPage> {
+    let chart_id = DataPointId::from_str(&data_point_id)
+        .map_err(|e| Problem::invalid_parameter("data_point_id", &e))?
+        .chart_id();
+    let use_case = state.update_data_point();
+    use_case
+        .execute(command_use_case::update_data_point::Input {
+            data_point_id,
+            expected_version,
+            y_value: y_value.trim().to_owned(),
+        })
+        .await
+        .map_err(Problem::from)?;
+    Ok(Redirect::to(&format!("/ui/charts/{}", chart_id)))
+}
+
+pub fn router<T: Clone + HasUpdateDataPoint + Send + Sync + 'static>() -> Router<T> {
+    Router::new().route(
+        "/ui/data_points/:data_point_id/update",
+        axum::routing::post(handler::<T>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::{header, HeaderValue, StatusCode};
+    use command_use_case::update_data_point::MockUpdateDataPoint;
+    use write_model::value_object::ChartId;
+
+    use crate::server::handler::{tests::send_request, ui::tests::build_form_request};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path() -> anyhow::Result<()> {
+        let chart_id = ChartId::generate();
+        let data_point_id = format!("{}:2020-01-02", chart_id);
+        let mut update_data_point = MockUpdateDataPoint::new();
+        update_data_point
+            .expect_execute()
+            .withf({
+                let data_point_id = data_point_id.clone();
+                move |input| {
+                    input.data_point_id == data_point_id
+                        && input.expected_version == Some(1)
+                        && input.y_value == "12.5"
+                }
+            })
+            .return_once(|_| Ok(command_use_case::update_data_point::Output { version: 2 }));
+        let mocks = Mocks {
+            update_data_point: Arc::new(update_data_point),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request(
+            &format!("/ui/data_points/{}/update", data_point_id),
+            "expected_version=1&y_value=12.5",
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION),
+            Some(&HeaderValue::from_str(&format!("/ui/charts/{}", chart_id))?)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_data_point_id() -> anyhow::Result<()> {
+        let mocks = Mocks {
+            update_data_point: Arc::new(MockUpdateDataPoint::new()),
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_form_request("/ui/data_points/x/update", "y_value=12.5")?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[derive(Clone)]
+    struct Mocks {
+        update_data_point: Arc<MockUpdateDataPoint>,
+    }
+
+    impl command_use_case::update_data_point::HasUpdateDataPoint for Mocks {
+        fn update_data_point(
+            &self,
+        ) -> Arc<dyn command_use_case::update_data_point::UpdateDataPoint + Send + Sync> {
+            self.update_data_point.clone()
+        }
+    }
+}
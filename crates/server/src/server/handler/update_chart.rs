@@ -1,11 +1,17 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Router,
 };
 
 use command_use_case::{self, update_chart::HasUpdateChart};
 
+use crate::server::handler::{
+    etag::{etag_from_version, expected_version_from_headers},
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     chart_id: String,
@@ -18,25 +24,50 @@ struct RequestBody {
 
 fn input_from_request(
     PathParameters { chart_id }: PathParameters,
+    expected_version: Option<u32>,
     RequestBody { title }: RequestBody,
 ) -> command_use_case::update_chart::Input {
-    command_use_case::update_chart::Input { chart_id, title }
+    command_use_case::update_chart::Input {
+        chart_id,
+        expected_version,
+        title,
+    }
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct ResponseBody {}
 
+impl From<command_use_case::update_chart::Error> for Problem {
+    fn from(error: command_use_case::update_chart::Error) -> Self {
+        use command_use_case::update_chart::Error;
+        match &error {
+            Error::ChartFind(e) | Error::ChartStore(e) => {
+                Problem::from_chart_repository_error(&error, e)
+            }
+            Error::ChartId(_) => Problem::invalid_parameter("chart_id", &error),
+            Error::ChartNotFound(_) => Problem::not_found(error.to_string()),
+            Error::ChartUpdate(e) => Problem::from_chart_error(&error, e),
+            Error::VersionMismatch { .. } => Problem::precondition_failed(&error),
+        }
+    }
+}
+
 async fn handler<T: HasUpdateChart>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
+    headers: HeaderMap,
     Json(body): Json<RequestBody>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
+    let expected_version = expected_version_from_headers(&headers)?;
     let use_case = state.update_chart();
-    let _output = use_case
-        .execute(input_from_request(path_parameters, body))
+    let output = use_case
+        .execute(input_from_request(path_parameters, expected_version, body))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ResponseBody {}))
+        .map_err(Problem::from)?;
+    Ok((
+        [(header::ETAG, etag_from_version(output.version))],
+        Json(ResponseBody {}),
+    ))
 }
 
 pub fn router<T: Clone + HasUpdateChart + Send + Sync + 'static>() -> Router<T> {
@@ -47,6 +78,7 @@ pub fn router<T: Clone + HasUpdateChart + Send + Sync + 'static>() -> Router<T>
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use command_use_case::update_chart::MockUpdateChart;
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -62,6 +94,10 @@ mod tests {
         let request = build_request(&PathParameters { chart_id }, &RequestBody { title })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {}
@@ -87,7 +123,108 @@ mod tests {
         let request = build_request(&PathParameters { chart_id }, &RequestBody { title })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chart_not_found() -> anyhow::Result<()> {
+        let title = "title1".to_string();
+        let chart_id = write_model::value_object::ChartId::generate();
+        let mut mocks = Mocks::with_happy_path_behavior(title.clone(), chart_id.to_string());
+        mocks.update_chart = {
+            let mut mock = MockUpdateChart::new();
+            mock.expect_execute().return_once(move |_| {
+                Err(command_use_case::update_chart::Error::ChartNotFound(
+                    chart_id,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(
+            &PathParameters {
+                chart_id: chart_id.to_string(),
+            },
+            &RequestBody { title },
+        )?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/not-found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch() -> anyhow::Result<()> {
+        let title = "title1".to_string();
+        let chart_id = "chart_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(title.clone(), chart_id.clone());
+        mocks.update_chart = {
+            let mut mock = MockUpdateChart::new();
+            mock.expect_execute().return_once(|input| {
+                assert_eq!(input.expected_version, Some(1));
+                Err(command_use_case::update_chart::Error::VersionMismatch {
+                    actual: 2,
+                    expected: 1,
+                })
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let mut request = build_request(&PathParameters { chart_id }, &RequestBody { title })?;
+        request.headers_mut().insert(
+            axum::http::header::IF_MATCH,
+            axum::http::HeaderValue::from_static("\"1\""),
+        );
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("version mismatch (expected = 1, actual = 2)".to_string()),
+                field: None,
+                status: 412,
+                title: "Precondition failed".to_string(),
+                r#type: "/problems/precondition-failed".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_conflict() -> anyhow::Result<()> {
+        let title = "title1".to_string();
+        let chart_id = "chart_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(title.clone(), chart_id.clone());
+        mocks.update_chart = {
+            let mut mock = MockUpdateChart::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::update_chart::Error::ChartStore(
+                    command_use_case::port::chart_repository::Error::Conflict(build_error()),
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { chart_id }, &RequestBody { title })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?,
+            Problem {
+                detail: Some("chart store: conflict: error".to_string()),
+                field: None,
+                status: 409,
+                title: "Conflict".to_string(),
+                r#type: "/problems/conflict".to_string(),
+            }
+        );
         Ok(())
     }
 
@@ -101,8 +238,9 @@ mod tests {
             let mut update_chart = MockUpdateChart::new();
             update_chart.expect_execute().return_once(move |input| {
                 assert_eq!(input.chart_id, chart_id);
+                assert_eq!(input.expected_version, None);
                 assert_eq!(input.title, title);
-                Ok(command_use_case::update_chart::Output {})
+                Ok(command_use_case::update_chart::Output { version: 2 })
             });
             Self {
                 update_chart: Arc::new(update_chart),
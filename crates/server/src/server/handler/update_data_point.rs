@@ -1,11 +1,18 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json, Router,
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Router,
 };
 
 use command_use_case::{self, update_data_point::HasUpdateDataPoint};
 
+use crate::server::handler::{
+    decimal::Decimal,
+    etag::{etag_from_version, expected_version_from_headers},
+    extract::{Json, Path},
+    problem::Problem,
+};
+
 #[derive(serde::Deserialize)]
 struct PathParameters {
     data_point_id: String,
@@ -13,33 +20,56 @@ struct PathParameters {
 
 #[derive(serde::Deserialize, serde::Serialize)]
 struct RequestBody {
-    y_value: u32,
+    y_value: Decimal,
 }
 
 fn input_from_request(
     PathParameters { data_point_id }: PathParameters,
+    expected_version: Option<u32>,
     RequestBody { y_value }: RequestBody,
 ) -> command_use_case::update_data_point::Input {
     command_use_case::update_data_point::Input {
         data_point_id,
-        y_value,
+        expected_version,
+        y_value: y_value.to_string(),
     }
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 struct ResponseBody {}
 
+impl From<command_use_case::update_data_point::Error> for Problem {
+    fn from(error: command_use_case::update_data_point::Error) -> Self {
+        use command_use_case::update_data_point::Error;
+        match &error {
+            Error::DataPointFind(e) | Error::DataPointStore(e) => {
+                Problem::from_data_point_repository_error(&error, e)
+            }
+            Error::DataPointId(_) => Problem::invalid_parameter("data_point_id", &error),
+            Error::DataPointNotFound(_) => Problem::not_found(error.to_string()),
+            Error::DataPointUpdate(e) => Problem::from_data_point_error(&error, e),
+            Error::VersionMismatch { .. } => Problem::precondition_failed(&error),
+            Error::YValue(_) => Problem::invalid_parameter("y_value", &error),
+        }
+    }
+}
+
 async fn handler<T: HasUpdateDataPoint>(
     State(state): State<T>,
     Path(path_parameters): Path<PathParameters>,
+    headers: HeaderMap,
     Json(body): Json<RequestBody>,
-) -> Result<Json<ResponseBody>, StatusCode> {
+) -> Result<([(HeaderName, HeaderValue); 1], Json<ResponseBody>), Problem> {
+    let expected_version = expected_version_from_headers(&headers)?;
     let use_case = state.update_data_point();
-    let _output = use_case
-        .execute(input_from_request(path_parameters, body))
+    let output = use_case
+        .execute(input_from_request(path_parameters, expected_version, body))
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ResponseBody {}))
+        .map_err(Problem::from)?;
+    Ok((
+        [(header::ETAG, etag_from_version(output.version))],
+        Json(ResponseBody {}),
+    ))
 }
 
 pub fn router<T: Clone + HasUpdateDataPoint + Send + Sync + 'static>() -> Router<T> {
@@ -53,6 +83,7 @@ pub fn router<T: Clone + HasUpdateDataPoint + Send + Sync + 'static>() -> Router
 mod tests {
     use std::sync::Arc;
 
+    use axum::http::StatusCode;
     use command_use_case::update_data_point::MockUpdateDataPoint;
 
     use crate::server::handler::tests::{send_request, ResponseExt as _};
@@ -62,12 +93,16 @@ mod tests {
     #[tokio::test]
     async fn test_happy_path() -> anyhow::Result<()> {
         let data_point_id = "data_point_id1".to_string();
-        let y_value = 123_u32;
-        let mocks = Mocks::with_happy_path_behavior(data_point_id.clone(), y_value);
+        let y_value = "-12.5".parse::<Decimal>()?;
+        let mocks = Mocks::with_happy_path_behavior(data_point_id.clone(), "-12.5".to_string());
         let app = router().with_state(mocks.clone());
         let request = build_request(&PathParameters { data_point_id }, &RequestBody { y_value })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_static("\"2\""))
+        );
         assert_eq!(
             response.into_body_as_json::<ResponseBody>().await?,
             ResponseBody {}
@@ -77,9 +112,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_error() -> anyhow::Result<()> {
-        let y_value = 123_u32;
+        let y_value = "123".parse::<Decimal>()?;
         let data_point_id = "data_point_id1".to_string();
-        let mut mocks = Mocks::with_happy_path_behavior(data_point_id.clone(), y_value);
+        let mut mocks = Mocks::with_happy_path_behavior(data_point_id.clone(), y_value.to_string());
         mocks.update_data_point = {
             let mut mock = MockUpdateDataPoint::new();
             mock.expect_execute().return_once(|_| {
@@ -93,7 +128,35 @@ mod tests {
         let request = build_request(&PathParameters { data_point_id }, &RequestBody { y_value })?;
         let response = send_request(app, request).await?;
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(response.into_body_string().await?, "");
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/internal-server-error"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_already_deleted() -> anyhow::Result<()> {
+        let y_value = "123".parse::<Decimal>()?;
+        let data_point_id = "data_point_id1".to_string();
+        let mut mocks = Mocks::with_happy_path_behavior(data_point_id.clone(), y_value.to_string());
+        mocks.update_data_point = {
+            let mut mock = MockUpdateDataPoint::new();
+            mock.expect_execute().return_once(|_| {
+                Err(command_use_case::update_data_point::Error::DataPointUpdate(
+                    write_model::aggregate::data_point::Error::AlreadyDeleted,
+                ))
+            });
+            Arc::new(mock)
+        };
+        let app = router().with_state(mocks.clone());
+        let request = build_request(&PathParameters { data_point_id }, &RequestBody { y_value })?;
+        let response = send_request(app, request).await?;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.into_body_as_json::<Problem>().await?.r#type,
+            "/problems/conflict"
+        );
         Ok(())
     }
 
@@ -103,14 +166,14 @@ mod tests {
     }
 
     impl Mocks {
-        fn with_happy_path_behavior(data_point_id: String, y_value: u32) -> Self {
+        fn with_happy_path_behavior(data_point_id: String, y_value: String) -> Self {
             let mut update_data_point = MockUpdateDataPoint::new();
             update_data_point
                 .expect_execute()
                 .return_once(move |input| {
                     assert_eq!(input.data_point_id, data_point_id);
                     assert_eq!(input.y_value, y_value);
-                    Ok(command_use_case::update_data_point::Output)
+                    Ok(command_use_case::update_data_point::Output { version: 2 })
                 });
             Self {
                 update_data_point: Arc::new(update_data_point),
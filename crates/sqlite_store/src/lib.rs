@@ -0,0 +1,3 @@
+mod sqlite_store;
+
+pub use self::sqlite_store::*;
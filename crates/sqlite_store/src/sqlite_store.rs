@@ -0,0 +1,9 @@
+mod error;
+mod event_table;
+mod migration;
+mod sqlite_chart_store;
+mod sqlite_connection;
+mod sqlite_data_point_store;
+
+pub use self::sqlite_chart_store::*;
+pub use self::sqlite_data_point_store::*;
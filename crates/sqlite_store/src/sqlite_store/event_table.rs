@@ -0,0 +1,109 @@
+use rusqlite::{params, Connection, Row};
+use store_error::{BoxError, CorruptedError};
+use write_model::value_object::Version;
+
+/// The value of the `stream_type` column
+#[derive(Clone, Copy)]
+pub(crate) enum StreamType {
+    Chart,
+    DataPoint,
+}
+
+impl StreamType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Chart => "chart",
+            Self::DataPoint => "data_point",
+        }
+    }
+}
+
+/// A row of the `events` table. `data` is the event data in JSON
+pub(crate) struct EventRow {
+    pub(crate) at: String,
+    pub(crate) data: String,
+    pub(crate) id: String,
+    pub(crate) stream_id: String,
+    pub(crate) version: i64,
+}
+
+impl EventRow {
+    fn from_row(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            at: row.get("at")?,
+            data: row.get("data")?,
+            id: row.get("id")?,
+            stream_id: row.get("stream_id")?,
+            version: row.get("version")?,
+        })
+    }
+}
+
+/// Returns the version of the last event of the stream (`None` if the stream does not exist)
+pub(crate) fn last_version(
+    connection: &Connection,
+    stream_id: &str,
+) -> Result<Option<Version>, BoxError> {
+    let version = connection.query_row(
+        "SELECT MAX(version) FROM events WHERE stream_id = ?1",
+        params![stream_id],
+        |row| row.get::<_, Option<i64>>(0),
+    )?;
+    version
+        .map(Version::try_from)
+        .transpose()
+        .map_err(|e| CorruptedError(e.into()).into())
+}
+
+pub(crate) fn insert(
+    connection: &Connection,
+    stream_type: StreamType,
+    event_row: &EventRow,
+) -> Result<(), rusqlite::Error> {
+    connection.execute(
+        "INSERT INTO events (stream_id, version, at, data, id, stream_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            event_row.stream_id,
+            event_row.version,
+            event_row.at,
+            event_row.data,
+            event_row.id,
+            stream_type.as_str()
+        ],
+    )?;
+    Ok(())
+}
+
+/// Returns the events of the stream in version order
+pub(crate) fn select_by_stream_id(
+    connection: &Connection,
+    stream_id: &str,
+) -> Result<Vec<EventRow>, rusqlite::Error> {
+    let mut statement = connection.prepare(
+        "SELECT at, data, id, stream_id, version FROM events WHERE stream_id = ?1 ORDER BY version",
+    )?;
+    let rows = statement.query_map(params![stream_id], EventRow::from_row)?;
+    rows.collect()
+}
+
+/// Returns the id of every stream of the type. The order is not specified
+pub(crate) fn select_stream_ids(
+    connection: &Connection,
+    stream_type: StreamType,
+) -> Result<Vec<String>, rusqlite::Error> {
+    let mut statement =
+        connection.prepare("SELECT DISTINCT stream_id FROM events WHERE stream_type = ?1")?;
+    let rows = statement.query_map(params![stream_type.as_str()], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Returns the events of every stream of the type. The order is not specified
+pub(crate) fn select_by_stream_type(
+    connection: &Connection,
+    stream_type: StreamType,
+) -> Result<Vec<EventRow>, rusqlite::Error> {
+    let mut statement = connection
+        .prepare("SELECT at, data, id, stream_id, version FROM events WHERE stream_type = ?1")?;
+    let rows = statement.query_map(params![stream_type.as_str()], EventRow::from_row)?;
+    rows.collect()
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use rusqlite::{Connection, TransactionBehavior};
+use store_error::BoxError;
+
+/// The schema changes, applied in order. `PRAGMA user_version` is the number of applied migrations.
+/// Append new migrations; never edit the applied ones
+const MIGRATIONS: &[&str] = &[r#"
+CREATE TABLE events (
+    stream_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    at TEXT NOT NULL,
+    data TEXT NOT NULL,
+    id TEXT NOT NULL UNIQUE,
+    stream_type TEXT NOT NULL,
+    PRIMARY KEY (stream_id, version)
+);
+CREATE INDEX events_stream_type ON events (stream_type);
+CREATE TABLE charts (
+    id TEXT NOT NULL PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    series TEXT NOT NULL,
+    title TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    version INTEGER NOT NULL
+);
+CREATE TABLE data_points (
+    id TEXT NOT NULL PRIMARY KEY,
+    chart_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    series_id TEXT,
+    version INTEGER NOT NULL,
+    x_value TEXT NOT NULL,
+    y_value TEXT NOT NULL
+);
+CREATE INDEX data_points_chart_id_x_value ON data_points (chart_id, x_value);
+"#];
+
+#[derive(Debug, thiserror::Error)]
+#[error("the database is newer than this version (user_version = {0})")]
+struct UnknownSchemaVersionError(usize);
+
+/// Opens the database file (created if it does not exist) and applies the pending migrations
+pub(crate) fn open(path: &Path) -> Result<Connection, BoxError> {
+    let mut connection = Connection::open(path)?;
+    connection.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+    migrate(&mut connection)?;
+    Ok(connection)
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), BoxError> {
+    let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let applied = transaction.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if applied > MIGRATIONS.len() {
+        return Err(UnknownSchemaVersionError(applied).into());
+    }
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", index + 1)?;
+    }
+    transaction.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_open() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("sqlite_store")?;
+        let path = temp_dir.path().join("date_line_charts.sqlite");
+        let user_version = |connection: &Connection| {
+            connection.pragma_query_value(None, "user_version", |row| row.get::<_, usize>(0))
+        };
+        let connection = open(&path).map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(user_version(&connection)?, MIGRATIONS.len());
+        drop(connection);
+
+        // reopening applies nothing
+        let connection = open(&path).map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(user_version(&connection)?, MIGRATIONS.len());
+        connection.pragma_update(None, "user_version", MIGRATIONS.len() + 1)?;
+        drop(connection);
+        assert!(open(&path).is_err());
+        Ok(())
+    }
+}
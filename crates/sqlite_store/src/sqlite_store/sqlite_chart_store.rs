@@ -0,0 +1,463 @@
+use std::{path::PathBuf, str::FromStr as _};
+
+use query_use_case::projection;
+use rusqlite::{params, Connection, OptionalExtension as _, Row, TransactionBehavior};
+use store_error::{check_version, BoxError, CorruptedError};
+use write_model::{
+    aggregate::Chart,
+    event::{
+        ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated,
+        ChartSeriesDeleted, ChartSeriesRenamed, ChartUpdated,
+    },
+    value_object::{ChartId, SeriesId, Version},
+};
+
+use crate::sqlite_store::{
+    error::{chart_reader_error, chart_repository_error},
+    event_table::{self, EventRow, StreamType},
+    sqlite_connection::SqliteConnection,
+};
+
+/// The `data` column of the `events` table
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+enum EventDataJson {
+    Created { title: String },
+    Deleted {},
+    SeriesCreated { name: String, series_id: String },
+    SeriesDeleted { series_id: String },
+    SeriesRenamed { name: String, series_id: String },
+    Updated { title: String },
+}
+
+impl From<&ChartEventData> for EventDataJson {
+    fn from(data: &ChartEventData) -> Self {
+        match data {
+            ChartEventData::Created(ChartCreated { title }) => Self::Created {
+                title: title.to_owned(),
+            },
+            ChartEventData::Deleted(ChartDeleted {}) => Self::Deleted {},
+            ChartEventData::SeriesCreated(ChartSeriesCreated { name, series_id }) => {
+                Self::SeriesCreated {
+                    name: name.to_owned(),
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }) => {
+                Self::SeriesDeleted {
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }) => {
+                Self::SeriesRenamed {
+                    name: name.to_owned(),
+                    series_id: series_id.to_string(),
+                }
+            }
+            ChartEventData::Updated(ChartUpdated { title }) => Self::Updated {
+                title: title.to_owned(),
+            },
+        }
+    }
+}
+
+impl TryFrom<EventDataJson> for ChartEventData {
+    type Error = BoxError;
+
+    fn try_from(data: EventDataJson) -> Result<Self, Self::Error> {
+        Ok(match data {
+            EventDataJson::Created { title } => Self::Created(ChartCreated { title }),
+            EventDataJson::Deleted {} => Self::Deleted(ChartDeleted {}),
+            EventDataJson::SeriesCreated { name, series_id } => {
+                Self::SeriesCreated(ChartSeriesCreated {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventDataJson::SeriesDeleted { series_id } => Self::SeriesDeleted(ChartSeriesDeleted {
+                series_id: SeriesId::from_str(&series_id)?,
+            }),
+            EventDataJson::SeriesRenamed { name, series_id } => {
+                Self::SeriesRenamed(ChartSeriesRenamed {
+                    name,
+                    series_id: SeriesId::from_str(&series_id)?,
+                })
+            }
+            EventDataJson::Updated { title } => Self::Updated(ChartUpdated { title }),
+        })
+    }
+}
+
+impl TryFrom<&ChartEvent> for EventRow {
+    type Error = BoxError;
+
+    fn try_from(event: &ChartEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            at: event.at.to_string(),
+            data: serde_json::to_string(&EventDataJson::from(&event.data))?,
+            id: event.id.to_string(),
+            stream_id: event.stream_id.to_string(),
+            version: i64::from(event.version),
+        })
+    }
+}
+
+impl TryFrom<EventRow> for ChartEvent {
+    type Error = BoxError;
+
+    fn try_from(
+        EventRow {
+            at,
+            data,
+            id,
+            stream_id,
+            version,
+        }: EventRow,
+    ) -> Result<Self, Self::Error> {
+        Ok(ChartEvent {
+            at: at.parse()?,
+            data: ChartEventData::try_from(serde_json::from_str::<EventDataJson>(&data)?)?,
+            id: id.parse()?,
+            stream_id: stream_id.parse()?,
+            version: Version::try_from(version)?,
+        })
+    }
+}
+
+/// An element of the `series` column of the `charts` table
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct SeriesJson {
+    id: String,
+    name: String,
+}
+
+const CHART_COLUMNS: &str = "id, created_at, series, title, updated_at, version";
+
+fn chart_from_row(row: &Row<'_>) -> Result<query_use_case::port::ChartQueryData, BoxError> {
+    let series = serde_json::from_str::<Vec<SeriesJson>>(&row.get::<_, String>("series")?)?;
+    Ok(query_use_case::port::ChartQueryData {
+        created_at: row.get::<_, String>("created_at")?.parse()?,
+        id: row.get::<_, String>("id")?.parse()?,
+        series: series
+            .into_iter()
+            .map(|SeriesJson { id, name }| {
+                Ok(query_use_case::port::SeriesQueryData {
+                    id: SeriesId::from_str(&id)?,
+                    name,
+                })
+            })
+            .collect::<Result<Vec<_>, BoxError>>()?,
+        title: row.get("title")?,
+        updated_at: row.get::<_, String>("updated_at")?.parse()?,
+        version: Version::try_from(row.get::<_, i64>("version")?)?,
+    })
+}
+
+fn select_chart(
+    connection: &Connection,
+    id: ChartId,
+) -> Result<Option<query_use_case::port::ChartQueryData>, BoxError> {
+    connection
+        .query_row(
+            &format!("SELECT {} FROM charts WHERE id = ?1", CHART_COLUMNS),
+            params![id.to_string()],
+            |row| Ok(chart_from_row(row)),
+        )
+        .optional()?
+        .transpose()
+        .map_err(|e| CorruptedError(e).into())
+}
+
+/// Writes the chart to the `charts` table (`None` deletes it)
+fn write_chart(
+    connection: &Connection,
+    id: ChartId,
+    chart: Option<&query_use_case::port::ChartQueryData>,
+) -> Result<(), BoxError> {
+    match chart {
+        None => {
+            connection.execute("DELETE FROM charts WHERE id = ?1", params![id.to_string()])?;
+        }
+        Some(chart) => {
+            let series = chart
+                .series
+                .iter()
+                .map(|series| SeriesJson {
+                    id: series.id.to_string(),
+                    name: series.name.clone(),
+                })
+                .collect::<Vec<SeriesJson>>();
+            connection.execute(
+                &format!(
+                    "INSERT OR REPLACE INTO charts ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    CHART_COLUMNS
+                ),
+                params![
+                    chart.id.to_string(),
+                    chart.created_at.to_string(),
+                    serde_json::to_string(&series)?,
+                    chart.title,
+                    chart.updated_at.to_string(),
+                    i64::from(chart.version)
+                ],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub struct SqliteChartStore {
+    connection: SqliteConnection,
+}
+
+impl SqliteChartStore {
+    /// Opens the database file (created if it does not exist) and migrates its schema
+    pub fn new(path: PathBuf) -> Result<Self, BoxError> {
+        Ok(Self {
+            connection: SqliteConnection::open(&path)?,
+        })
+    }
+
+    async fn find_impl(&self, id: ChartId) -> Result<Option<Chart>, BoxError> {
+        self.connection
+            .run(move |connection| {
+                let events = event_table::select_by_stream_id(connection, &id.to_string())?
+                    .into_iter()
+                    .map(ChartEvent::try_from)
+                    .collect::<Result<Vec<ChartEvent>, BoxError>>()
+                    .map_err(CorruptedError)?;
+                if events.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(
+                    Chart::from_events(&events).map_err(|e| CorruptedError(e.into()))?,
+                ))
+            })
+            .await
+    }
+
+    async fn find_events_impl(&self, id: ChartId) -> Result<Vec<ChartEvent>, BoxError> {
+        self.connection
+            .run(move |connection| {
+                event_table::select_by_stream_id(connection, &id.to_string())?
+                    .into_iter()
+                    .map(ChartEvent::try_from)
+                    .collect::<Result<Vec<ChartEvent>, BoxError>>()
+                    .map_err(|e| CorruptedError(e).into())
+            })
+            .await
+    }
+
+    async fn find_ids_impl(&self) -> Result<Vec<ChartId>, BoxError> {
+        self.connection
+            .run(|connection| {
+                let mut ids = event_table::select_stream_ids(connection, StreamType::Chart)?
+                    .iter()
+                    .map(|id| ChartId::from_str(id).map_err(BoxError::from))
+                    .collect::<Result<Vec<ChartId>, BoxError>>()
+                    .map_err(CorruptedError)?;
+                // the ids are read in no particular order
+                ids.sort();
+                Ok(ids)
+            })
+            .await
+    }
+
+    async fn find_all_events_impl(&self) -> Result<Vec<ChartEvent>, BoxError> {
+        self.connection
+            .run(|connection| {
+                let mut events = event_table::select_by_stream_type(connection, StreamType::Chart)?
+                    .into_iter()
+                    .map(ChartEvent::try_from)
+                    .collect::<Result<Vec<ChartEvent>, BoxError>>()
+                    .map_err(CorruptedError)?;
+                events.sort_by_key(|event| (event.stream_id, event.version));
+                Ok(events)
+            })
+            .await
+    }
+
+    async fn get_impl(
+        &self,
+        id: ChartId,
+    ) -> Result<Option<query_use_case::port::ChartQueryData>, BoxError> {
+        self.connection
+            .run(move |connection| select_chart(connection, id))
+            .await
+    }
+
+    async fn list_impl(
+        &self,
+        query: query_use_case::port::ChartListQuery,
+    ) -> Result<Vec<query_use_case::port::ChartQueryData>, BoxError> {
+        self.connection
+            .run(move |connection| {
+                // the timestamps are stored in the string form of `ChartSortKey::value`
+                let column = match query.sort_key {
+                    query_use_case::port::ChartSortKey::CreatedAt => "created_at",
+                    query_use_case::port::ChartSortKey::Title => "title",
+                    query_use_case::port::ChartSortKey::UpdatedAt => "updated_at",
+                };
+                let (operator, order) = match query.order {
+                    query_use_case::port::SortOrder::Ascending => (">", "ASC"),
+                    query_use_case::port::SortOrder::Descending => ("<", "DESC"),
+                };
+                let mut statement = connection.prepare(&format!(
+                    "SELECT {columns} FROM charts WHERE ?1 IS NULL OR ({column}, id) {operator} (?1, ?2) ORDER BY {column} {order}, id {order} LIMIT ?3",
+                    columns = CHART_COLUMNS,
+                ))?;
+                let after = query.after.as_ref();
+                let rows = statement.query_map(
+                    params![
+                        after.map(|cursor| cursor.key.as_str()),
+                        after.map(|cursor| cursor.id.to_string()),
+                        i64::try_from(query.limit)?
+                    ],
+                    |row| Ok(chart_from_row(row)),
+                )?;
+                let mut charts = vec![];
+                for row in rows {
+                    charts.push(row?.map_err(CorruptedError)?);
+                }
+                Ok(charts)
+            })
+            .await
+    }
+
+    async fn store_impl(
+        &self,
+        current: Option<Version>,
+        events: &[ChartEvent],
+    ) -> Result<(), BoxError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let events = events.to_vec();
+        self.connection
+            .run(move |connection| {
+                let id = events[0].stream_id;
+                let transaction =
+                    connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+                check_version(
+                    current,
+                    event_table::last_version(&transaction, &id.to_string())?,
+                )?;
+                // the query data is updated in the same transaction
+                let mut chart = select_chart(&transaction, id)?;
+                for event in &events {
+                    event_table::insert(
+                        &transaction,
+                        StreamType::Chart,
+                        &EventRow::try_from(event)?,
+                    )?;
+                    chart = projection::project_chart(chart, event)
+                        .map_err(|e| CorruptedError(e.into()))?;
+                }
+                write_chart(&transaction, id, chart.as_ref())?;
+                transaction.commit()?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl command_use_case::port::ChartRepository for SqliteChartStore {
+    async fn find(
+        &self,
+        id: ChartId,
+    ) -> Result<Option<Chart>, command_use_case::port::chart_repository::Error> {
+        self.find_impl(id).await.map_err(chart_repository_error)
+    }
+
+    async fn find_all_events(
+        &self,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_all_events_impl()
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_events(
+        &self,
+        id: ChartId,
+    ) -> Result<Vec<ChartEvent>, command_use_case::port::chart_repository::Error> {
+        self.find_events_impl(id)
+            .await
+            .map_err(chart_repository_error)
+    }
+
+    async fn find_ids(
+        &self,
+    ) -> Result<Vec<ChartId>, command_use_case::port::chart_repository::Error> {
+        self.find_ids_impl().await.map_err(chart_repository_error)
+    }
+
+    async fn store(
+        &self,
+        current: Option<Version>,
+        events: &[ChartEvent],
+    ) -> Result<(), command_use_case::port::chart_repository::Error> {
+        self.store_impl(current, events)
+            .await
+            .map_err(chart_repository_error)
+    }
+}
+
+#[async_trait::async_trait]
+impl query_use_case::port::ChartReader for SqliteChartStore {
+    async fn get(
+        &self,
+        id: ChartId,
+    ) -> Result<
+        Option<query_use_case::port::ChartQueryData>,
+        query_use_case::port::chart_reader::Error,
+    > {
+        self.get_impl(id).await.map_err(chart_reader_error)
+    }
+
+    async fn list(
+        &self,
+        query: query_use_case::port::ChartListQuery,
+    ) -> Result<Vec<query_use_case::port::ChartQueryData>, query_use_case::port::chart_reader::Error>
+    {
+        self.list_impl(query).await.map_err(chart_reader_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_use_case::port::ChartRepository as _;
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_test_kit() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("sqlite_store")?;
+        let store = SqliteChartStore::new(temp_dir.path().join("date_line_charts.sqlite"))
+            .map_err(|e| anyhow::anyhow!(e))?;
+        store_test_kit::test_chart_store(&store).await
+    }
+
+    #[tokio::test]
+    async fn test_reopen() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new("sqlite_store")?;
+        let path = temp_dir.path().join("date_line_charts.sqlite");
+        let store = SqliteChartStore::new(path.clone()).map_err(|e| anyhow::anyhow!(e))?;
+        let (chart, events) = Chart::create("title1".to_owned())?;
+        store.store(None, &events).await?;
+        let version = chart.version();
+        let (chart, events) = chart.create_series("series1".to_owned())?;
+        store.store(Some(version), &events).await?;
+        drop(store);
+
+        let store = SqliteChartStore::new(path).map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(store.find(chart.id()).await?, Some(chart.clone()));
+        let query_data = query_use_case::port::ChartReader::get(&store, chart.id())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("not found"))?;
+        assert_eq!(query_data.series[0].name, "series1");
+        Ok(())
+    }
+}
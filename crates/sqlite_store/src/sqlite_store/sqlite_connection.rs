@@ -0,0 +1,36 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use rusqlite::Connection;
+use store_error::BoxError;
+
+use crate::sqlite_store::migration;
+
+/// The connection to the database file. rusqlite blocks the thread, so the queries run on the
+/// blocking threads of the runtime instead of the async workers. Cheap to clone
+#[derive(Clone)]
+pub(crate) struct SqliteConnection(Arc<Mutex<Connection>>);
+
+impl SqliteConnection {
+    /// Opens the database file (created if it does not exist) and migrates its schema
+    pub(crate) fn open(path: &Path) -> Result<Self, BoxError> {
+        Ok(Self(Arc::new(Mutex::new(migration::open(path)?))))
+    }
+
+    /// Runs `f` with the connection on a blocking thread. One `f` runs at a time
+    pub(crate) async fn run<T, F>(&self, f: F) -> Result<T, BoxError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, BoxError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let connection = Arc::clone(&self.0);
+        tokio::task::spawn_blocking(move || {
+            // a panic in `f` rolls its transaction back when unwinding, so the connection is usable
+            let mut connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+            f(&mut connection)
+        })
+        .await?
+    }
+}
@@ -0,0 +1,3 @@
+mod store_error;
+
+pub use self::store_error::*;
@@ -0,0 +1,104 @@
+//! Errors shared by the stores and the ports they implement
+use write_model::value_object::Version;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The stream already exists, or its last version is not the expected one
+#[derive(Debug, thiserror::Error)]
+pub enum ConflictError {
+    #[error("stream already exists")]
+    AlreadyExists,
+    #[error("version mismatch (expected = {expected:?}, actual = {actual:?})")]
+    VersionMismatch { actual: Version, expected: Version },
+}
+
+/// The stored events or the query data built from them are invalid
+#[derive(Debug, thiserror::Error)]
+#[error("corrupted")]
+pub struct CorruptedError(#[source] pub BoxError);
+
+/// Events were appended to a stream that does not exist
+#[derive(Debug, thiserror::Error)]
+#[error("stream not found")]
+pub struct StreamNotFoundError;
+
+/// The class of a store error, which decides the variant of the port error
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    Conflict,
+    Corrupted,
+    NotFound,
+    Unavailable,
+    Other,
+}
+
+impl ErrorKind {
+    /// Classifies `error`. `backend` classifies the errors of the storage itself
+    /// (e.g. a unique violation); the errors of this crate are classified here
+    pub fn of<F>(error: &(dyn std::error::Error + 'static), backend: F) -> Self
+    where
+        F: FnOnce(&(dyn std::error::Error + 'static)) -> Option<Self>,
+    {
+        if error.is::<ConflictError>() {
+            Self::Conflict
+        } else if error.is::<CorruptedError>() {
+            Self::Corrupted
+        } else if error.is::<StreamNotFoundError>() {
+            Self::NotFound
+        } else {
+            backend(error).unwrap_or(Self::Other)
+        }
+    }
+}
+
+/// Checks that `current` matches the version of the last stored event (`None` = no stream)
+pub fn check_version(current: Option<Version>, stored: Option<Version>) -> Result<(), BoxError> {
+    match (current, stored) {
+        (None, None) => Ok(()),
+        (None, Some(_)) => Err(ConflictError::AlreadyExists.into()),
+        (Some(_), None) => Err(StreamNotFoundError.into()),
+        (Some(expected), Some(actual)) if expected == actual => Ok(()),
+        (Some(expected), Some(actual)) => {
+            Err(ConflictError::VersionMismatch { actual, expected }.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_version() -> anyhow::Result<()> {
+        let v1 = Version::new();
+        let v2 = v1.next()?;
+        let kind = |result: Result<(), BoxError>| result.map_err(|e| ErrorKind::of(&*e, |_| None));
+        assert_eq!(kind(check_version(None, None)), Ok(()));
+        assert_eq!(
+            kind(check_version(None, Some(v1))),
+            Err(ErrorKind::Conflict)
+        );
+        assert_eq!(
+            kind(check_version(Some(v1), None)),
+            Err(ErrorKind::NotFound)
+        );
+        assert_eq!(kind(check_version(Some(v1), Some(v1))), Ok(()));
+        assert_eq!(
+            kind(check_version(Some(v1), Some(v2))),
+            Err(ErrorKind::Conflict)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_kind_of() {
+        let error: BoxError = CorruptedError("x".into()).into();
+        assert_eq!(ErrorKind::of(&*error, |_| None), ErrorKind::Corrupted);
+        let error: BoxError = "x".into();
+        assert_eq!(ErrorKind::of(&*error, |_| None), ErrorKind::Other);
+        assert_eq!(
+            ErrorKind::of(&*error, |_| Some(ErrorKind::Unavailable)),
+            ErrorKind::Unavailable
+        );
+    }
+}
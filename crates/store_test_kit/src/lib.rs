@@ -0,0 +1,3 @@
+mod store_test_kit;
+
+pub use self::store_test_kit::*;
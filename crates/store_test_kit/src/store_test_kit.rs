@@ -0,0 +1,6 @@
+//! Behavioral tests that every store runs against itself
+mod chart_store;
+mod data_point_store;
+
+pub use self::chart_store::*;
+pub use self::data_point_store::*;
@@ -0,0 +1,269 @@
+use std::collections::BTreeSet;
+
+use anyhow::Context as _;
+use command_use_case::port::{chart_repository::Error, ChartRepository};
+use query_use_case::port::{
+    ChartListQuery, ChartQueryData, ChartReader, ChartSortKey, SeriesQueryData, SortOrder,
+};
+use write_model::{
+    aggregate::Chart,
+    event::ChartEvent,
+    value_object::{ChartId, DateTime},
+};
+
+/// Runs the chart tests against `store`.
+/// Every test creates its own charts, so `store` may already contain other charts
+pub async fn test_chart_store<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    test_round_trip(store).await.context("round trip")?;
+    test_conflict(store).await.context("conflict")?;
+    test_delete(store).await.context("delete")?;
+    test_find_all_events(store)
+        .await
+        .context("find all events")?;
+    test_find_events(store).await.context("find events")?;
+    test_list(store).await.context("list")?;
+    Ok(())
+}
+
+fn query_data(chart: &Chart, created_at: DateTime, updated_at: DateTime) -> ChartQueryData {
+    ChartQueryData {
+        created_at,
+        id: chart.id(),
+        series: chart
+            .series()
+            .iter()
+            .map(|series| SeriesQueryData {
+                id: series.id(),
+                name: series.name().to_owned(),
+            })
+            .collect(),
+        title: chart.title().to_owned(),
+        updated_at,
+        version: chart.version(),
+    }
+}
+
+/// Lists every chart, 2 charts per page
+async fn list_all<S>(
+    store: &S,
+    sort_key: ChartSortKey,
+    order: SortOrder,
+) -> anyhow::Result<Vec<ChartQueryData>>
+where
+    S: ChartReader + Send + Sync,
+{
+    let mut query = ChartListQuery {
+        after: None,
+        limit: 2,
+        order,
+        sort_key,
+    };
+    let mut charts = vec![];
+    loop {
+        let page = store.list(query.clone()).await?;
+        anyhow::ensure!(page.len() <= query.limit, "the page exceeds the limit");
+        query.after = page.last().map(|chart| query.cursor(chart));
+        let is_end = page.len() < query.limit;
+        charts.extend(page);
+        if is_end {
+            return Ok(charts);
+        }
+    }
+}
+
+async fn test_round_trip<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let (chart, created) = Chart::create("title1".to_owned())?;
+    assert_eq!(store.find(chart.id()).await?, None);
+    assert_eq!(store.get(chart.id()).await?, None);
+    store.store(None, &created).await?;
+    assert_eq!(store.find(chart.id()).await?, Some(chart.clone()));
+    assert_eq!(
+        store.get(chart.id()).await?,
+        Some(query_data(&chart, created[0].at, created[0].at))
+    );
+
+    // several events at once
+    let version = chart.version();
+    let (chart, updated) = chart.update("title2".to_owned())?;
+    let (chart, series_created) = chart.create_series("series1".to_owned())?;
+    store
+        .store(Some(version), &[updated, series_created].concat())
+        .await?;
+    let series_id = chart.series()[0].id();
+    let version = chart.version();
+    let (chart, series_renamed) = chart.rename_series(series_id, "series2".to_owned())?;
+    store.store(Some(version), &series_renamed).await?;
+    assert_eq!(store.find(chart.id()).await?, Some(chart.clone()));
+    assert_eq!(
+        store.get(chart.id()).await?,
+        Some(query_data(&chart, created[0].at, series_renamed[0].at))
+    );
+
+    let version = chart.version();
+    let (chart, series_deleted) = chart.delete_series(series_id)?;
+    store.store(Some(version), &series_deleted).await?;
+    assert_eq!(store.find(chart.id()).await?, Some(chart.clone()));
+    assert_eq!(
+        store.get(chart.id()).await?,
+        Some(query_data(&chart, created[0].at, series_deleted[0].at))
+    );
+    Ok(())
+}
+
+async fn test_conflict<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let (chart, created) = Chart::create("title1".to_owned())?;
+    let (updated_chart, updated) = chart.update("title2".to_owned())?;
+    assert!(matches!(
+        store.store(Some(chart.version()), &updated).await,
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(store.find(chart.id()).await?, None);
+
+    store.store(None, &created).await?;
+    assert!(matches!(
+        store.store(None, &created).await,
+        Err(Error::Conflict(_))
+    ));
+    store.store(Some(chart.version()), &updated).await?;
+    let (_, stale) = chart.update("title3".to_owned())?;
+    assert!(matches!(
+        store.store(Some(chart.version()), &stale).await,
+        Err(Error::Conflict(_))
+    ));
+    assert_eq!(store.find(chart.id()).await?, Some(updated_chart.clone()));
+    assert_eq!(
+        store.get(chart.id()).await?,
+        Some(query_data(&updated_chart, created[0].at, updated[0].at))
+    );
+    Ok(())
+}
+
+async fn test_delete<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let (chart, created) = Chart::create("title1".to_owned())?;
+    store.store(None, &created).await?;
+    let (deleted_chart, deleted) = chart.delete()?;
+    store.store(Some(chart.version()), &deleted).await?;
+    // the repository keeps the events, the reader hides the chart
+    assert_eq!(store.find(chart.id()).await?, Some(deleted_chart));
+    assert_eq!(store.get(chart.id()).await?, None);
+    assert!(
+        !list_all(store, ChartSortKey::CreatedAt, SortOrder::Ascending)
+            .await?
+            .iter()
+            .any(|listed| listed.id == chart.id()),
+        "the deleted chart is listed"
+    );
+    Ok(())
+}
+
+async fn test_find_all_events<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let (chart1, created1) = Chart::create("title1".to_owned())?;
+    store.store(None, &created1).await?;
+    let (chart2, created2) = Chart::create("title2".to_owned())?;
+    store.store(None, &created2).await?;
+    let (_, updated1) = chart1.update("title3".to_owned())?;
+    store.store(Some(chart1.version()), &updated1).await?;
+
+    let events = store.find_all_events().await?;
+    assert!(
+        events
+            .windows(2)
+            .all(|w| (w[0].stream_id, w[0].version) < (w[1].stream_id, w[1].version)),
+        "the events are not ordered by stream id and version"
+    );
+    let ids = BTreeSet::from([chart1.id(), chart2.id()]);
+    let events = events
+        .into_iter()
+        .filter(|event| ids.contains(&event.stream_id))
+        .collect::<Vec<ChartEvent>>();
+    let events1 = [created1, updated1].concat();
+    let expected = if chart1.id() < chart2.id() {
+        [events1, created2].concat()
+    } else {
+        [created2, events1].concat()
+    };
+    assert_eq!(events, expected);
+    Ok(())
+}
+
+async fn test_find_events<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let (chart1, created1) = Chart::create("title1".to_owned())?;
+    store.store(None, &created1).await?;
+    let (chart2, created2) = Chart::create("title2".to_owned())?;
+    store.store(None, &created2).await?;
+    let (chart1_updated, updated1) = chart1.update("title3".to_owned())?;
+    store.store(Some(chart1.version()), &updated1).await?;
+    let (_, deleted1) = chart1_updated.delete()?;
+    store
+        .store(Some(chart1_updated.version()), &deleted1)
+        .await?;
+
+    assert_eq!(
+        store.find_events(chart1.id()).await?,
+        [created1, updated1, deleted1].concat(),
+        "the events of the deleted chart are not found"
+    );
+    assert_eq!(store.find_events(chart2.id()).await?, created2);
+    assert_eq!(store.find_events(ChartId::generate()).await?, vec![]);
+
+    let ids = store.find_ids().await?;
+    assert!(
+        ids.windows(2).all(|w| w[0] < w[1]),
+        "the ids are not ordered"
+    );
+    assert!(ids.contains(&chart1.id()), "the deleted chart is not found");
+    assert!(ids.contains(&chart2.id()));
+    Ok(())
+}
+
+async fn test_list<S>(store: &S) -> anyhow::Result<()>
+where
+    S: ChartReader + ChartRepository + Send + Sync,
+{
+    let mut ids = BTreeSet::new();
+    for title in ["b", "a", "c"] {
+        let (chart, created) = Chart::create(title.to_owned())?;
+        store.store(None, &created).await?;
+        ids.insert(chart.id());
+    }
+
+    for (order, expected) in [
+        (SortOrder::Ascending, ["a", "b", "c"]),
+        (SortOrder::Descending, ["c", "b", "a"]),
+    ] {
+        let charts = list_all(store, ChartSortKey::Title, order).await?;
+        let listed_ids = charts
+            .iter()
+            .map(|chart| chart.id)
+            .collect::<BTreeSet<ChartId>>();
+        assert_eq!(listed_ids.len(), charts.len(), "a chart is listed twice");
+        let titles = charts
+            .iter()
+            .filter(|chart| ids.contains(&chart.id))
+            .map(|chart| chart.title.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(titles, expected);
+        for chart in charts.iter().filter(|chart| ids.contains(&chart.id)) {
+            assert_eq!(store.get(chart.id).await?.as_ref(), Some(chart));
+        }
+    }
+    Ok(())
+}
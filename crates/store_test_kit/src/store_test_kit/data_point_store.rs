@@ -0,0 +1,385 @@
+use std::{collections::BTreeSet, str::FromStr as _};
+
+use anyhow::Context as _;
+use command_use_case::port::{data_point_repository::Error, DataPointRepository};
+use query_use_case::port::{
+    DataPointCursor, DataPointQueryData, DataPointReader, SeriesFilter, XValueRange,
+};
+use write_model::{
+    aggregate::DataPoint,
+    event::DataPointEvent,
+    value_object::{ChartId, DateTime, SeriesId, XValue, YValue},
+};
+
+/// Runs the data point tests against `store`.
+/// Every test uses new chart ids, so `store` may already contain other data points
+pub async fn test_data_point_store<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    test_round_trip(store).await.context("round trip")?;
+    test_conflict(store).await.context("conflict")?;
+    test_delete(store).await.context("delete")?;
+    test_find_all_events(store)
+        .await
+        .context("find all events")?;
+    test_find_events(store).await.context("find events")?;
+    test_list(store).await.context("list")?;
+    test_list_page(store).await.context("list page")?;
+    Ok(())
+}
+
+fn query_data(data_point: &DataPoint, created_at: DateTime) -> DataPointQueryData {
+    DataPointQueryData {
+        chart_id: data_point.chart_id(),
+        created_at,
+        series_id: data_point.series_id(),
+        version: data_point.version(),
+        x_value: data_point.x_value(),
+        y_value: data_point.y_value(),
+    }
+}
+
+async fn create<S>(
+    store: &S,
+    chart_id: ChartId,
+    series_id: Option<SeriesId>,
+    x_value: &str,
+) -> anyhow::Result<(DataPoint, Vec<DataPointEvent>)>
+where
+    S: DataPointRepository + Send + Sync,
+{
+    let (data_point, created) = DataPoint::create(
+        chart_id,
+        series_id,
+        XValue::from_str(x_value)?,
+        YValue::from(1_u32),
+    )?;
+    store.store(None, &created).await?;
+    Ok((data_point, created))
+}
+
+/// Lists the data points in `[from, to)` sorted by series id and x value.
+/// The order of the listed data points is not specified
+async fn list<S>(
+    store: &S,
+    chart_id: ChartId,
+    series: SeriesFilter,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> anyhow::Result<Vec<DataPointQueryData>>
+where
+    S: DataPointReader + Send + Sync,
+{
+    let range = XValueRange {
+        from: from.map(XValue::from_str).transpose()?,
+        to: to.map(XValue::from_str).transpose()?,
+    };
+    let mut data_points = store.list(chart_id, series, range).await?;
+    data_points.sort_by_key(|data_point| (data_point.series_id, data_point.x_value));
+    Ok(data_points)
+}
+
+async fn test_round_trip<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let (data_point, created) = DataPoint::create(
+        ChartId::generate(),
+        Some(SeriesId::generate()),
+        XValue::from_str("2020-01-02")?,
+        YValue::from_str("-1.25")?,
+    )?;
+    assert_eq!(store.find(data_point.id()).await?, None);
+    assert_eq!(store.get(data_point.id()).await?, None);
+    store.store(None, &created).await?;
+    assert_eq!(store.find(data_point.id()).await?, Some(data_point.clone()));
+    assert_eq!(
+        store.get(data_point.id()).await?,
+        Some(query_data(&data_point, created[0].at))
+    );
+
+    let version = data_point.version();
+    let (data_point, updated) = data_point.update(YValue::from(123_u32))?;
+    store.store(Some(version), &updated).await?;
+    assert_eq!(store.find(data_point.id()).await?, Some(data_point.clone()));
+    assert_eq!(
+        store.get(data_point.id()).await?,
+        Some(query_data(&data_point, created[0].at))
+    );
+    Ok(())
+}
+
+async fn test_conflict<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let (data_point, created) = DataPoint::create(
+        ChartId::generate(),
+        None,
+        XValue::from_str("2020-01-02")?,
+        YValue::from(1_u32),
+    )?;
+    let (updated_data_point, updated) = data_point.update(YValue::from(2_u32))?;
+    assert!(matches!(
+        store.store(Some(data_point.version()), &updated).await,
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(store.find(data_point.id()).await?, None);
+
+    store.store(None, &created).await?;
+    assert!(matches!(
+        store.store(None, &created).await,
+        Err(Error::Conflict(_))
+    ));
+    store.store(Some(data_point.version()), &updated).await?;
+    let (_, stale) = data_point.update(YValue::from(3_u32))?;
+    assert!(matches!(
+        store.store(Some(data_point.version()), &stale).await,
+        Err(Error::Conflict(_))
+    ));
+    assert_eq!(
+        store.find(data_point.id()).await?,
+        Some(updated_data_point.clone())
+    );
+    assert_eq!(
+        store.get(data_point.id()).await?,
+        Some(query_data(&updated_data_point, created[0].at))
+    );
+    Ok(())
+}
+
+async fn test_delete<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let chart_id = ChartId::generate();
+    let (data_point, _) = create(store, chart_id, None, "2020-01-02").await?;
+    let (deleted_data_point, deleted) = data_point.delete()?;
+    store.store(Some(data_point.version()), &deleted).await?;
+    // the repository keeps the events, the reader hides the data point
+    assert_eq!(store.find(data_point.id()).await?, Some(deleted_data_point));
+    assert_eq!(store.get(data_point.id()).await?, None);
+    assert_eq!(
+        store
+            .list(chart_id, SeriesFilter::All, XValueRange::default())
+            .await?,
+        vec![]
+    );
+    Ok(())
+}
+
+async fn test_find_all_events<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let chart_id = ChartId::generate();
+    let (data_point1, created1) = create(store, chart_id, None, "2020-01-01").await?;
+    let (data_point2, created2) = create(store, chart_id, None, "2020-01-02").await?;
+    let (_, updated1) = data_point1.update(YValue::from(2_u32))?;
+    store.store(Some(data_point1.version()), &updated1).await?;
+
+    let events = store.find_all_events().await?;
+    assert!(
+        events
+            .windows(2)
+            .all(|w| (w[0].stream_id, w[0].version) < (w[1].stream_id, w[1].version)),
+        "the events are not ordered by stream id and version"
+    );
+    let ids = BTreeSet::from([data_point1.id(), data_point2.id()]);
+    let events = events
+        .into_iter()
+        .filter(|event| ids.contains(&event.stream_id))
+        .collect::<Vec<DataPointEvent>>();
+    let events1 = [created1, updated1].concat();
+    let expected = if data_point1.id() < data_point2.id() {
+        [events1, created2].concat()
+    } else {
+        [created2, events1].concat()
+    };
+    assert_eq!(events, expected);
+    Ok(())
+}
+
+async fn test_find_events<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let chart_id = ChartId::generate();
+    let (data_point1, created1) = create(store, chart_id, None, "2020-01-01").await?;
+    let (data_point2, created2) = create(store, chart_id, None, "2020-01-02").await?;
+    let (data_point1_updated, updated1) = data_point1.update(YValue::from(2_u32))?;
+    store.store(Some(data_point1.version()), &updated1).await?;
+    let (_, deleted1) = data_point1_updated.delete()?;
+    store
+        .store(Some(data_point1_updated.version()), &deleted1)
+        .await?;
+
+    assert_eq!(
+        store.find_events(data_point1.id()).await?,
+        [created1, updated1, deleted1].concat(),
+        "the events of the deleted data point are not found"
+    );
+    assert_eq!(store.find_events(data_point2.id()).await?, created2);
+    assert_eq!(
+        store
+            .find_events(
+                DataPoint::create(
+                    chart_id,
+                    None,
+                    XValue::from_str("2020-01-03")?,
+                    YValue::from(1_u32)
+                )?
+                .0
+                .id()
+            )
+            .await?,
+        vec![]
+    );
+
+    let ids = store.find_ids().await?;
+    assert!(
+        ids.windows(2).all(|w| w[0] < w[1]),
+        "the ids are not ordered"
+    );
+    assert!(
+        ids.contains(&data_point1.id()),
+        "the deleted data point is not found"
+    );
+    assert!(ids.contains(&data_point2.id()));
+    Ok(())
+}
+
+async fn test_list<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let chart_id = ChartId::generate();
+    let series_id = SeriesId::generate();
+    let (data_point1, created1) = create(store, chart_id, None, "2020-01-01").await?;
+    let (data_point2, created2) = create(store, chart_id, None, "2020-01-02").await?;
+    let (data_point3, created3) = create(store, chart_id, None, "2020-01-03").await?;
+    let (data_point4, created4) = create(store, chart_id, Some(series_id), "2020-01-02").await?;
+    create(store, ChartId::generate(), None, "2020-01-02").await?;
+    let expected1 = query_data(&data_point1, created1[0].at);
+    let expected2 = query_data(&data_point2, created2[0].at);
+    let expected3 = query_data(&data_point3, created3[0].at);
+    let expected4 = query_data(&data_point4, created4[0].at);
+
+    assert_eq!(
+        list(store, chart_id, SeriesFilter::All, None, None).await?,
+        vec![
+            expected1.clone(),
+            expected2.clone(),
+            expected3.clone(),
+            expected4.clone(),
+        ]
+    );
+    assert_eq!(
+        list(store, chart_id, SeriesFilter::Series(series_id), None, None).await?,
+        vec![expected4.clone()]
+    );
+    assert_eq!(
+        list(store, chart_id, SeriesFilter::Default, None, None).await?,
+        vec![expected1.clone(), expected2.clone(), expected3.clone()]
+    );
+    assert_eq!(
+        list(
+            store,
+            chart_id,
+            SeriesFilter::All,
+            Some("2020-01-02"),
+            Some("2020-01-03")
+        )
+        .await?,
+        vec![expected2.clone(), expected4]
+    );
+    assert_eq!(
+        list(store, chart_id, SeriesFilter::All, Some("2020-01-03"), None).await?,
+        vec![expected3]
+    );
+    assert_eq!(
+        list(store, chart_id, SeriesFilter::All, None, Some("2020-01-02")).await?,
+        vec![expected1]
+    );
+    assert_eq!(
+        store
+            .list(
+                ChartId::generate(),
+                SeriesFilter::All,
+                XValueRange::default()
+            )
+            .await?,
+        vec![]
+    );
+    Ok(())
+}
+
+async fn test_list_page<S>(store: &S) -> anyhow::Result<()>
+where
+    S: DataPointReader + DataPointRepository + Send + Sync,
+{
+    let chart_id = ChartId::generate();
+    let series_id = SeriesId::generate();
+    let mut expected = vec![];
+    for (series_id, x_value) in [
+        (Some(series_id), "2020-01-01"),
+        (None, "2020-01-01"),
+        (None, "2020-01-02"),
+        (Some(series_id), "2020-01-03"),
+        (None, "2020-01-04"),
+    ] {
+        let (data_point, created) = create(store, chart_id, series_id, x_value).await?;
+        expected.push(query_data(&data_point, created[0].at));
+    }
+    expected.sort_by_key(|data_point| DataPointCursor::from(data_point));
+
+    let mut pages = vec![];
+    let mut after = None;
+    loop {
+        let page = store
+            .list_page(
+                chart_id,
+                SeriesFilter::All,
+                XValueRange::default(),
+                after,
+                2,
+            )
+            .await?;
+        after = page.last().map(DataPointCursor::from);
+        let last_page = page.len() < 2;
+        pages.push(page);
+        if last_page {
+            break;
+        }
+    }
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![2, 2, 1]
+    );
+    assert_eq!(pages.concat(), expected);
+
+    let range = XValueRange {
+        from: Some(XValue::from_str("2020-01-02")?),
+        to: Some(XValue::from_str("2020-01-04")?),
+    };
+    assert_eq!(
+        store
+            .list_page(chart_id, SeriesFilter::Default, range, None, 10)
+            .await?,
+        vec![expected[2].clone()]
+    );
+    assert_eq!(
+        store
+            .list_page(
+                chart_id,
+                SeriesFilter::Series(series_id),
+                XValueRange::default(),
+                Some(DataPointCursor::from(&expected[1])),
+                10
+            )
+            .await?,
+        vec![expected[3].clone()]
+    );
+    Ok(())
+}
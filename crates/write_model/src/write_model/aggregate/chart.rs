@@ -1,25 +1,53 @@
-use crate::value_object::{ChartId, DateTime, Version};
+use crate::value_object::{ChartId, DateTime, SeriesId, Version};
 
-use crate::event::{ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartUpdated};
+use crate::event::{
+    ChartCreated, ChartDeleted, ChartEvent, ChartEventData, ChartSeriesCreated, ChartSeriesDeleted,
+    ChartSeriesRenamed, ChartUpdated,
+};
 
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum Error {
     #[error("already deleted")]
     AlreadyDeleted,
+    #[error("invalid series name")]
+    InvalidSeriesName,
     #[error("invalid title")]
     InvalidTitle,
     #[error("multiple created event")]
     MultipleCreatedEvent,
     #[error("no created event")]
     NoCreatedEvent,
+    #[error("series not found")]
+    SeriesNotFound,
     #[error("version overflow")]
     VersionOverflow,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Series {
+    id: SeriesId,
+    name: String,
+}
+
+impl Series {
+    pub fn reconstruct(id: SeriesId, name: String) -> Self {
+        Self { id, name }
+    }
+
+    pub fn id(&self) -> SeriesId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Chart {
     deleted_at: Option<DateTime>,
     id: ChartId,
+    series: Vec<Series>,
     title: String,
     version: Version,
 }
@@ -39,6 +67,7 @@ impl Chart {
         let state = Self {
             deleted_at: None,
             id: events[0].stream_id,
+            series: vec![],
             title,
             version: events[0].version,
         };
@@ -57,6 +86,7 @@ impl Chart {
             }) => Self {
                 deleted_at: None,
                 id: *stream_id,
+                series: vec![],
                 title: event.title.clone(),
                 version: *version,
             },
@@ -69,17 +99,39 @@ impl Chart {
     pub fn reconstruct(
         deleted_at: Option<DateTime>,
         id: ChartId,
+        series: Vec<Series>,
         title: String,
         version: Version,
     ) -> Self {
         Self {
             deleted_at,
             id,
+            series,
             title,
             version,
         }
     }
 
+    pub fn create_series(&self, name: String) -> Result<(Self, Vec<ChartEvent>), Error> {
+        if self.deleted_at.is_some() {
+            return Err(Error::AlreadyDeleted);
+        }
+        if name.is_empty() {
+            return Err(Error::InvalidSeriesName);
+        }
+        let events = vec![ChartEvent::new(
+            self.id,
+            ChartEventData::SeriesCreated(ChartSeriesCreated {
+                name,
+                series_id: SeriesId::generate(),
+            }),
+            self.version.next().map_err(|_| Error::VersionOverflow)?,
+        )];
+        let mut state = self.clone();
+        state.apply_events(&events)?;
+        Ok((state, events))
+    }
+
     pub fn delete(&self) -> Result<(Self, Vec<ChartEvent>), Error> {
         if self.deleted_at.is_some() {
             return Err(Error::AlreadyDeleted);
@@ -94,14 +146,63 @@ impl Chart {
         Ok((state, events))
     }
 
+    pub fn delete_series(&self, series_id: SeriesId) -> Result<(Self, Vec<ChartEvent>), Error> {
+        if self.deleted_at.is_some() {
+            return Err(Error::AlreadyDeleted);
+        }
+        if self.find_series(series_id).is_none() {
+            return Err(Error::SeriesNotFound);
+        }
+        let events = vec![ChartEvent::new(
+            self.id,
+            ChartEventData::SeriesDeleted(ChartSeriesDeleted { series_id }),
+            self.version.next().map_err(|_| Error::VersionOverflow)?,
+        )];
+        let mut state = self.clone();
+        state.apply_events(&events)?;
+        Ok((state, events))
+    }
+
     pub fn deleted_at(&self) -> Option<DateTime> {
         self.deleted_at
     }
 
+    pub fn find_series(&self, series_id: SeriesId) -> Option<&Series> {
+        self.series.iter().find(|series| series.id == series_id)
+    }
+
     pub fn id(&self) -> ChartId {
         self.id
     }
 
+    pub fn rename_series(
+        &self,
+        series_id: SeriesId,
+        name: String,
+    ) -> Result<(Self, Vec<ChartEvent>), Error> {
+        if self.deleted_at.is_some() {
+            return Err(Error::AlreadyDeleted);
+        }
+        if self.find_series(series_id).is_none() {
+            return Err(Error::SeriesNotFound);
+        }
+        if name.is_empty() {
+            return Err(Error::InvalidSeriesName);
+        }
+        let events = vec![ChartEvent::new(
+            self.id,
+            ChartEventData::SeriesRenamed(ChartSeriesRenamed { name, series_id }),
+            self.version.next().map_err(|_| Error::VersionOverflow)?,
+        )];
+        let mut state = self.clone();
+        state.apply_events(&events)?;
+        Ok((state, events))
+    }
+
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
     pub fn title(&self) -> &str {
         &self.title
     }
@@ -143,6 +244,26 @@ impl Chart {
                     self.deleted_at = Some(at);
                     self.version = version;
                 }
+                ChartEventData::SeriesCreated(e) => {
+                    self.series.push(Series {
+                        id: e.series_id,
+                        name: e.name.clone(),
+                    });
+                    self.version = version;
+                }
+                ChartEventData::SeriesDeleted(e) => {
+                    self.series.retain(|series| series.id != e.series_id);
+                    self.version = version;
+                }
+                ChartEventData::SeriesRenamed(e) => {
+                    let series = self
+                        .series
+                        .iter_mut()
+                        .find(|series| series.id == e.series_id)
+                        .ok_or(Error::SeriesNotFound)?;
+                    series.name.clone_from(&e.name);
+                    self.version = version;
+                }
             }
         }
         Ok(())
@@ -213,6 +334,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_series() -> anyhow::Result<()> {
+        let (state, mut all_events) = build_chart()?;
+        assert!(state.series().is_empty());
+
+        let (state, events) = state.create_series("systolic".to_string())?;
+        all_events.extend(events);
+        let (state, events) = state.create_series("diastolic".to_string())?;
+        all_events.extend(events);
+        assert_eq!(
+            state
+                .series()
+                .iter()
+                .map(|series| series.name())
+                .collect::<Vec<&str>>(),
+            vec!["systolic", "diastolic"]
+        );
+        assert_eq!(Chart::from_events(&all_events)?, state);
+
+        let series_id = state.series()[0].id();
+        let (state, events) = state.rename_series(series_id, "sys".to_string())?;
+        all_events.extend(events);
+        assert_eq!(
+            state.find_series(series_id).map(|series| series.name()),
+            Some("sys")
+        );
+        assert_eq!(Chart::from_events(&all_events)?, state);
+
+        let (state, events) = state.delete_series(series_id)?;
+        all_events.extend(events);
+        assert!(state.find_series(series_id).is_none());
+        assert_eq!(state.series().len(), 1);
+        assert_eq!(Chart::from_events(&all_events)?, state);
+
+        assert_eq!(
+            state.create_series(String::new()).unwrap_err(),
+            Error::InvalidSeriesName
+        );
+        assert_eq!(
+            state
+                .rename_series(series_id, "sys".to_string())
+                .unwrap_err(),
+            Error::SeriesNotFound
+        );
+        assert_eq!(
+            state.delete_series(series_id).unwrap_err(),
+            Error::SeriesNotFound
+        );
+        let (state, _) = state.delete()?;
+        assert_eq!(
+            state.create_series("pulse".to_string()).unwrap_err(),
+            Error::AlreadyDeleted
+        );
+        Ok(())
+    }
+
     fn build_chart() -> anyhow::Result<(Chart, Vec<ChartEvent>)> {
         Ok(Chart::create("title".to_string())?)
     }
@@ -1,4 +1,4 @@
-use crate::value_object::{ChartId, DataPointId, DateTime, Version, XValue, YValue};
+use crate::value_object::{ChartId, DataPointId, DateTime, SeriesId, Version, XValue, YValue};
 
 use crate::event::{
     DataPointCreated, DataPointDeleted, DataPointEvent, DataPointEventData, DataPointUpdated,
@@ -27,11 +27,12 @@ pub struct DataPoint {
 impl DataPoint {
     pub fn create(
         chart_id: ChartId,
+        series_id: Option<SeriesId>,
         x_value: XValue,
         y_value: YValue,
     ) -> Result<(Self, Vec<DataPointEvent>), Error> {
         let events = vec![DataPointEvent::new(
-            DataPointId::new(chart_id, x_value),
+            DataPointId::new(chart_id, series_id, x_value),
             DataPointEventData::Created(DataPointCreated { value: y_value }),
             Version::new(),
         )];
@@ -105,6 +106,10 @@ impl DataPoint {
         self.id
     }
 
+    pub fn series_id(&self) -> Option<SeriesId> {
+        self.id.series_id()
+    }
+
     pub fn x_value(&self) -> XValue {
         self.id.x_value()
     }
@@ -163,6 +168,7 @@ mod tests {
         let chart_id = ChartId::generate();
         let (state, events) = DataPoint::create(
             chart_id,
+            None,
             XValue::from_str("2020-01-02")?,
             YValue::from(123_u32),
         )?;
@@ -170,6 +176,7 @@ mod tests {
         assert_eq!(DataPoint::from_events(&all_events)?, state);
         assert!(state.deleted_at.is_none());
         assert_eq!(state.chart_id(), chart_id);
+        assert_eq!(state.series_id(), None);
         assert_eq!(state.x_value(), XValue::from_str("2020-01-02")?);
         assert_eq!(state.y_value(), YValue::from(123_u32));
         let (state, events) = state.update(YValue::from(456_u32))?;
@@ -232,6 +239,7 @@ mod tests {
         let chart_id = ChartId::generate();
         Ok(DataPoint::create(
             chart_id,
+            None,
             XValue::from_str("2020-01-02")?,
             YValue::from(123_u32),
         )?)
@@ -1,7 +1,7 @@
 use std::str::FromStr as _;
 
 use crate::value_object::{
-    ChartId, DataPointId, DateTime, EventId, EventStreamId, Version, YValue,
+    ChartId, DataPointId, DateTime, EventId, EventStreamId, SeriesId, Version, YValue,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -104,6 +104,9 @@ impl EventStream for DataPointEventStream {
 pub enum ChartEventData {
     Created(ChartCreated),
     Deleted(ChartDeleted),
+    SeriesCreated(ChartSeriesCreated),
+    SeriesDeleted(ChartSeriesDeleted),
+    SeriesRenamed(ChartSeriesRenamed),
     Updated(ChartUpdated),
 }
 
@@ -115,6 +118,23 @@ pub struct ChartCreated {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ChartDeleted {}
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartSeriesCreated {
+    pub name: String,
+    pub series_id: SeriesId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartSeriesDeleted {
+    pub series_id: SeriesId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartSeriesRenamed {
+    pub name: String,
+    pub series_id: SeriesId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ChartUpdated {
     pub title: String,
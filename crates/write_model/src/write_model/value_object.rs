@@ -3,6 +3,7 @@ pub mod data_point_id;
 pub mod date_time;
 pub mod event_id;
 pub mod event_stream_id;
+pub mod series_id;
 pub mod version;
 pub mod x_value;
 pub mod y_value;
@@ -12,6 +13,7 @@ pub use self::data_point_id::DataPointId;
 pub use self::date_time::DateTime;
 pub use self::event_id::EventId;
 pub use self::event_stream_id::EventStreamId;
+pub use self::series_id::SeriesId;
 pub use self::version::Version;
 pub use self::x_value::XValue;
 pub use self::y_value::YValue;
@@ -1,28 +1,39 @@
-use super::{ChartId, EventStreamId, XValue};
+use super::{ChartId, EventStreamId, SeriesId, XValue};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("chart id")]
     ChartId(#[source] crate::value_object::chart_id::Error),
+    #[error("invalid format")]
+    InvalidFormat,
+    #[error("series id")]
+    SeriesId(#[source] crate::value_object::series_id::Error),
     #[error("x value")]
     XValue(#[source] crate::value_object::x_value::Error),
 }
 
+/// Data point id
+///
+/// A data point without a series belongs to the chart's default series.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DataPointId(ChartId, XValue);
+pub struct DataPointId(ChartId, Option<SeriesId>, XValue);
 
 impl DataPointId {
-    pub fn new(chart_id: ChartId, x_value: XValue) -> Self {
-        Self(chart_id, x_value)
+    pub fn new(chart_id: ChartId, series_id: Option<SeriesId>, x_value: XValue) -> Self {
+        Self(chart_id, series_id, x_value)
     }
 
     pub fn chart_id(&self) -> ChartId {
         self.0
     }
 
-    pub fn x_value(&self) -> XValue {
+    pub fn series_id(&self) -> Option<SeriesId> {
         self.1
     }
+
+    pub fn x_value(&self) -> XValue {
+        self.2
+    }
 }
 
 impl From<DataPointId> for EventStreamId {
@@ -36,15 +47,26 @@ impl std::str::FromStr for DataPointId {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let chart_id = ChartId::from_str(&s[0..36]).map_err(Error::ChartId)?;
-        let x_value = XValue::from_str(&s[37..]).map_err(Error::XValue)?;
-        Ok(Self(chart_id, x_value))
+        let (chart_id, rest) = s.split_once(':').ok_or(Error::InvalidFormat)?;
+        let chart_id = ChartId::from_str(chart_id).map_err(Error::ChartId)?;
+        let (series_id, x_value) = match rest.split_once(':') {
+            None => (None, rest),
+            Some((series_id, x_value)) => (
+                Some(SeriesId::from_str(series_id).map_err(Error::SeriesId)?),
+                x_value,
+            ),
+        };
+        let x_value = XValue::from_str(x_value).map_err(Error::XValue)?;
+        Ok(Self(chart_id, series_id, x_value))
     }
 }
 
 impl std::fmt::Display for DataPointId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.0, self.1)
+        match self.1 {
+            None => write!(f, "{}:{}", self.0, self.2),
+            Some(series_id) => write!(f, "{}:{}:{}", self.0, series_id, self.2),
+        }
     }
 }
 
@@ -63,7 +85,25 @@ mod tests {
             id.chart_id(),
             ChartId::from_str("67051e1b-fc32-43c8-899f-e2c73a1319f4")?
         );
+        assert_eq!(id.series_id(), None);
+        assert_eq!(id.x_value(), XValue::from_str("2020-01-02")?);
+
+        let s =
+            "67051e1b-fc32-43c8-899f-e2c73a1319f4:0b6ac3a8-1bb4-4e5a-9b0e-2c1a6a4c7f3d:2020-01-02";
+        let id = DataPointId::from_str(s)?;
+        assert_eq!(id.to_string(), s);
+        assert_eq!(
+            id.series_id(),
+            Some(SeriesId::from_str("0b6ac3a8-1bb4-4e5a-9b0e-2c1a6a4c7f3d")?)
+        );
         assert_eq!(id.x_value(), XValue::from_str("2020-01-02")?);
+        assert_eq!(EventStreamId::from(id).to_string(), s);
+
+        assert!(DataPointId::from_str("").is_err());
+        assert!(DataPointId::from_str("67051e1b-fc32-43c8-899f-e2c73a1319f4").is_err());
+        assert!(
+            DataPointId::from_str("67051e1b-fc32-43c8-899f-e2c73a1319f4:x:2020-01-02").is_err()
+        );
         Ok(())
     }
 }
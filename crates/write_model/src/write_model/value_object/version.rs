@@ -16,6 +16,23 @@ impl Version {
     }
 }
 
+/// A version starts at `1` (see `Version::new`). `0` is rejected
+impl TryFrom<u32> for Version {
+    type Error = Error;
+    fn try_from(n: u32) -> Result<Self, Self::Error> {
+        if n == 0 {
+            return Err(Error);
+        }
+        Ok(Self(n))
+    }
+}
+
+impl From<Version> for u32 {
+    fn from(version: Version) -> Self {
+        version.0
+    }
+}
+
 impl TryFrom<i64> for Version {
     type Error = Error;
     fn try_from(n: i64) -> Result<Self, Self::Error> {
@@ -43,6 +60,15 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_u32_conversion() -> anyhow::Result<()> {
+        assert!(Version::try_from(0_u32).is_err());
+        assert_eq!(u32::from(Version::try_from(1_u32)?), 1_u32);
+        assert_eq!(u32::from(Version::try_from(u32::MAX)?), u32::MAX);
+        assert_eq!(Version::try_from(1_u32)?, Version::new());
+        Ok(())
+    }
+
     #[test]
     fn test_next() -> anyhow::Result<()> {
         let version = Version::new();
@@ -7,17 +7,91 @@ pub struct Error;
 pub struct XValue(u32);
 
 impl XValue {
+    /// Returns the date `days` days after `1970-01-01`. `None` if the date is out of
+    /// `0000-01-01..=9999-12-31`
+    pub fn from_days_since_epoch(days: i32) -> Option<Self> {
+        // <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+        let z = i64::from(days) + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day_of_month = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+        u32::try_from(year * 10000 + month * 100 + day_of_month)
+            .ok()
+            .map(Self)
+    }
+
     pub fn day_of_month(&self) -> u8 {
         (self.0 % 100) as u8
     }
 
+    /// Returns the number of days since `1970-01-01`
+    pub fn days_since_epoch(&self) -> i32 {
+        let days = days_from_civil(
+            i64::from(self.year()),
+            i64::from(self.month()),
+            i64::from(self.day_of_month()),
+        );
+        i32::try_from(days).expect("days of 0000-01-01..=9999-12-31 to fit in i32")
+    }
+
+    /// Returns the ISO week-numbering year and the ISO week number (`1..=53`). The year may
+    /// differ from `year()` around January 1st (e.g. `2021-01-03` is in `2020-W53`)
+    pub fn iso_week(&self) -> (i32, u8) {
+        let year = i32::from(self.year());
+        let week = (i32::from(self.ordinal_day()) + 10 - i32::from(self.weekday())) / 7;
+        if week < 1 {
+            (year - 1, weeks_in_year(year - 1))
+        } else if week > i32::from(weeks_in_year(year)) {
+            (year + 1, 1)
+        } else {
+            (year, week as u8)
+        }
+    }
+
+    /// Returns the following date. `None` after `9999-12-31`
+    pub fn next_day(&self) -> Option<Self> {
+        let (year, month, day_of_month) = (self.year(), self.month(), self.day_of_month());
+        if day_of_month < days_in_month(year, month) {
+            Some(Self(self.0 + 1))
+        } else if month < 12 {
+            Some(Self(
+                u32::from(year) * 10000 + u32::from(month + 1) * 100 + 1,
+            ))
+        } else if year < 9999 {
+            Some(Self(u32::from(year + 1) * 10000 + 101))
+        } else {
+            None
+        }
+    }
+
     pub fn month(&self) -> u8 {
         (self.0 / 100 % 100) as u8
     }
 
+    /// Returns the ISO weekday: `1` (Monday) to `7` (Sunday)
+    pub fn weekday(&self) -> u8 {
+        weekday_from_days(i64::from(self.days_since_epoch()))
+    }
+
     pub fn year(&self) -> u16 {
         (self.0 / 10000) as u16
     }
+
+    /// Returns the day of the year (`1..=366`)
+    fn ordinal_day(&self) -> u16 {
+        (1..self.month())
+            .map(|month| u16::from(days_in_month(self.year(), month)))
+            .sum::<u16>()
+            + u16::from(self.day_of_month())
+    }
 }
 
 impl std::str::FromStr for XValue {
@@ -43,22 +117,7 @@ impl std::str::FromStr for XValue {
         if !(1..=31).contains(&dd) {
             return Err(Error);
         }
-        let is_leap = yyyy % 4 == 0 && (yyyy % 100 != 0 || yyyy % 400 == 0);
-        let max_dd = [
-            31,
-            28 + if is_leap { 1 } else { 0 },
-            31,
-            30,
-            31,
-            30,
-            31,
-            31,
-            30,
-            31,
-            30,
-            31,
-        ][mm as usize - 1];
-        if dd > max_dd {
+        if dd > days_in_month(yyyy, mm) {
             return Err(Error);
         }
 
@@ -68,6 +127,52 @@ impl std::str::FromStr for XValue {
     }
 }
 
+/// Returns the number of days since `1970-01-01`. `year` may be out of `0..=9999`
+fn days_from_civil(year: i64, month: i64, day_of_month: i64) -> i64 {
+    // <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day_of_month - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn weekday_from_days(days: i64) -> u8 {
+    // 1970-01-01 is Thursday
+    (days + 3).rem_euclid(7) as u8 + 1
+}
+
+/// Returns `52` or `53`. `year` may be out of `0..=9999` (the ISO year of `0000-01-01` is `-1`)
+fn weeks_in_year(year: i32) -> u8 {
+    let january_1st = weekday_from_days(days_from_civil(i64::from(year), 1, 1));
+    let is_leap =
+        year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0);
+    if january_1st == 4 || (january_1st == 3 && is_leap) {
+        53
+    } else {
+        52
+    }
+}
+
+fn days_in_month(yyyy: u16, mm: u8) -> u8 {
+    let is_leap = yyyy % 4 == 0 && (yyyy % 100 != 0 || yyyy % 400 == 0);
+    [
+        31,
+        28 + if is_leap { 1 } else { 0 },
+        31,
+        30,
+        31,
+        30,
+        31,
+        31,
+        30,
+        31,
+        30,
+        31,
+    ][mm as usize - 1]
+}
+
 impl std::fmt::Display for XValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         format!(
@@ -86,6 +191,28 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_days_since_epoch() -> anyhow::Result<()> {
+        for (s, days) in [
+            ("1970-01-01", 0),
+            ("1969-12-31", -1),
+            ("2000-03-01", 11017),
+            ("2020-02-29", 18321),
+            ("0000-01-01", -719528),
+        ] {
+            let x_value = XValue::from_str(s)?;
+            assert_eq!(x_value.days_since_epoch(), days);
+            assert_eq!(XValue::from_days_since_epoch(days), Some(x_value));
+        }
+        let last = XValue::from_str("9999-12-31")?;
+        assert_eq!(
+            XValue::from_days_since_epoch(last.days_since_epoch() + 1),
+            None
+        );
+        assert_eq!(XValue::from_days_since_epoch(-719529), None);
+        Ok(())
+    }
+
     #[test]
     fn test_day_of_month() -> anyhow::Result<()> {
         assert_eq!(XValue::from_str("0000-01-01")?.day_of_month(), 1_u8);
@@ -93,6 +220,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_iso_week() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("2020-01-01", (2020, 1)),
+            ("2020-12-31", (2020, 53)),
+            ("2021-01-03", (2020, 53)),
+            ("2021-01-04", (2021, 1)),
+            ("2024-12-30", (2025, 1)),
+            ("2026-10-17", (2026, 42)),
+            ("0000-01-01", (-1, 52)),
+            ("9999-12-31", (9999, 52)),
+        ] {
+            assert_eq!(XValue::from_str(s)?.iso_week(), expected, "{}", s);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_month() -> anyhow::Result<()> {
         assert_eq!(XValue::from_str("0000-01-01")?.month(), 1_u8);
@@ -100,6 +244,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_next_day() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("2020-01-01", "2020-01-02"),
+            ("2020-01-31", "2020-02-01"),
+            ("2020-02-28", "2020-02-29"),
+            ("2021-02-28", "2021-03-01"),
+            ("2020-12-31", "2021-01-01"),
+        ] {
+            assert_eq!(
+                XValue::from_str(s)?.next_day(),
+                Some(XValue::from_str(expected)?)
+            );
+        }
+        assert_eq!(XValue::from_str("9999-12-31")?.next_day(), None);
+        Ok(())
+    }
+
     #[test]
     fn test_string_convesion() -> anyhow::Result<()> {
         assert!(XValue::from_str("0000-00-00").is_err());
@@ -129,6 +291,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_weekday() -> anyhow::Result<()> {
+        for (s, expected) in [
+            ("1970-01-01", 4),
+            ("2020-01-05", 7),
+            ("2020-01-06", 1),
+            ("2026-10-17", 6),
+            ("0000-01-01", 6),
+        ] {
+            assert_eq!(XValue::from_str(s)?.weekday(), expected, "{}", s);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_year() -> anyhow::Result<()> {
         assert_eq!(XValue::from_str("0000-01-01")?.year(), 0_u16);
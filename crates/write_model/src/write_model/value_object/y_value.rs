@@ -1,26 +1,204 @@
+#[derive(Debug, thiserror::Error)]
+#[error("error")]
+pub struct Error;
+
+const FRACTION_DIGITS: usize = 6;
+
+const SCALE: i64 = 1_000_000;
+
 /// Y-value (value)
+///
+/// A signed decimal number with up to 6 fractional digits.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct YValue(u32);
+pub struct YValue(i64);
+
+impl YValue {
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Divides the value by `divisor`, truncating toward zero to 6 fractional digits
+    pub fn checked_div(&self, divisor: u32) -> Option<Self> {
+        self.0.checked_div(i64::from(divisor)).map(Self)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+}
 
 impl From<u32> for YValue {
     fn from(value: u32) -> Self {
-        Self(value)
+        Self(i64::from(value) * SCALE)
     }
 }
 
-impl From<YValue> for u32 {
-    fn from(value: YValue) -> Self {
-        value.0
+impl std::str::FromStr for YValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (is_negative, unsigned) = match s.strip_prefix('-') {
+            None => (false, s),
+            Some(unsigned) => (true, unsigned),
+        };
+        let (integer_part, fraction_part) = match unsigned.split_once('.') {
+            None => (unsigned, None),
+            Some((integer_part, fraction_part)) => (integer_part, Some(fraction_part)),
+        };
+        if integer_part.is_empty() || !integer_part.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error);
+        }
+        let fraction_part = match fraction_part {
+            None => "",
+            Some(fraction_part) => {
+                if fraction_part.is_empty()
+                    || fraction_part.len() > FRACTION_DIGITS
+                    || !fraction_part.chars().all(|c| c.is_ascii_digit())
+                {
+                    return Err(Error);
+                }
+                fraction_part
+            }
+        };
+        let integer = integer_part.parse::<i64>().map_err(|_| Error)?;
+        let fraction = format!("{:0<width$}", fraction_part, width = FRACTION_DIGITS)
+            .parse::<i64>()
+            .map_err(|_| Error)?;
+        let value = integer
+            .checked_mul(SCALE)
+            .and_then(|v| v.checked_add(fraction))
+            .ok_or(Error)?;
+        Ok(Self(if is_negative { -value } else { value }))
+    }
+}
+
+impl std::fmt::Display for YValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let unsigned = self.0.unsigned_abs();
+        let integer = unsigned / SCALE.unsigned_abs();
+        let fraction = unsigned % SCALE.unsigned_abs();
+        if fraction == 0 {
+            format!("{}{}", sign, integer).fmt(f)
+        } else {
+            let fraction = format!("{:0width$}", fraction, width = FRACTION_DIGITS);
+            format!("{}{}.{}", sign, integer, fraction.trim_end_matches('0')).fmt(f)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use super::*;
 
+    #[test]
+    fn test_string_conversion() -> anyhow::Result<()> {
+        for s in [
+            "0",
+            "1",
+            "-1",
+            "123",
+            "0.5",
+            "-0.5",
+            "12.345",
+            "-273.15",
+            "0.000001",
+            "4294967295",
+            "9223372036854.775807",
+            "-9223372036854.775807",
+        ] {
+            assert_eq!(YValue::from_str(s)?.to_string(), s);
+        }
+        for (s, expected) in [
+            ("-0", "0"),
+            ("00123", "123"),
+            ("1.0", "1"),
+            ("1.500000", "1.5"),
+        ] {
+            assert_eq!(YValue::from_str(s)?.to_string(), expected);
+        }
+        for s in [
+            "",
+            "-",
+            ".5",
+            "1.",
+            "+1",
+            "1e3",
+            "1.2.3",
+            " 1",
+            "0.0000001",
+            "9223372036854.775808",
+            "abc",
+        ] {
+            assert!(YValue::from_str(s).is_err(), "{}", s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_add() -> anyhow::Result<()> {
+        assert_eq!(
+            YValue::from_str("1.5")?.checked_add(YValue::from_str("-0.25")?),
+            Some(YValue::from_str("1.25")?)
+        );
+        assert_eq!(
+            YValue::from_str("9223372036854.775807")?.checked_add(YValue::from_str("0.000001")?),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_div() -> anyhow::Result<()> {
+        assert_eq!(
+            YValue::from_str("10")?.checked_div(4),
+            Some(YValue::from_str("2.5")?)
+        );
+        assert_eq!(
+            YValue::from_str("-1")?.checked_div(3),
+            Some(YValue::from_str("-0.333333")?)
+        );
+        assert_eq!(YValue::from_str("1")?.checked_div(0), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_sub() -> anyhow::Result<()> {
+        assert_eq!(
+            YValue::from_str("1.5")?.checked_sub(YValue::from_str("2")?),
+            Some(YValue::from_str("-0.5")?)
+        );
+        assert_eq!(
+            YValue::from_str("-9223372036854.775807")?.checked_sub(YValue::from_str("1")?),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ord() -> anyhow::Result<()> {
+        assert!(YValue::from_str("-1")? < YValue::from_str("-0.5")?);
+        assert!(YValue::from_str("-0.5")? < YValue::from_str("0")?);
+        assert!(YValue::from_str("0.5")? < YValue::from_str("1")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_f64() -> anyhow::Result<()> {
+        assert_eq!(YValue::from_str("-273.15")?.to_f64(), -273.15_f64);
+        assert_eq!(YValue::from(123_u32).to_f64(), 123_f64);
+        Ok(())
+    }
+
     #[test]
     fn test_u32_conversion() {
-        assert_eq!(u32::from(YValue::from(u32::MAX)), u32::MAX);
-        assert_eq!(u32::from(YValue::from(u32::MIN)), u32::MIN);
+        assert_eq!(YValue::from(u32::MAX).to_string(), u32::MAX.to_string());
+        assert_eq!(YValue::from(u32::MIN).to_string(), u32::MIN.to_string());
     }
 }
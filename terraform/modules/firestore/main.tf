@@ -24,3 +24,21 @@ resource "google_firestore_index" "index1" {
     order      = "ASCENDING"
   }
 }
+
+resource "google_firestore_index" "index2" {
+  collection = "data_points"
+  database   = google_firestore_database.default.name
+
+  fields {
+    field_path = "series_id"
+    order      = "ASCENDING"
+  }
+  fields {
+    field_path = "x_value"
+    order      = "ASCENDING"
+  }
+  fields {
+    field_path = "__name__"
+    order      = "ASCENDING"
+  }
+}
@@ -7,3 +7,8 @@ output "index1_name" {
   description = "The name of the created index1"
   value       = google_firestore_index.index1.name
 }
+
+output "index2_name" {
+  description = "The name of the created index2"
+  value       = google_firestore_index.index2.name
+}